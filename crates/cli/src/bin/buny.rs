@@ -77,6 +77,30 @@ enum Command {
 		/// Paths to source packages
 		files: Vec<std::path::PathBuf>,
 	},
+	/// Run a function of a source and print the result
+	Run {
+		/// A .bunpack file or a wasm file
+		path: std::path::PathBuf,
+		/// Print the result as json
+		#[arg(long, global = true)]
+		json: bool,
+		#[command(subcommand)]
+		function: commands::run::RunFunction,
+	},
+	/// Run contract tests against a source package
+	TestContract {
+		/// A .bunpack file or a wasm file
+		path: std::path::PathBuf,
+		/// Search query used to find a novel to test
+		#[arg(short, long)]
+		query: Option<String>,
+		/// Listing url that should resolve to a listing as a deep link
+		#[arg(long)]
+		listing_url: Vec<String>,
+		/// Search url that should resolve to a listing as a deep link
+		#[arg(long)]
+		search_url: Vec<String>,
+	},
 }
 
 #[tokio::main]
@@ -114,6 +138,25 @@ async fn main() -> Result<()> {
 			port,
 		} => commands::serve::run(path, &output, port).await?,
 		Command::Verify { files } => commands::verify::run(files)?,
+		Command::Run {
+			path,
+			json,
+			function,
+		} => {
+			// the source runner makes blocking requests, which can't be done in the async runtime
+			tokio::task::spawn_blocking(move || commands::run::run(path, function, json)).await??
+		}
+		Command::TestContract {
+			path,
+			query,
+			listing_url,
+			search_url,
+		} => {
+			tokio::task::spawn_blocking(move || {
+				commands::test_contract::run(path, query, listing_url, search_url)
+			})
+			.await??
+		}
 	}
 
 	Ok(())
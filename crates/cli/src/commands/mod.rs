@@ -2,5 +2,7 @@ pub mod build;
 pub mod init;
 pub mod logcat;
 pub mod package;
+pub mod run;
 pub mod serve;
+pub mod test_contract;
 pub mod verify;
@@ -0,0 +1,502 @@
+//! Run the functions of a source from the command line.
+use buny::{Chapter, Comment, Filter, FilterKind, FilterValue, Listing, Novel};
+use buny_test_runner::{
+	libs::{
+		format_chapter, format_comment, format_content_block, format_home_layout, format_novel,
+		format_novel_group, format_novel_page, format_reading_progress, format_search_suggestion,
+		format_unlock_option, format_unlock_result,
+	},
+	SourceHost,
+};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+
+// the filters declared in filters.json, with the fields needed to build filter values
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FilterJson {
+	id: Option<String>,
+	title: Option<String>,
+	#[serde(rename = "type")]
+	kind: String,
+	#[serde(default)]
+	options: Vec<String>,
+	ids: Option<Vec<String>>,
+	#[serde(default)]
+	can_exclude: bool,
+}
+
+impl From<FilterJson> for Filter {
+	fn from(value: FilterJson) -> Self {
+		// the id defaults to the title, and then the filter type
+		let id = value
+			.id
+			.or_else(|| value.title.clone())
+			.unwrap_or_else(|| value.kind.clone());
+		let options = value.options.into_iter().map(Into::into).collect();
+		let ids = value
+			.ids
+			.map(|ids| ids.into_iter().map(Into::into).collect());
+		let kind = match value.kind.as_str() {
+			"text" => FilterKind::Text { placeholder: None },
+			"sort" => FilterKind::Sort {
+				can_ascend: true,
+				options,
+				default: None,
+			},
+			"check" => FilterKind::Check {
+				name: None,
+				can_exclude: value.can_exclude,
+				default: None,
+			},
+			"multi-select" => FilterKind::MultiSelect {
+				is_genre: false,
+				can_exclude: value.can_exclude,
+				uses_tag_style: false,
+				options,
+				ids,
+				default_included: None,
+				default_excluded: None,
+			},
+			"range" => FilterKind::Range {
+				min: None,
+				max: None,
+				decimal: false,
+			},
+			"note" => FilterKind::Note("".into()),
+			_ => FilterKind::Select {
+				is_genre: false,
+				uses_tag_style: false,
+				options,
+				ids,
+				default: None,
+			},
+		};
+		Filter {
+			id: id.into(),
+			title: value.title.map(Into::into),
+			hide_from_header: None,
+			kind,
+		}
+	}
+}
+
+#[derive(clap::Subcommand)]
+pub enum RunFunction {
+	/// Search for novels
+	Search {
+		/// Search query
+		#[arg(default_value = "")]
+		query: String,
+		/// Page to fetch
+		#[arg(short, long, default_value_t = 1)]
+		page: i32,
+		/// Filter values, as `id=value` pairs
+		///
+		/// Values are read by the kind of the source's filter with the id: comma separated
+		/// options for multi-select filters (with a `-` prefix to exclude an option),
+		/// `from..to` for range filters, an option with an optional `:asc` suffix for sort
+		/// filters, and a number for check filters. Other values are passed as is, and ids
+		/// without a filter are passed as select filters.
+		#[arg(short, long, value_parser = parse_filter)]
+		filter: Vec<(String, String)>,
+	},
+	/// Fetch the details and chapters of a novel
+	Novel {
+		/// Novel key
+		key: String,
+		/// Fetch the novel details
+		#[arg(short, long)]
+		details: bool,
+		/// Fetch the novel chapters
+		#[arg(short, long)]
+		chapters: bool,
+		/// Page of chapters to fetch
+		#[arg(short, long, default_value_t = 1)]
+		page: i32,
+	},
+	/// Fetch the content of a chapter
+	Chapter {
+		/// Novel key
+		novel: String,
+		/// Chapter key
+		chapter: String,
+	},
+	/// Fetch the home layout
+	Home,
+	/// Fetch the novels of a listing
+	Listing {
+		/// Listing id
+		id: String,
+		/// Page to fetch
+		#[arg(short, long, default_value_t = 1)]
+		page: i32,
+	},
+	/// Fetch the novels in the user's library on the website
+	Library {
+		/// Page to fetch
+		#[arg(short, long, default_value_t = 1)]
+		page: i32,
+	},
+	/// Fetch the comments on a novel or chapter
+	Comments {
+		/// Novel key
+		novel: String,
+		/// Chapter key, to fetch the comments on a chapter
+		#[arg(short, long)]
+		chapter: Option<String>,
+		/// Comment key, to fetch the replies to a comment
+		#[arg(short, long)]
+		replies: Option<String>,
+		/// Page to fetch
+		#[arg(short, long, default_value_t = 1)]
+		page: i32,
+	},
+	/// Fetch the suggested novels for a partial search query
+	Suggestions {
+		/// Partial search query
+		query: String,
+	},
+	/// Fetch the novels related to a novel
+	Related {
+		/// Novel key
+		key: String,
+	},
+	/// Fetch the reading progress of a novel on the website, after marking a chapter as read
+	Progress {
+		/// Novel key
+		novel: String,
+		/// Chapter key, to mark the chapter as read first
+		#[arg(short, long)]
+		chapter: Option<String>,
+		/// Position the chapter was read up to, from 0 (start) to 1 (end)
+		#[arg(long, default_value_t = 1.0)]
+		position: f32,
+	},
+	/// List the ways a locked chapter can be unlocked, or unlock it
+	Unlock {
+		/// Novel key
+		novel: String,
+		/// Chapter key
+		chapter: String,
+		/// Id of the unlock option to unlock the chapter with
+		#[arg(short, long)]
+		option: Option<String>,
+	},
+}
+
+fn parse_filter(value: &str) -> Result<(String, String), String> {
+	let (id, value) = value
+		.split_once('=')
+		.ok_or_else(|| format!("invalid filter `{value}`, expected `id=value`"))?;
+	Ok((id.into(), value.into()))
+}
+
+// returns the id of an option, for filters with separate option ids
+fn option_id(options: &[impl AsRef<str>], ids: Option<&[impl AsRef<str>]>, value: &str) -> String {
+	options
+		.iter()
+		.position(|option| option.as_ref() == value)
+		.and_then(|index| Some(ids?.get(index)?.as_ref().into()))
+		.unwrap_or_else(|| value.into())
+}
+
+/// Builds filter values from `id=value` pairs, using the kinds of the source's filters.
+fn filter_values(
+	filters: &[Filter],
+	pairs: Vec<(String, String)>,
+) -> anyhow::Result<Vec<FilterValue>> {
+	let mut values: Vec<FilterValue> = Vec::new();
+	for (id, value) in pairs {
+		let kind = filters
+			.iter()
+			.find(|filter| filter.id == id)
+			.map(|filter| &filter.kind);
+		let filter_value = match kind {
+			Some(FilterKind::Text { .. }) => FilterValue::Text { id, value },
+			Some(FilterKind::Sort { options, .. }) => {
+				let (option, ascending) = match value.strip_suffix(":asc") {
+					Some(option) => (option, true),
+					None => (value.as_str(), false),
+				};
+				let index = match options.iter().position(|name| name == option) {
+					Some(index) => index as i32,
+					None => option.parse().map_err(|_| {
+						anyhow::anyhow!("unknown sort option `{option}` for `{id}`")
+					})?,
+				};
+				FilterValue::Sort {
+					id,
+					index,
+					ascending,
+				}
+			}
+			Some(FilterKind::Check { .. }) => {
+				let value = value
+					.parse()
+					.map_err(|_| anyhow::anyhow!("invalid check value `{value}` for `{id}`"))?;
+				FilterValue::Check { id, value }
+			}
+			Some(FilterKind::MultiSelect { options, ids, .. }) => {
+				let (mut included, mut excluded) = (Vec::new(), Vec::new());
+				for option in value.split(',').map(str::trim).filter(|o| !o.is_empty()) {
+					match option.strip_prefix('-') {
+						Some(option) => excluded.push(option_id(options, ids.as_deref(), option)),
+						None => included.push(option_id(options, ids.as_deref(), option)),
+					}
+				}
+				// repeated filters add to the same value
+				if let Some(FilterValue::MultiSelect {
+					included: previous_included,
+					excluded: previous_excluded,
+					..
+				}) = values.iter_mut().find(
+					|value| matches!(value, FilterValue::MultiSelect { id: other, .. } if *other == id),
+				) {
+					previous_included.append(&mut included);
+					previous_excluded.append(&mut excluded);
+					continue;
+				}
+				FilterValue::MultiSelect {
+					id,
+					included,
+					excluded,
+				}
+			}
+			Some(FilterKind::Range { .. }) => {
+				let (from, to) = value.split_once("..").ok_or_else(|| {
+					anyhow::anyhow!("invalid range `{value}` for `{id}`, expected `from..to`")
+				})?;
+				let bound = |bound: &str| -> anyhow::Result<Option<f32>> {
+					if bound.is_empty() {
+						Ok(None)
+					} else {
+						Ok(Some(bound.parse().map_err(|_| {
+							anyhow::anyhow!("invalid range bound `{bound}` for `{id}`")
+						})?))
+					}
+				};
+				FilterValue::Range {
+					from: bound(from)?,
+					to: bound(to)?,
+					id,
+				}
+			}
+			Some(FilterKind::Select { options, ids, .. }) => FilterValue::Select {
+				value: option_id(options, ids.as_deref(), &value),
+				id,
+			},
+			Some(FilterKind::Note(_)) | None => FilterValue::Select { id, value },
+		};
+		values.push(filter_value);
+	}
+	Ok(values)
+}
+
+pub fn run(path: std::path::PathBuf, function: RunFunction, json: bool) -> anyhow::Result<()> {
+	let mut source = SourceHost::from_file(&path)?;
+
+	let result = match function {
+		RunFunction::Search {
+			query,
+			page,
+			filter,
+		} => {
+			let query = (!query.is_empty()).then_some(query.as_str());
+			// filters can be declared in filters.json and returned by get_filters
+			let mut filters = Vec::new();
+			if !filter.is_empty() {
+				filters = read_filters(&path)?;
+				if source.has_function("get_filters") {
+					filters.extend(source.filters()?);
+				}
+			}
+			let result = filter_values(&filters, filter)
+				.and_then(|filter| source.search(query, page, &filter));
+			output(result, json, format_novel_page)
+		}
+		RunFunction::Novel {
+			key,
+			details,
+			chapters,
+			page,
+		} => {
+			// fetch everything if nothing specific was requested
+			let (details, chapters) = if !details && !chapters {
+				(true, true)
+			} else {
+				(details, chapters)
+			};
+			let result = source.novel_update(&novel(key), details, chapters, page);
+			output(result, json, |novel| {
+				let mut output = format_novel(novel);
+				for chapter in novel.chapters.iter().flatten() {
+					output.push_str(&format_chapter(chapter));
+					output.push('\n');
+				}
+				output.trim_end().into()
+			})
+		}
+		RunFunction::Chapter {
+			novel: novel_key,
+			chapter: chapter_key,
+		} => {
+			let result = source.chapter_content(&novel(novel_key), &chapter(chapter_key));
+			output(result, json, |blocks| {
+				lines(blocks, format_content_block, "\n\n")
+			})
+		}
+		RunFunction::Home => {
+			let result = source.home();
+			output(result, json, format_home_layout)
+		}
+		RunFunction::Listing { id, page } => {
+			let listing = Listing {
+				name: id.clone(),
+				id,
+				..Default::default()
+			};
+			let result = source.novel_list(&listing, page);
+			output(result, json, format_novel_page)
+		}
+		RunFunction::Library { page } => {
+			let result = source.library(page);
+			output(result, json, format_novel_page)
+		}
+		RunFunction::Comments {
+			novel: novel_key,
+			chapter: chapter_key,
+			replies,
+			page,
+		} => {
+			let novel = novel(novel_key);
+			let chapter = chapter_key.map(chapter);
+			let result = match replies {
+				Some(key) => {
+					let comment = Comment {
+						key,
+						..Default::default()
+					};
+					source.comment_replies(&novel, chapter.as_ref(), &comment, page)
+				}
+				None => source.comments(&novel, chapter.as_ref(), page),
+			};
+			output(result, json, |page| {
+				let mut output = lines(&page.entries, format_comment, "\n");
+				if page.has_next_page {
+					output.push_str("\nhas next page");
+				}
+				output
+			})
+		}
+		RunFunction::Suggestions { query } => {
+			let result = source.search_suggestions(&query);
+			output(result, json, |suggestions| {
+				lines(suggestions, format_search_suggestion, "\n")
+			})
+		}
+		RunFunction::Related { key } => {
+			let result = source.related_novels(&novel(key));
+			output(result, json, |groups| {
+				lines(groups, format_novel_group, "\n")
+			})
+		}
+		RunFunction::Progress {
+			novel: novel_key,
+			chapter: chapter_key,
+			position,
+		} => {
+			let novel = novel(novel_key);
+			let result = match chapter_key {
+				Some(key) => source.mark_chapter_read(&novel, &chapter(key), position),
+				None => Ok(()),
+			}
+			.and_then(|_| source.remote_progress(&novel));
+			output(result, json, |progress| match progress {
+				Some(progress) => format_reading_progress(progress),
+				None => "no progress".into(),
+			})
+		}
+		RunFunction::Unlock {
+			novel: novel_key,
+			chapter: chapter_key,
+			option,
+		} => {
+			let (novel, chapter) = (novel(novel_key), chapter(chapter_key));
+			let options = source.unlock_options(&novel, &chapter);
+			match option {
+				Some(id) => {
+					let result = options.and_then(|options| {
+						let option = options
+							.into_iter()
+							.find(|option| option.id == id)
+							.ok_or_else(|| anyhow::anyhow!("no unlock option with id `{id}`"))?;
+						source.unlock_chapter(&novel, &chapter, &option)
+					});
+					output(result, json, format_unlock_result)
+				}
+				None => output(options, json, |options| {
+					lines(options, format_unlock_option, "\n")
+				}),
+			}
+		}
+	};
+
+	// print the source's logs separately from the result, so json output can be piped
+	let logs = source.take_stdout();
+	if !logs.is_empty() {
+		eprint!("{logs}");
+	}
+
+	println!("{}", result?);
+	Ok(())
+}
+
+// read the filters declared in filters.json, if the path is a package that has them
+fn read_filters(path: &std::path::Path) -> anyhow::Result<Vec<Filter>> {
+	if path.extension().is_some_and(|ext| ext == "wasm") {
+		return Ok(Vec::new());
+	}
+	let file = std::fs::File::open(path)?;
+	let mut archive = zip::read::ZipArchive::new(std::io::BufReader::new(file))?;
+	let mut contents = String::new();
+	match archive.by_name("Payload/filters.json") {
+		Ok(mut file) => file.read_to_string(&mut contents)?,
+		Err(zip::result::ZipError::FileNotFound) => return Ok(Vec::new()),
+		Err(error) => return Err(error.into()),
+	};
+	let filters: Vec<FilterJson> = serde_json::from_str(&contents)?;
+	Ok(filters.into_iter().map(Into::into).collect())
+}
+
+fn output<T: Serialize>(
+	result: anyhow::Result<T>,
+	json: bool,
+	format: impl FnOnce(&T) -> String,
+) -> anyhow::Result<String> {
+	let result = result?;
+	if json {
+		Ok(serde_json::to_string_pretty(&result)?)
+	} else {
+		Ok(format(&result))
+	}
+}
+
+fn novel(key: String) -> Novel {
+	Novel {
+		key,
+		..Default::default()
+	}
+}
+
+fn chapter(key: String) -> Chapter {
+	Chapter {
+		key,
+		..Default::default()
+	}
+}
+
+/// Formats each item and joins them with a separator.
+fn lines<T>(items: &[T], format: impl Fn(&T) -> String, separator: &str) -> String {
+	items.iter().map(format).collect::<Vec<_>>().join(separator)
+}
@@ -0,0 +1,314 @@
+//! Run a battery of contract tests against a source package.
+use anyhow::anyhow;
+use buny::{DeepLinkResult, Listing, ListingKind};
+use buny_test_runner::{
+	libs::{check_chapters, check_content, check_novel_entry, check_novel_page},
+	SourceHost,
+};
+use colored::Colorize;
+use serde::Deserialize;
+use std::io::prelude::*;
+
+// the listings declared in source.json, which can be an id or an object
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ListingJson {
+	Id(String),
+	Object {
+		id: String,
+		name: Option<String>,
+		kind: Option<i32>,
+	},
+}
+
+impl From<ListingJson> for Listing {
+	fn from(value: ListingJson) -> Self {
+		match value {
+			ListingJson::Id(id) => Listing {
+				name: id.clone(),
+				id,
+				kind: ListingKind::Default,
+			},
+			ListingJson::Object { id, name, kind } => Listing {
+				name: name.unwrap_or_else(|| id.clone()),
+				id,
+				kind: if kind == Some(1) {
+					ListingKind::List
+				} else {
+					ListingKind::Default
+				},
+			},
+		}
+	}
+}
+
+#[derive(Deserialize)]
+struct SourceJson {
+	#[serde(default)]
+	listings: Vec<ListingJson>,
+}
+
+#[derive(Default)]
+struct Report {
+	passed: usize,
+	failed: usize,
+}
+
+impl Report {
+	// print the result of a check, given a list of problems or an error
+	fn check(&mut self, name: &str, result: anyhow::Result<Vec<String>>) {
+		print!("  * {name}... ");
+		match result {
+			Ok(problems) if problems.is_empty() => {
+				println!("{}", "ok".green());
+				self.passed += 1;
+			}
+			Ok(problems) => {
+				println!("{}", "failed".red());
+				for problem in problems {
+					println!("    - {problem}");
+				}
+				self.failed += 1;
+			}
+			Err(error) => {
+				println!("{}", "failed".red());
+				for line in format!("{error:#}").lines() {
+					println!("    {line}");
+				}
+				self.failed += 1;
+			}
+		}
+	}
+
+	fn skip(&self, name: &str, reason: &str) {
+		println!("  * {name}... {}", format!("skipped ({reason})").yellow());
+	}
+}
+
+pub fn run(
+	path: std::path::PathBuf,
+	query: Option<String>,
+	listing_urls: Vec<String>,
+	search_urls: Vec<String>,
+) -> anyhow::Result<()> {
+	println!("* Testing {}", path.display());
+
+	let mut host = SourceHost::from_file(&path)?;
+	let mut report = Report::default();
+
+	// search
+	let search = host.search(query.as_deref(), 1, &[]);
+	let entries = search
+		.as_ref()
+		.map(|page| page.entries.clone())
+		.unwrap_or_default();
+	let has_next_page = search.as_ref().is_ok_and(|page| page.has_next_page);
+	report.check(
+		"search returns entries",
+		search.map(|page| {
+			let mut problems = check_novel_page(&page);
+			if page.entries.is_empty() {
+				problems.push("search returned no entries".into());
+			}
+			problems
+		}),
+	);
+	if has_next_page {
+		report.check(
+			"search next page",
+			host.search(query.as_deref(), 2, &[]).map(|page| {
+				let mut problems = check_novel_page(&page);
+				if page.entries.is_empty() {
+					problems
+						.push("first page has a next page, but the second page is empty".into());
+				}
+				problems
+			}),
+		);
+	} else {
+		report.skip("search next page", "no next page");
+	}
+
+	// novel details and chapters
+	let mut novel = None;
+	if let Some(entry) = entries.first() {
+		let update = host.novel_update(entry, true, true, 1);
+		novel = update.as_ref().ok().cloned();
+		report.check(
+			"novel update fills details and chapters",
+			update.map(|novel| {
+				let mut problems = check_novel_entry(&novel);
+				if novel.key != entry.key {
+					problems.push(format!(
+						"novel key changed from {} to {}",
+						entry.key, novel.key
+					));
+				}
+				if novel
+					.chapters
+					.as_ref()
+					.is_none_or(|chapters| chapters.is_empty())
+				{
+					problems.push("novel has no chapters".into());
+				}
+				problems
+			}),
+		);
+	} else {
+		report.skip(
+			"novel update fills details and chapters",
+			"no search entries",
+		);
+	}
+
+	let chapters = novel
+		.as_ref()
+		.and_then(|novel| novel.chapters.clone())
+		.unwrap_or_default();
+	if chapters.is_empty() {
+		report.skip("chapter invariants", "no chapters");
+		report.skip("chapter content", "no chapters");
+	} else {
+		report.check("chapter invariants", Ok(check_chapters(&chapters)));
+
+		// the first chapter that can be read in the app
+		let chapter = chapters.iter().find(|chapter| {
+			!chapter.locked && !chapter.is_volume_header && chapter.external_url.is_none()
+		});
+		if let (Some(novel), Some(chapter)) = (&novel, chapter) {
+			report.check(
+				"chapter content",
+				host.chapter_content(novel, chapter)
+					.map(|blocks| check_content(&blocks)),
+			);
+		} else {
+			report.skip("chapter content", "no readable chapters");
+		}
+	}
+
+	// listings
+	let mut listings = read_listings(&path)?;
+	if host.has_function("get_listings") {
+		match host.listings() {
+			Ok(dynamic) => listings.extend(dynamic),
+			Err(error) => report.check("dynamic listings", Err(error)),
+		}
+	}
+	if listings.is_empty() {
+		report.skip("listings", "no listings");
+	}
+	for listing in listings {
+		report.check(
+			&format!("listing {} loads", listing.id),
+			host.novel_list(&listing, 1)
+				.map(|page| check_novel_page(&page)),
+		);
+	}
+
+	// deep links, reporting each pattern that isn't checked
+	if !host.has_function("handle_deep_link") {
+		report.skip("deep links", "no deep link handler");
+	} else {
+		match novel.as_ref().map(|novel| (novel, &novel.url)) {
+			Some((novel, Some(url))) => {
+				let expected = DeepLinkResult::Novel {
+					key: novel.key.clone(),
+				};
+				report.check(
+					"novel url deep link",
+					check_deep_link(&mut host, url, expected),
+				);
+			}
+			Some((_, None)) => report.skip("novel url deep link", "novel has no url"),
+			None => report.skip("novel url deep link", "no novel"),
+		}
+		match (
+			&novel,
+			chapters.iter().find(|chapter| chapter.url.is_some()),
+		) {
+			(Some(novel), Some(chapter)) => {
+				let expected = DeepLinkResult::Chapter {
+					novel_key: novel.key.clone(),
+					key: chapter.key.clone(),
+				};
+				report.check(
+					"chapter url deep link",
+					check_deep_link(
+						&mut host,
+						chapter.url.as_deref().unwrap_or_default(),
+						expected,
+					),
+				);
+			}
+			(Some(_), None) => report.skip("chapter url deep link", "no chapters with urls"),
+			(None, _) => report.skip("chapter url deep link", "no novel"),
+		}
+		for (kind, urls, flag) in [
+			("listing", &listing_urls, "--listing-url"),
+			("search", &search_urls, "--search-url"),
+		] {
+			if urls.is_empty() {
+				report.skip(
+					&format!("{kind} url deep link"),
+					&format!("no urls, pass them with {flag}"),
+				);
+			}
+			for url in urls {
+				report.check(
+					&format!("{kind} url {url} deep link"),
+					check_listing_deep_link(&mut host, url),
+				);
+			}
+		}
+	}
+
+	println!(
+		"\n{} passed, {} failed",
+		report.passed.to_string().green(),
+		report.failed.to_string().red()
+	);
+	if report.failed > 0 {
+		Err(anyhow!("contract tests failed"))
+	} else {
+		Ok(())
+	}
+}
+
+// check that a url resolves to the expected deep link result
+fn check_deep_link(
+	host: &mut SourceHost,
+	url: &str,
+	expected: DeepLinkResult,
+) -> anyhow::Result<Vec<String>> {
+	Ok(match host.deep_link(url)? {
+		Some(result) if result == expected => Vec::new(),
+		Some(result) => vec![format!(
+			"{url} resolved to {result:?}, expected {expected:?}"
+		)],
+		None => vec![format!("{url} didn't resolve")],
+	})
+}
+
+// read the listings declared in source.json, if the path is a package
+fn read_listings(path: &std::path::Path) -> anyhow::Result<Vec<Listing>> {
+	if path.extension().is_some_and(|ext| ext == "wasm") {
+		return Ok(Vec::new());
+	}
+	let file = std::fs::File::open(path)?;
+	let mut archive = zip::read::ZipArchive::new(std::io::BufReader::new(file))?;
+	let mut contents = String::new();
+	archive
+		.by_name("Payload/source.json")?
+		.read_to_string(&mut contents)?;
+	let source_json: SourceJson = serde_json::from_str(&contents)?;
+	Ok(source_json.listings.into_iter().map(Into::into).collect())
+}
+
+// check that a listing or search url resolves to a listing that loads
+fn check_listing_deep_link(host: &mut SourceHost, url: &str) -> anyhow::Result<Vec<String>> {
+	Ok(match host.deep_link(url)? {
+		Some(DeepLinkResult::Listing(listing)) => check_novel_page(&host.novel_list(&listing, 1)?),
+		Some(result) => vec![format!("{url} resolved to {result:?}, expected a listing")],
+		None => vec![format!("{url} didn't resolve")],
+	})
+}
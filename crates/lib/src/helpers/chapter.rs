@@ -0,0 +1,353 @@
+//! Parsing of volume and chapter numbers from chapter titles.
+//!
+//! Sites usually include the numbers of a chapter in its title, such as
+//! "Vol. 3 Chapter 12.5: Title", "Ch 12 (Part 2)", "第一百二十三章 标题" or "Episode 45".
+//! [ChapterTitle] splits these titles into their numbers and the remaining title.
+//!
+//! # Examples
+//! ```
+//! use buny::{helpers::chapter::ChapterTitle, Chapter};
+//! let mut chapter = Chapter {
+//!     title: Some("Vol. 3 Chapter 12.5: The Title".into()),
+//!     ..Default::default()
+//! };
+//! ChapterTitle::parse(chapter.title.as_deref().unwrap_or_default()).apply_to(&mut chapter);
+//! assert_eq!(chapter.volume_number, Some(3.0));
+//! assert_eq!(chapter.chapter_number, Some(12.5));
+//! assert_eq!(chapter.title, Some("The Title".into()));
+//! ```
+extern crate alloc;
+
+use crate::Chapter;
+use alloc::string::{String, ToString};
+
+// keywords that are followed by a volume number, longest first
+const VOLUME_KEYWORDS: &[&str] = &["volume", "tome", "tomo", "book", "vol"];
+// keywords that are followed by a chapter number, longest first
+const CHAPTER_KEYWORDS: &[&str] = &[
+	"chapitre",
+	"capítulo",
+	"capitulo",
+	"episode",
+	"chapter",
+	"chương",
+	"глава",
+	"chap",
+	"cap",
+	"bab",
+	"ep",
+	"ch",
+];
+const PART_KEYWORDS: &[&str] = &["partie", "parte", "part", "pt"];
+
+// the units that follow volume and chapter numbers in chinese, japanese and korean titles
+const CJK_VOLUME_UNITS: &[char] = &['卷', '巻', '册', '冊', '部', '권'];
+const CJK_CHAPTER_UNITS: &[char] = &['章', '话', '話', '回', '节', '節', '集', '화', '장'];
+
+/// The volume and chapter numbers of a chapter title, and the title without them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChapterTitle {
+	pub volume_number: Option<f32>,
+	pub chapter_number: Option<f32>,
+	/// The part of a chapter that is split into several parts, e.g. `(Part 2)` or `（下）`.
+	pub part_number: Option<f32>,
+	/// The title after the numbers, or `None` if there's no other text.
+	pub title: Option<String>,
+}
+
+impl ChapterTitle {
+	/// Parses the numbers at the start of a chapter title.
+	///
+	/// If the title doesn't start with a chapter number, the chapter number is read from a
+	/// chapter keyword anywhere in the title, and the title is kept as it is.
+	///
+	/// # Examples
+	/// ```
+	/// use buny::helpers::chapter::ChapterTitle;
+	/// let title = ChapterTitle::parse("Ch 12 (Part 2)");
+	/// assert_eq!(title.chapter_number, Some(12.0));
+	/// assert_eq!(title.part_number, Some(2.0));
+	/// assert_eq!(title.title, None);
+	///
+	/// let title = ChapterTitle::parse("第一百二十三章 标题");
+	/// assert_eq!(title.chapter_number, Some(123.0));
+	/// assert_eq!(title.title, Some("标题".into()));
+	///
+	/// let title = ChapterTitle::parse("Side Story - Episode 45");
+	/// assert_eq!(title.chapter_number, Some(45.0));
+	/// assert_eq!(title.title, Some("Side Story - Episode 45".into()));
+	/// ```
+	pub fn parse<T: AsRef<str>>(title: T) -> Self {
+		let title = normalize_digits(title.as_ref().trim());
+		let mut result = Self::default();
+
+		let mut rest = title.as_str();
+		loop {
+			let next = skip_separators(rest, true);
+			if result.volume_number.is_none() && result.chapter_number.is_none() {
+				if let Some((number, after)) = read_volume(next) {
+					result.volume_number = Some(number);
+					rest = after;
+					continue;
+				}
+			}
+			if result.chapter_number.is_none() {
+				if let Some((number, after)) = read_chapter(next) {
+					result.chapter_number = Some(number);
+					rest = after;
+					continue;
+				}
+			}
+			if result.chapter_number.is_some() && result.part_number.is_none() {
+				if let Some((number, after)) = read_part(rest) {
+					result.part_number = Some(number);
+					rest = after;
+					continue;
+				}
+			}
+			break;
+		}
+
+		if result.chapter_number.is_none() && result.volume_number.is_none() {
+			// titles that are only numbered, e.g. "12. Title" or "12", but not titles that start
+			// with a number, e.g. "2024 Recap"
+			if let Some((number, after)) = read_number(rest) {
+				if after.is_empty() || after.trim_start().starts_with(['.', ':', '-']) {
+					result.chapter_number = Some(number);
+					rest = after;
+				}
+			}
+		}
+		if result.chapter_number.is_none() {
+			result.chapter_number = find_chapter(&title);
+		}
+
+		let rest = skip_separators(rest, false).trim_end();
+		result.title = (!rest.is_empty()).then(|| rest.to_string());
+		result
+	}
+
+	/// Fills in the volume and chapter numbers missing from a chapter, and replaces its title
+	/// with the title without the numbers.
+	pub fn apply_to(self, chapter: &mut Chapter) {
+		chapter.volume_number = chapter.volume_number.or(self.volume_number);
+		chapter.chapter_number = chapter.chapter_number.or(self.chapter_number);
+		chapter.title = self.title;
+	}
+}
+
+/// Removes the volume and chapter numbers from the start of a chapter title.
+///
+/// Returns `None` if the title only contains the numbers.
+///
+/// # Examples
+/// ```
+/// use buny::helpers::chapter::normalize_chapter_title;
+/// assert_eq!(normalize_chapter_title("Chapter 12 - The Title"), Some("The Title".into()));
+/// assert_eq!(normalize_chapter_title("第三卷 第十二章：标题"), Some("标题".into()));
+/// assert_eq!(normalize_chapter_title("Prologue"), Some("Prologue".into()));
+/// assert_eq!(normalize_chapter_title("Episode 45"), None);
+/// ```
+pub fn normalize_chapter_title<T: AsRef<str>>(title: T) -> Option<String> {
+	ChapterTitle::parse(title).title
+}
+
+fn normalize_digits(text: &str) -> String {
+	text.chars()
+		.map(|c| match c {
+			'０'..='９' => char::from_u32(c as u32 - '０' as u32 + '0' as u32).unwrap_or(c),
+			'．' => '.',
+			c => c,
+		})
+		.collect()
+}
+
+// skips the punctuation between the numbers of a title, and opening brackets if they're
+// followed by another number
+fn skip_separators(text: &str, opening: bool) -> &str {
+	text.trim_start_matches(|c: char| {
+		c.is_whitespace()
+			|| ":-–—.|,·、：，]】)）」』".contains(c)
+			|| (opening && "[【(（「『".contains(c))
+	})
+}
+
+// removes a keyword from the start of the text, ignoring case
+fn strip_keyword<'a>(text: &'a str, keyword: &str) -> Option<&'a str> {
+	let mut chars = text.chars();
+	for c in keyword.chars() {
+		if !chars.next()?.to_lowercase().eq(c.to_lowercase()) {
+			return None;
+		}
+	}
+	Some(chars.as_str())
+}
+
+// reads a keyword followed by a number, e.g. "Vol. 3", "ch12" or "c12"
+fn read_keyword_number<'a>(
+	text: &'a str,
+	keywords: &[&str],
+	letter: char,
+) -> Option<(f32, &'a str)> {
+	for keyword in keywords {
+		let Some(rest) = strip_keyword(text, keyword) else {
+			continue;
+		};
+		let rest = rest.strip_prefix('.').unwrap_or(rest).trim_start();
+		if let Some(number) = read_number(rest) {
+			return Some(number);
+		}
+	}
+	// a single letter is only a keyword if it's directly followed by the number, e.g. "V2C3"
+	let rest = strip_keyword(text, letter.encode_utf8(&mut [0; 4]))?;
+	rest.starts_with(|c: char| c.is_ascii_digit())
+		.then(|| read_number(rest))
+		.flatten()
+}
+
+// reads a number followed by a unit, e.g. "第十二章" or "제12화"
+fn read_cjk_number<'a>(text: &'a str, units: &[char]) -> Option<(f32, &'a str)> {
+	let rest = text
+		.strip_prefix('第')
+		.or_else(|| text.strip_prefix('제'))
+		.unwrap_or(text)
+		.trim_start();
+	let (number, rest) = read_number(rest)?;
+	let rest = rest.trim_start();
+	let unit = rest.chars().next().filter(|c| units.contains(c))?;
+	Some((number, &rest[unit.len_utf8()..]))
+}
+
+fn read_volume(text: &str) -> Option<(f32, &str)> {
+	read_keyword_number(text, VOLUME_KEYWORDS, 'v')
+		.or_else(|| read_cjk_number(text, CJK_VOLUME_UNITS))
+}
+
+fn read_chapter(text: &str) -> Option<(f32, &str)> {
+	read_keyword_number(text, CHAPTER_KEYWORDS, 'c')
+		.or_else(|| read_cjk_number(text, CJK_CHAPTER_UNITS))
+}
+
+// reads the part of a chapter, e.g. "Part 2", "(2)", "(2/3)" or "（下）"
+fn read_part(text: &str) -> Option<(f32, &str)> {
+	let text = skip_separators(text, false);
+	let Some(inner) = text.strip_prefix(['(', '（', '[']) else {
+		return PART_KEYWORDS.iter().find_map(|keyword| {
+			let rest = strip_keyword(text, keyword)?;
+			let rest = rest.strip_prefix('.').unwrap_or(rest).trim_start();
+			rest.starts_with(|c: char| c.is_ascii_digit())
+				.then(|| read_number(rest))?
+		});
+	};
+	let (number, rest) = match inner.chars().next()? {
+		'上' => (1.0, &inner['上'.len_utf8()..]),
+		'中' => (2.0, &inner['中'.len_utf8()..]),
+		'下' => (3.0, &inner['下'.len_utf8()..]),
+		_ => read_part(inner).or_else(|| {
+			let (number, rest) = read_number(inner)?;
+			// the total number of parts, e.g. "(2/3)"
+			let rest = match rest.strip_prefix('/') {
+				Some(total) => read_number(total)?.1,
+				None => rest,
+			};
+			Some((number, rest))
+		})?,
+	};
+	let rest = rest.trim_start().strip_prefix([')', '）', ']'])?;
+	Some((number, rest))
+}
+
+// reads a number written with digits or chinese numerals
+fn read_number(text: &str) -> Option<(f32, &str)> {
+	let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+	if digits == 0 {
+		return read_cjk_numerals(text).map(|(number, rest)| (number as f32, rest));
+	}
+	// include a decimal part, e.g. "12.5", but not the period after a number, e.g. "12. Title"
+	let len = match text[digits..].strip_prefix('.') {
+		Some(decimal) if decimal.starts_with(|c: char| c.is_ascii_digit()) => {
+			digits + 1 + decimal.len()
+				- decimal
+					.trim_start_matches(|c: char| c.is_ascii_digit())
+					.len()
+		}
+		_ => digits,
+	};
+	Some((text[..len].parse().ok()?, &text[len..]))
+}
+
+// reads a number written with chinese numerals, e.g. "一百二十三", "两万" or "二〇五", which is
+// also used for the counts of relative dates
+pub(crate) fn read_cjk_numerals(text: &str) -> Option<(u32, &str)> {
+	let mut total: u32 = 0;
+	// the part of the number below ten thousand
+	let mut section: u32 = 0;
+	let mut digit: Option<u32> = None;
+	let mut len = 0;
+	// numbers too large to be chapter numbers are rejected instead of overflowing
+	for c in text.chars() {
+		let multiplier = match c {
+			'十' | '拾' => 10,
+			'百' | '佰' => 100,
+			'千' | '仟' => 1000,
+			'万' | '萬' => {
+				let value = section.checked_add(digit.take().unwrap_or(0))?.max(1);
+				total = total.checked_add(value.checked_mul(10000)?)?;
+				section = 0;
+				len += c.len_utf8();
+				continue;
+			}
+			c => {
+				let Some(value) = cjk_digit(c) else {
+					break;
+				};
+				// numbers written digit by digit, e.g. "二〇五"
+				digit = Some(match digit {
+					Some(digit) => digit.checked_mul(10)?.checked_add(value)?,
+					None => value,
+				});
+				len += c.len_utf8();
+				continue;
+			}
+		};
+		section = section.checked_add(digit.take().unwrap_or(1).checked_mul(multiplier)?)?;
+		len += c.len_utf8();
+	}
+	let number = total
+		.checked_add(section)?
+		.checked_add(digit.unwrap_or(0))?;
+	(len > 0).then(|| (number, &text[len..]))
+}
+
+fn cjk_digit(c: char) -> Option<u32> {
+	let digit = match c {
+		'〇' | '零' => 0,
+		'一' | '壹' => 1,
+		'二' | '两' | '兩' | '贰' | '貳' => 2,
+		'三' | '叁' | '參' => 3,
+		'四' | '肆' => 4,
+		'五' | '伍' => 5,
+		'六' | '陆' | '陸' => 6,
+		'七' | '柒' => 7,
+		'八' | '捌' => 8,
+		'九' | '玖' => 9,
+		_ => return None,
+	};
+	Some(digit)
+}
+
+// finds a chapter keyword followed by a number anywhere in a title
+fn find_chapter(title: &str) -> Option<f32> {
+	title.char_indices().find_map(|(idx, c)| {
+		let rest = &title[idx..];
+		let previous = title[..idx].chars().next_back();
+		// keywords have to start a word, but cjk numbers can be anywhere
+		if previous.is_some_and(char::is_alphanumeric) && (c == '第' || c == '제') {
+			return read_cjk_number(rest, CJK_CHAPTER_UNITS).map(|(number, _)| number);
+		}
+		if previous.is_some_and(char::is_alphanumeric) {
+			return None;
+		}
+		read_chapter(rest).map(|(number, _)| number)
+	})
+}
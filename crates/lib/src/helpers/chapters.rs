@@ -0,0 +1,242 @@
+//! Normalization of scraped chapter lists.
+//!
+//! Chapter lists scraped from websites often contain duplicates, are ordered oldest-first,
+//! or are missing chapter numbers. The app expects chapters to be unique and ordered from
+//! newest to oldest, so these functions should be applied before returning a chapter list.
+//!
+//! # Examples
+//! ```
+//! use buny::{helpers::chapters, Chapter};
+//! let chapter = |key: &str, title: &str| Chapter {
+//!     key: key.into(),
+//!     title: Some(title.into()),
+//!     ..Default::default()
+//! };
+//! let mut list = vec![
+//!     chapter("/1", "Chapter 1"),
+//!     chapter("/2", "Chapter 2"),
+//!     chapter("/2", "Chapter 2"),
+//!     chapter("/3", "Extra"),
+//! ];
+//! chapters::normalize(&mut list);
+//! chapters::set_language(&mut list, "en");
+//! assert_eq!(
+//!     list.iter().map(|chapter| chapter.chapter_number).collect::<Vec<_>>(),
+//!     [Some(3.0), Some(2.0), Some(1.0)]
+//! );
+//! assert!(list.iter().all(|chapter| chapter.language.as_deref() == Some("en")));
+//! ```
+extern crate alloc;
+
+use super::chapter::ChapterTitle;
+use crate::{Chapter, Novel};
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+use core::cmp::Ordering;
+
+/// Removes duplicates, orders the chapters from newest to oldest and fills in missing chapter
+/// numbers.
+///
+/// This applies [dedupe], [sort_newest_first] and [infer_chapter_numbers].
+pub fn normalize(chapters: &mut Vec<Chapter>) {
+	dedupe(chapters);
+	// the numbers in the titles are needed to find the order of the chapters
+	numbers_from_titles(chapters);
+	sort_newest_first(chapters);
+	infer_chapter_numbers(chapters);
+}
+
+/// Removes chapters with the same key as an earlier chapter.
+pub fn dedupe(chapters: &mut Vec<Chapter>) {
+	let mut keys = BTreeSet::new();
+	chapters.retain(|chapter| keys.insert(chapter.key.clone()));
+}
+
+/// Orders the chapters from newest to oldest.
+///
+/// Lists that are ordered from oldest to newest, judging by their chapter numbers or upload
+/// dates, are reversed. If every chapter has a chapter number, the chapters are then sorted
+/// by their volume and chapter numbers, keeping the order of chapters with equal numbers.
+///
+/// # Examples
+/// ```
+/// use buny::{helpers::chapters::sort_newest_first, Chapter};
+/// let mut chapters = [1.0, 3.0, 2.0].map(|number| Chapter {
+///     chapter_number: Some(number),
+///     ..Default::default()
+/// });
+/// sort_newest_first(&mut chapters);
+/// assert_eq!(
+///     chapters.map(|chapter| chapter.chapter_number),
+///     [Some(3.0), Some(2.0), Some(1.0)]
+/// );
+/// ```
+pub fn sort_newest_first(chapters: &mut [Chapter]) {
+	let mut numbers = chapters.iter().filter_map(|chapter| {
+		chapter
+			.chapter_number
+			.map(|number| (chapter.volume_number, number))
+	});
+	let ascending = match (numbers.next(), numbers.next_back()) {
+		(Some(first), Some(last)) if first != last => first < last,
+		_ => {
+			let mut dates = chapters.iter().filter_map(|chapter| chapter.date_uploaded);
+			matches!((dates.next(), dates.next_back()), (Some(first), Some(last)) if first < last)
+		}
+	};
+	if ascending {
+		chapters.reverse();
+	}
+
+	if chapters
+		.iter()
+		.all(|chapter| chapter.chapter_number.is_some())
+	{
+		// chapter numbers can restart in each volume, so volumes are only compared if every
+		// chapter has one
+		let volumes = chapters
+			.iter()
+			.all(|chapter| chapter.volume_number.is_some());
+		chapters.sort_by(|a, b| {
+			let volume = if volumes {
+				compare(b.volume_number, a.volume_number)
+			} else {
+				Ordering::Equal
+			};
+			volume.then_with(|| compare(b.chapter_number, a.chapter_number))
+		});
+	}
+}
+
+/// Fills in missing chapter numbers of a list ordered from newest to oldest.
+///
+/// Numbers are read from the chapter titles with [ChapterTitle] first. Any chapters that are
+/// still missing a number are numbered after the previous chapter, as long as that number is
+/// lower than the number of the next chapter. Volume headers are not numbered.
+///
+/// # Examples
+/// ```
+/// use buny::{helpers::chapters::infer_chapter_numbers, Chapter};
+/// let mut chapters = [Some("Chapter 3"), None, Some("Intermission"), Some("Chapter 1")]
+///     .map(|title| Chapter {
+///         title: title.map(String::from),
+///         ..Default::default()
+///     });
+/// infer_chapter_numbers(&mut chapters);
+/// assert_eq!(
+///     chapters.map(|chapter| chapter.chapter_number),
+///     [Some(3.0), None, Some(2.0), Some(1.0)]
+/// );
+/// ```
+pub fn infer_chapter_numbers(chapters: &mut [Chapter]) {
+	numbers_from_titles(chapters);
+
+	// the number of the closest newer chapter with a number
+	let mut next_numbers = Vec::with_capacity(chapters.len());
+	let mut next = None;
+	for chapter in chapters.iter() {
+		next_numbers.push(next);
+		next = chapter.chapter_number.or(next);
+	}
+
+	let mut previous = 0.0;
+	for (chapter, next) in chapters.iter_mut().zip(next_numbers).rev() {
+		if chapter.is_volume_header {
+			continue;
+		}
+		match chapter.chapter_number {
+			Some(number) => previous = number,
+			None => {
+				let number = (previous as i64 + 1) as f32;
+				if next.is_none_or(|next| number < next) {
+					chapter.chapter_number = Some(number);
+					previous = number;
+				}
+			}
+		}
+	}
+}
+
+/// Combines pages of chapters into a single list, removing chapters that appear on several
+/// pages.
+///
+/// # Examples
+/// ```
+/// use buny::{helpers::chapters::merge_pages, Chapter};
+/// let chapter = |key: &str| Chapter {
+///     key: key.into(),
+///     ..Default::default()
+/// };
+/// let chapters = merge_pages([
+///     vec![chapter("/4"), chapter("/3")],
+///     vec![chapter("/3"), chapter("/2"), chapter("/1")],
+/// ]);
+/// assert_eq!(
+///     chapters.iter().map(|chapter| chapter.key.as_str()).collect::<Vec<_>>(),
+///     ["/4", "/3", "/2", "/1"]
+/// );
+/// ```
+pub fn merge_pages<I: IntoIterator<Item = Vec<Chapter>>>(pages: I) -> Vec<Chapter> {
+	let mut chapters = pages.into_iter().flatten().collect();
+	dedupe(&mut chapters);
+	chapters
+}
+
+/// Fetches every page of chapters for a novel, starting from page 1.
+///
+/// Pages are requested until a page doesn't set `has_more_chapters`, or a page doesn't
+/// contain any new chapters. The chapters of each page are merged with [merge_pages].
+///
+/// # Examples
+/// ```ignore
+/// use buny::helpers::chapters::fetch_pages;
+/// let chapters = fetch_pages(|page| {
+///     self.get_novel_update(novel.clone(), false, true, page)
+/// })?;
+/// ```
+pub fn fetch_pages<E, F: FnMut(i32) -> Result<Novel, E>>(mut fetch: F) -> Result<Vec<Chapter>, E> {
+	let mut chapters = Vec::new();
+	let mut keys = BTreeSet::new();
+	let mut page = 1;
+	loop {
+		let novel = fetch(page)?;
+		let count = chapters.len();
+		for chapter in novel.chapters.unwrap_or_default() {
+			if keys.insert(chapter.key.clone()) {
+				chapters.push(chapter);
+			}
+		}
+		if novel.has_more_chapters != Some(true) || chapters.len() == count {
+			break;
+		}
+		page += 1;
+	}
+	Ok(chapters)
+}
+
+/// Sets the language of the chapters that don't have one.
+pub fn set_language<T: Into<String>>(chapters: &mut [Chapter], language: T) {
+	let language = language.into();
+	for chapter in chapters
+		.iter_mut()
+		.filter(|chapter| chapter.language.is_none())
+	{
+		chapter.language = Some(language.clone());
+	}
+}
+
+fn numbers_from_titles(chapters: &mut [Chapter]) {
+	for chapter in chapters.iter_mut() {
+		if chapter.chapter_number.is_some() || chapter.is_volume_header {
+			continue;
+		}
+		if let Some(title) = &chapter.title {
+			let title = ChapterTitle::parse(title);
+			chapter.chapter_number = title.chapter_number;
+			chapter.volume_number = chapter.volume_number.or(title.volume_number);
+		}
+	}
+}
+
+fn compare(a: Option<f32>, b: Option<f32>) -> Ordering {
+	a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+}
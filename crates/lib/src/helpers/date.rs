@@ -0,0 +1,534 @@
+//! Parsing of the dates that sites show for chapters and updates.
+//!
+//! Besides absolute dates, sites often show how long ago something happened, such as
+//! "3 hours ago", "yesterday", "2 週間前" or "3天前". [parse_relative_date] reads these
+//! phrases in English, Spanish, Portuguese, French, Indonesian, Vietnamese, Chinese,
+//! Japanese, Korean and Russian, and [DateParser] falls back to absolute date formats.
+//!
+//! # Examples
+//! ```ignore
+//! use buny::helpers::date::DateParser;
+//! let parser = DateParser::new().format("MMMM d, yyyy").format("dd/MM/yyyy");
+//! let date_uploaded = parser.parse(&date_text);
+//! ```
+extern crate alloc;
+
+use super::chapter::read_cjk_numerals;
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+
+#[cfg(feature = "imports")]
+use crate::imports::std::{current_date, parse_date_with_options};
+
+const MINUTE: i64 = 60;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+const WEEK: i64 = 7 * DAY;
+const MONTH: i64 = 30 * DAY;
+const YEAR: i64 = 365 * DAY;
+
+// phrases that are a fixed amount of time ago
+const PHRASES: &[(&str, i64)] = &[
+	// now
+	("just now", 0),
+	("now", 0),
+	("a moment ago", 0),
+	("moments ago", 0),
+	("ahora", 0),
+	("justo ahora", 0),
+	("hace un momento", 0),
+	("agora", 0),
+	("agora mesmo", 0),
+	("há pouco", 0),
+	("à l'instant", 0),
+	("maintenant", 0),
+	("baru saja", 0),
+	("sekarang", 0),
+	("vừa xong", 0),
+	("vừa mới", 0),
+	("bây giờ", 0),
+	("刚刚", 0),
+	("剛剛", 0),
+	("刚才", 0),
+	("たった今", 0),
+	("今", 0),
+	("방금", 0),
+	("방금 전", 0),
+	("только что", 0),
+	("сейчас", 0),
+	// today
+	("today", 0),
+	("hoy", 0),
+	("hoje", 0),
+	("aujourd'hui", 0),
+	("hari ini", 0),
+	("hôm nay", 0),
+	("今天", 0),
+	("今日", 0),
+	("오늘", 0),
+	("сегодня", 0),
+	// yesterday
+	("yesterday", DAY),
+	("ayer", DAY),
+	("ontem", DAY),
+	("hier", DAY),
+	("kemarin", DAY),
+	("hôm qua", DAY),
+	("昨天", DAY),
+	("昨日", DAY),
+	("어제", DAY),
+	("вчера", DAY),
+	// the day before yesterday
+	("anteayer", 2 * DAY),
+	("antes de ayer", 2 * DAY),
+	("anteontem", 2 * DAY),
+	("avant-hier", 2 * DAY),
+	("kemarin lusa", 2 * DAY),
+	("hôm kia", 2 * DAY),
+	("前天", 2 * DAY),
+	("一昨日", 2 * DAY),
+	("おととい", 2 * DAY),
+	("그저께", 2 * DAY),
+	("그제", 2 * DAY),
+	("позавчера", 2 * DAY),
+	// the previous week, month or year
+	("la semana pasada", WEEK),
+	("el mes pasado", MONTH),
+	("el año pasado", YEAR),
+	("semana passada", WEEK),
+	("mês passado", MONTH),
+	("ano passado", YEAR),
+	("la semaine dernière", WEEK),
+	("le mois dernier", MONTH),
+	("l'année dernière", YEAR),
+	("l'an dernier", YEAR),
+	("上周", WEEK),
+	("上週", WEEK),
+	("上个月", MONTH),
+	("上個月", MONTH),
+	("去年", YEAR),
+	("先週", WEEK),
+	("先月", MONTH),
+	("昨年", YEAR),
+	("지난주", WEEK),
+	("지난달", MONTH),
+	("작년", YEAR),
+	("на прошлой неделе", WEEK),
+	("в прошлом месяце", MONTH),
+	("в прошлом году", YEAR),
+];
+
+// words that mark an amount of time as being in the past, which are removed before reading it
+const PREFIXES: &[&str] = &["hace ", "há ", "ha ", "il y a ", "last "];
+const SUFFIXES: &[&str] = &[
+	" ago",
+	" atrás",
+	" yang lalu",
+	" lalu",
+	" trước",
+	"之前",
+	"以前",
+	"前",
+	" 전",
+	"전",
+	" назад",
+];
+
+// words for small numbers, which have to be followed by a space (apart from the indonesian "se")
+const NUMBERS: &[(&str, i64)] = &[
+	("a", 1),
+	("an", 1),
+	("one", 1),
+	("un", 1),
+	("una", 1),
+	("uno", 1),
+	("um", 1),
+	("uma", 1),
+	("une", 1),
+	("một", 1),
+	("hai", 2),
+	("ba", 3),
+	("한", 1),
+	("두", 2),
+	("세", 3),
+	("네", 4),
+	("один", 1),
+	("одна", 1),
+	("одну", 1),
+	("одно", 1),
+	("два", 2),
+	("две", 2),
+	("три", 3),
+	("пару", 2),
+];
+
+const UNITS: &[(&str, i64)] = &[
+	// english
+	("s", 1),
+	("sec", 1),
+	("secs", 1),
+	("second", 1),
+	("seconds", 1),
+	("m", MINUTE),
+	("min", MINUTE),
+	("mins", MINUTE),
+	("minute", MINUTE),
+	("minutes", MINUTE),
+	("h", HOUR),
+	("hr", HOUR),
+	("hrs", HOUR),
+	("hour", HOUR),
+	("hours", HOUR),
+	("d", DAY),
+	("day", DAY),
+	("days", DAY),
+	("w", WEEK),
+	("wk", WEEK),
+	("wks", WEEK),
+	("week", WEEK),
+	("weeks", WEEK),
+	("mo", MONTH),
+	("mos", MONTH),
+	("month", MONTH),
+	("months", MONTH),
+	("y", YEAR),
+	("yr", YEAR),
+	("yrs", YEAR),
+	("year", YEAR),
+	("years", YEAR),
+	// spanish and portuguese
+	("segundo", 1),
+	("segundos", 1),
+	("minuto", MINUTE),
+	("minutos", MINUTE),
+	("hora", HOUR),
+	("horas", HOUR),
+	("día", DAY),
+	("días", DAY),
+	("dia", DAY),
+	("dias", DAY),
+	("semana", WEEK),
+	("semanas", WEEK),
+	("mes", MONTH),
+	("mês", MONTH),
+	("meses", MONTH),
+	("año", YEAR),
+	("años", YEAR),
+	("ano", YEAR),
+	("anos", YEAR),
+	// french
+	("seconde", 1),
+	("secondes", 1),
+	("heure", HOUR),
+	("heures", HOUR),
+	("jour", DAY),
+	("jours", DAY),
+	("semaine", WEEK),
+	("semaines", WEEK),
+	("mois", MONTH),
+	("an", YEAR),
+	("ans", YEAR),
+	("année", YEAR),
+	("années", YEAR),
+	// indonesian
+	("detik", 1),
+	("menit", MINUTE),
+	("jam", HOUR),
+	("hari", DAY),
+	("minggu", WEEK),
+	("bulan", MONTH),
+	("tahun", YEAR),
+	// vietnamese
+	("giây", 1),
+	("phút", MINUTE),
+	("giờ", HOUR),
+	("tiếng", HOUR),
+	("ngày", DAY),
+	("tuần", WEEK),
+	("tháng", MONTH),
+	("năm", YEAR),
+	// chinese
+	("秒", 1),
+	("秒钟", 1),
+	("秒鐘", 1),
+	("分", MINUTE),
+	("分钟", MINUTE),
+	("分鐘", MINUTE),
+	("小时", HOUR),
+	("小時", HOUR),
+	("个小时", HOUR),
+	("個小時", HOUR),
+	("钟头", HOUR),
+	("鐘頭", HOUR),
+	("天", DAY),
+	("日", DAY),
+	("周", WEEK),
+	("週", WEEK),
+	("星期", WEEK),
+	("个星期", WEEK),
+	("個星期", WEEK),
+	("月", MONTH),
+	("个月", MONTH),
+	("個月", MONTH),
+	("年", YEAR),
+	// japanese
+	("時間", HOUR),
+	("週間", WEEK),
+	("ヶ月", MONTH),
+	("か月", MONTH),
+	("カ月", MONTH),
+	("ヵ月", MONTH),
+	("ケ月", MONTH),
+	// korean
+	("초", 1),
+	("분", MINUTE),
+	("시간", HOUR),
+	("일", DAY),
+	("주", WEEK),
+	("주일", WEEK),
+	("개월", MONTH),
+	("달", MONTH),
+	("년", YEAR),
+	// russian
+	("сек", 1),
+	("секунду", 1),
+	("секунды", 1),
+	("секунд", 1),
+	("мин", MINUTE),
+	("минуту", MINUTE),
+	("минуты", MINUTE),
+	("минут", MINUTE),
+	("час", HOUR),
+	("часа", HOUR),
+	("часов", HOUR),
+	("день", DAY),
+	("дня", DAY),
+	("дней", DAY),
+	("неделю", WEEK),
+	("недели", WEEK),
+	("недель", WEEK),
+	("месяц", MONTH),
+	("месяца", MONTH),
+	("месяцев", MONTH),
+	("год", YEAR),
+	("года", YEAR),
+	("лет", YEAR),
+];
+
+// words that join the parts of an amount, e.g. "1 hour and 20 minutes"
+const SEPARATORS: &[&str] = &[",", "and ", "y ", "e ", "et ", "và ", "dan ", "и "];
+
+/// Parses a relative date, such as "3 hours ago" or "yesterday", into a Unix timestamp.
+///
+/// The date is relative to `now`, which is usually the [current date](crate::imports::std::current_date).
+/// Months are counted as 30 days and years as 365 days, and days like "today" or "yesterday"
+/// are whole days before `now`. Returns `None` if the text isn't a relative date.
+///
+/// # Examples
+/// ```
+/// use buny::helpers::date::parse_relative_date;
+/// let now = 1751374800;
+/// assert_eq!(parse_relative_date("3 hours ago", now), Some(now - 3 * 60 * 60));
+/// assert_eq!(parse_relative_date("yesterday", now), Some(now - 24 * 60 * 60));
+/// assert_eq!(parse_relative_date("hace 2 días", now), Some(now - 2 * 24 * 60 * 60));
+/// assert_eq!(parse_relative_date("2 週間前", now), Some(now - 14 * 24 * 60 * 60));
+/// assert_eq!(parse_relative_date("三天前", now), Some(now - 3 * 24 * 60 * 60));
+/// assert_eq!(parse_relative_date("July 1, 2025", now), None);
+/// ```
+pub fn parse_relative_date<T: AsRef<str>>(text: T, now: i64) -> Option<i64> {
+	let text = normalize(text.as_ref());
+	if let Some((_, ago)) = PHRASES.iter().find(|(phrase, _)| *phrase == text) {
+		return Some(now - ago);
+	}
+
+	let mut amount = text.as_str();
+	let mut marked = false;
+	if let Some(rest) = PREFIXES
+		.iter()
+		.find_map(|prefix| amount.strip_prefix(prefix))
+	{
+		amount = rest;
+		marked = true;
+	}
+	if let Some(rest) = SUFFIXES
+		.iter()
+		.find_map(|suffix| amount.strip_suffix(suffix))
+	{
+		amount = rest;
+		marked = true;
+	}
+	// without a marker, only compact amounts like "3h" are relative
+	if !marked && !is_compact(amount) {
+		return None;
+	}
+	parse_duration(amount.trim(), marked).map(|ago| now - ago)
+}
+
+fn normalize(text: &str) -> String {
+	text.trim()
+		.trim_end_matches(['.', '!'])
+		.chars()
+		.map(|c| match c {
+			// full width digits are used in japanese and chinese text
+			'０'..='９' => char::from_u32(c as u32 - '０' as u32 + '0' as u32).unwrap_or(c),
+			'\u{a0}' | '\u{3000}' => ' ',
+			c => c,
+		})
+		.collect::<String>()
+		.to_lowercase()
+}
+
+fn is_compact(text: &str) -> bool {
+	let unit = text.trim_start_matches(|c: char| c.is_ascii_digit());
+	unit.len() < text.len() && ["s", "m", "h", "d", "w", "mo", "y"].contains(&unit)
+}
+
+// reads a sequence of amounts and units, returning the total number of seconds
+fn parse_duration(mut text: &str, implicit_count: bool) -> Option<i64> {
+	let mut total = None;
+	loop {
+		text = skip_separators(text);
+		if text.is_empty() {
+			return total;
+		}
+		let (count, rest) = match read_count(text).and_then(|(count, rest)| {
+			// a word that looked like a number may be the start of a unit instead
+			read_unit(rest.trim_start()).map(|(unit, rest)| (count * unit, rest))
+		}) {
+			Some(amount) => amount,
+			// the count can be left out, e.g. "hace una semana" or "tháng trước"
+			None if implicit_count => read_unit(text)?,
+			None => return None,
+		};
+		total = Some(total.unwrap_or(0) + count);
+		text = rest;
+	}
+}
+
+fn skip_separators(mut text: &str) -> &str {
+	loop {
+		text = text.trim_start();
+		match SEPARATORS
+			.iter()
+			.find_map(|separator| text.strip_prefix(separator))
+		{
+			Some(rest) => text = rest,
+			None => return text,
+		}
+	}
+}
+
+fn read_count(text: &str) -> Option<(i64, &str)> {
+	let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+	if digits > 0 {
+		return Some((text[..digits].parse().ok()?, &text[digits..]));
+	}
+	if let Some(rest) = text.strip_prefix("se") {
+		return Some((1, rest));
+	}
+	if let Some((count, rest)) = read_cjk_numerals(text) {
+		return Some((count.into(), rest));
+	}
+	NUMBERS.iter().find_map(|(word, count)| {
+		let rest = text.strip_prefix(word)?;
+		rest.starts_with(' ').then_some((*count, rest))
+	})
+}
+
+// reads the longest unit at the start of the text, returning its number of seconds
+fn read_unit(text: &str) -> Option<(i64, &str)> {
+	UNITS
+		.iter()
+		.filter_map(|(unit, seconds)| {
+			let rest = text.strip_prefix(unit)?;
+			// units in alphabetic scripts have to end at a word boundary
+			let is_word = !unit.chars().last().is_some_and(is_cjk);
+			if is_word && rest.starts_with(char::is_alphabetic) {
+				return None;
+			}
+			Some((unit.len(), *seconds, rest))
+		})
+		.max_by_key(|(len, _, _)| *len)
+		.map(|(_, seconds, rest)| (seconds, rest))
+}
+
+fn is_cjk(c: char) -> bool {
+	matches!(c,
+		'\u{3040}'..='\u{30ff}' // hiragana and katakana
+		| '\u{3400}'..='\u{9fff}' // cjk ideographs
+		| '\u{ac00}'..='\u{d7af}' // hangul syllables
+		| '\u{f900}'..='\u{faff}' // cjk compatibility ideographs
+	)
+}
+
+/// A parser for dates that may be relative or in one of several absolute formats.
+///
+/// Relative dates are read with [parse_relative_date], and the absolute formats are tried in
+/// the order they were added, with the `DateFormatter` syntax of
+/// [parse_date](crate::imports::std::parse_date).
+///
+/// # Examples
+/// ```ignore
+/// use buny::helpers::date::DateParser;
+/// let parser = DateParser::new()
+///     .format("d MMMM yyyy")
+///     .format("dd/MM/yyyy")
+///     .locale("fr_FR");
+/// assert!(parser.parse("il y a 3 heures").is_some());
+/// assert_eq!(parser.parse("1 juillet 2025"), Some(1751328000));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DateParser {
+	/// The absolute formats to try.
+	pub formats: Vec<String>,
+	/// The locale of the absolute formats, or `en_US_POSIX`.
+	pub locale: Option<String>,
+	/// The timezone of the absolute formats, or UTC.
+	pub timezone: Option<String>,
+}
+
+impl DateParser {
+	/// Creates a parser that only reads relative dates.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds an absolute format to try.
+	pub fn format<T: AsRef<str>>(mut self, format: T) -> Self {
+		self.formats.push(format.as_ref().to_string());
+		self
+	}
+
+	/// Sets the locale of the absolute formats.
+	pub fn locale<T: AsRef<str>>(mut self, locale: T) -> Self {
+		self.locale = Some(locale.as_ref().to_string());
+		self
+	}
+
+	/// Sets the timezone of the absolute formats.
+	pub fn timezone<T: AsRef<str>>(mut self, timezone: T) -> Self {
+		self.timezone = Some(timezone.as_ref().to_string());
+		self
+	}
+
+	/// Parses a relative date from the current date, or an absolute date in one of the formats.
+	#[cfg(feature = "imports")]
+	pub fn parse<T: AsRef<str>>(&self, text: T) -> Option<i64> {
+		self.parse_from(text, current_date())
+	}
+
+	/// Parses a relative date from `now`, or an absolute date in one of the formats.
+	#[cfg(feature = "imports")]
+	pub fn parse_from<T: AsRef<str>>(&self, text: T, now: i64) -> Option<i64> {
+		let text = text.as_ref().trim();
+		parse_relative_date(text, now).or_else(|| {
+			let locale = self.locale.as_deref().unwrap_or("en_US_POSIX");
+			let timezone = self.timezone.as_deref().unwrap_or("UTC");
+			self.formats
+				.iter()
+				.find_map(|format| parse_date_with_options(text, format, locale, timezone))
+		})
+	}
+}
@@ -1,6 +1,7 @@
 //! Additional functions for HTML elements.
+use super::string::{ruby, PlainText};
 use crate::alloc::{String, Vec};
-use crate::imports::html::{Document, Element, ElementList, Html};
+use crate::imports::html::{Document, Element, ElementList, Html, Node};
 
 pub trait ElementHelpers {
 	/// Get the text of the element(s) and their children.
@@ -54,3 +55,79 @@ impl ElementHelpers for Document {
 		self.0.text_with_newlines()
 	}
 }
+
+/// Returns the text of an element as escaped markdown, keeping ruby annotations.
+///
+/// `<ruby>` elements are converted to the [ruby annotation](crate::ContentBlock#ruby-annotations)
+/// syntax, pairing each `<rt>` annotation with the base text before it, and `<rp>`
+/// fallback parentheses are removed. Whitespace is collapsed, and `<br>` tags become
+/// line breaks.
+///
+/// # Examples
+/// ```ignore
+/// use buny::{helpers::element::text_with_ruby, imports::html::Html};
+/// let html = Html::parse_fragment("<p><ruby>漢<rt>かん</rt>字<rt>じ</rt></ruby>です</p>")?;
+/// let p = html.select_first("p").unwrap();
+/// assert_eq!(text_with_ruby(&p), "{漢|かん}{字|じ}です");
+/// ```
+pub fn text_with_ruby(element: &Element) -> String {
+	// collapses whitespace, keeping a single space at either end
+	fn push_text(output: &mut String, text: &str) {
+		if text.starts_with(char::is_whitespace) && !output.ends_with([' ', '\n']) {
+			output.push(' ');
+		}
+		let words = text.split_whitespace().collect::<Vec<_>>();
+		output.push_str(&words.join(" ").escape_markdown());
+		if !words.is_empty() && text.ends_with(char::is_whitespace) {
+			output.push(' ');
+		}
+	}
+
+	fn visit_ruby(element: &Element, output: &mut String) {
+		let mut base = String::new();
+		for node in element.child_nodes() {
+			match node {
+				Node::Text(text) => base.push_str(&text.text().unwrap_or_default()),
+				Node::Element(child) => match child.tag_name().unwrap_or_default().as_str() {
+					"rt" => {
+						let annotation = child.text().unwrap_or_default();
+						let base_text = base.split_whitespace().collect::<Vec<_>>().join(" ");
+						if base_text.is_empty() {
+							push_text(output, &annotation);
+						} else {
+							output.push_str(&ruby(base_text, annotation.trim()));
+						}
+						base.clear();
+					}
+					"rp" => {}
+					_ => base.push_str(&child.text().unwrap_or_default()),
+				},
+				Node::Comment(_) => {}
+			}
+		}
+		push_text(output, &base);
+	}
+
+	fn visit(element: &Element, output: &mut String) {
+		for node in element.child_nodes() {
+			match node {
+				Node::Text(text) => push_text(output, &text.text().unwrap_or_default()),
+				Node::Element(child) => match child.tag_name().unwrap_or_default().as_str() {
+					"ruby" => visit_ruby(&child, output),
+					"br" => {
+						let trimmed = output.trim_end_matches(' ').len();
+						output.truncate(trimmed);
+						output.push_str("  \n");
+					}
+					"script" | "style" | "rt" | "rp" => {}
+					_ => visit(&child, output),
+				},
+				Node::Comment(_) => {}
+			}
+		}
+	}
+
+	let mut output = String::new();
+	visit(element, &mut output);
+	output.trim().into()
+}
@@ -0,0 +1,368 @@
+//! Extraction of structured novel metadata embedded in web pages.
+//!
+//! Many sites describe their pages with [OpenGraph](https://ogp.me) and Twitter card
+//! `<meta>` tags, or with [JSON-LD](https://json-ld.org) `Book` objects. This module
+//! reads that data and maps it onto [Novel] fields, which can be used as a fallback
+//! when a source doesn't have site-specific selectors for a detail.
+//!
+//! # Examples
+//! ```ignore
+//! use buny::{helpers::metadata::Metadata, imports::net::Request};
+//! let html = Request::get(&url)?.html()?;
+//! Metadata::from_document(&html).apply_to(&mut novel);
+//! ```
+extern crate alloc;
+
+use crate::{Novel, NovelStatus};
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+
+#[cfg(feature = "imports")]
+use crate::imports::html::Document;
+
+#[cfg(feature = "json")]
+use serde_json::Value;
+
+/// Novel details found in page metadata.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct Metadata {
+	/// Title of the novel.
+	pub title: Option<String>,
+	/// List of authors.
+	pub authors: Option<Vec<String>>,
+	/// Link to the novel cover image.
+	pub cover: Option<String>,
+	/// Description of the novel.
+	pub description: Option<String>,
+	/// Link to the novel on the source website.
+	pub url: Option<String>,
+	/// List of genres or tags.
+	pub tags: Option<Vec<String>>,
+	/// Publishing status of the novel.
+	pub status: NovelStatus,
+}
+
+impl Metadata {
+	/// Extracts all the supported metadata from an HTML document.
+	///
+	/// JSON-LD data (when the `json` feature is enabled) takes precedence over
+	/// OpenGraph and Twitter card tags, which are used to fill in anything missing.
+	#[cfg(feature = "imports")]
+	pub fn from_document(document: &Document) -> Self {
+		#[allow(unused_mut)]
+		let mut metadata = Self::default();
+		#[cfg(feature = "json")]
+		if let Some(scripts) = document.select("script[type=\"application/ld+json\"]") {
+			for script in scripts {
+				let Some(data) = script.data().or_else(|| script.html()) else {
+					continue;
+				};
+				if let Ok(value) = serde_json::from_str::<Value>(&data) {
+					metadata.merge(Self::from_json_ld(&value));
+				}
+			}
+		}
+		if let Some(tags) = document.select("meta[content]") {
+			let tags = tags.filter_map(|tag| {
+				let key = tag.attr("property").or_else(|| tag.attr("name"))?;
+				let value = tag.attr("content")?;
+				Some((key, value))
+			});
+			metadata.merge(Self::from_meta_tags(tags));
+		}
+		metadata
+	}
+
+	/// Creates metadata from a list of `<meta>` tag property names and their content.
+	///
+	/// OpenGraph (`og:`, `book:`, and the `og:novel:` tags used by many novel sites)
+	/// and Twitter card properties are supported. Tag names are case insensitive.
+	///
+	/// # Examples
+	/// ```
+	/// use buny::{helpers::metadata::Metadata, NovelStatus};
+	/// let metadata = Metadata::from_meta_tags([
+	///     ("og:title", "Novel Title"),
+	///     ("og:novel:author", "Author"),
+	///     ("og:novel:status", "Completed"),
+	/// ]);
+	/// assert_eq!(metadata.title, Some("Novel Title".into()));
+	/// assert_eq!(metadata.authors, Some(vec!["Author".into()]));
+	/// assert_eq!(metadata.status, NovelStatus::Completed);
+	/// ```
+	pub fn from_meta_tags<I, K, V>(tags: I) -> Self
+	where
+		I: IntoIterator<Item = (K, V)>,
+		K: AsRef<str>,
+		V: AsRef<str>,
+	{
+		let tags = tags
+			.into_iter()
+			.filter_map(|(key, value)| {
+				let value = value.as_ref().trim();
+				if value.is_empty() {
+					None
+				} else {
+					Some((
+						key.as_ref().trim().to_ascii_lowercase(),
+						String::from(value),
+					))
+				}
+			})
+			.collect::<Vec<_>>();
+
+		// the first value found for the keys, in order of priority
+		let first = |keys: &[&str]| {
+			keys.iter().find_map(|key| {
+				tags.iter()
+					.find(|(k, _)| k == key)
+					.map(|(_, value)| value.clone())
+			})
+		};
+		// every value found for the keys
+		let all = |keys: &[&str], separator: Option<char>| {
+			let mut values: Vec<String> = Vec::new();
+			for (_, value) in tags.iter().filter(|(k, _)| keys.contains(&k.as_str())) {
+				let parts: Vec<&str> = match separator {
+					Some(separator) => value.split(separator).collect(),
+					None => alloc::vec![value.as_str()],
+				};
+				push_unique(&mut values, parts);
+			}
+			if values.is_empty() {
+				None
+			} else {
+				Some(values)
+			}
+		};
+
+		Self {
+			title: first(&["og:novel:book_name", "og:title", "twitter:title"]),
+			authors: all(&["og:novel:author", "book:author", "author"], None).map(|authors| {
+				// book:author is allowed to be a profile url, which isn't useful as a name
+				authors
+					.into_iter()
+					.filter(|author| !author.starts_with("http"))
+					.collect::<Vec<_>>()
+			}),
+			cover: first(&[
+				"og:image",
+				"og:image:secure_url",
+				"og:image:url",
+				"twitter:image",
+				"twitter:image:src",
+			]),
+			description: first(&["og:description", "twitter:description", "description"]),
+			url: first(&["og:url", "og:novel:read_url"]),
+			tags: all(&["og:novel:category", "book:tag", "article:tag"], Some(',')),
+			status: first(&["og:novel:status"])
+				.map(parse_status)
+				.unwrap_or_default(),
+		}
+		.normalized()
+	}
+
+	/// Creates metadata from a parsed JSON-LD value.
+	///
+	/// The value may be a single object, an array, or an object with a `@graph`
+	/// array. Objects with a `Book`, `BookSeries`, `CreativeWorkSeries` or
+	/// `CreativeWork` type are used, as well as the `isPartOf` object of a `Chapter`.
+	///
+	/// # Examples
+	/// ```
+	/// use buny::helpers::metadata::Metadata;
+	/// let value = serde_json::json!({
+	///     "@context": "https://schema.org",
+	///     "@type": "Book",
+	///     "name": "Novel Title",
+	///     "author": { "@type": "Person", "name": "Author" },
+	///     "genre": ["Fantasy", "Action"],
+	/// });
+	/// let metadata = Metadata::from_json_ld(&value);
+	/// assert_eq!(metadata.title, Some("Novel Title".into()));
+	/// assert_eq!(metadata.authors, Some(vec!["Author".into()]));
+	/// assert_eq!(metadata.tags, Some(vec!["Fantasy".into(), "Action".into()]));
+	/// ```
+	#[cfg(feature = "json")]
+	pub fn from_json_ld(value: &Value) -> Self {
+		const NOVEL_TYPES: &[&str] = &["Book", "BookSeries", "CreativeWorkSeries", "CreativeWork"];
+
+		let mut metadata = Self::default();
+		match value {
+			Value::Array(values) => {
+				for value in values {
+					metadata.merge(Self::from_json_ld(value));
+				}
+			}
+			Value::Object(object) => {
+				if let Some(graph) = object.get("@graph") {
+					metadata.merge(Self::from_json_ld(graph));
+				}
+				if json_ld_has_type(value, &["Chapter"]) {
+					if let Some(parent) = object.get("isPartOf") {
+						metadata.merge(Self::from_json_ld(parent));
+					}
+				} else if json_ld_has_type(value, NOVEL_TYPES) {
+					let mut tags = Vec::new();
+					for key in ["genre", "keywords"] {
+						let values = json_ld_strings(object.get(key));
+						push_unique(&mut tags, values.iter().flat_map(|tag| tag.split(',')));
+					}
+					metadata.merge(
+						Self {
+							title: json_ld_string(object.get("name"))
+								.or_else(|| json_ld_string(object.get("headline"))),
+							authors: Some(json_ld_strings(object.get("author"))),
+							cover: json_ld_image(object.get("image"))
+								.or_else(|| json_ld_image(object.get("thumbnailUrl"))),
+							description: json_ld_string(object.get("description")),
+							url: json_ld_string(object.get("url")),
+							tags: Some(tags),
+							status: json_ld_string(object.get("creativeWorkStatus"))
+								.map(parse_status)
+								.unwrap_or_default(),
+						}
+						.normalized(),
+					);
+				}
+			}
+			_ => {}
+		}
+		metadata.normalized()
+	}
+
+	/// Fills in any values missing from this metadata with the values of another.
+	pub fn merge(&mut self, other: Metadata) {
+		self.title = self.title.take().or(other.title);
+		self.authors = self.authors.take().or(other.authors);
+		self.cover = self.cover.take().or(other.cover);
+		self.description = self.description.take().or(other.description);
+		self.url = self.url.take().or(other.url);
+		self.tags = self.tags.take().or(other.tags);
+		if self.status == NovelStatus::Unknown {
+			self.status = other.status;
+		}
+	}
+
+	/// Fills in any details missing from a novel with this metadata.
+	///
+	/// Values that are already set on the novel are kept.
+	pub fn apply_to(self, novel: &mut Novel) {
+		if novel.title.is_empty() {
+			if let Some(title) = self.title {
+				novel.title = title;
+			}
+		}
+		novel.authors = novel.authors.take().or(self.authors);
+		novel.cover = novel.cover.take().or(self.cover);
+		novel.description = novel.description.take().or(self.description);
+		novel.url = novel.url.take().or(self.url);
+		novel.tags = novel.tags.take().or(self.tags);
+		if novel.status == NovelStatus::Unknown {
+			novel.status = self.status;
+		}
+	}
+
+	// replace empty lists with `None` so they don't take precedence when merging
+	fn normalized(mut self) -> Self {
+		if self.authors.as_ref().is_some_and(|a| a.is_empty()) {
+			self.authors = None;
+		}
+		if self.tags.as_ref().is_some_and(|t| t.is_empty()) {
+			self.tags = None;
+		}
+		self
+	}
+}
+
+/// Parses a publishing status string into a [NovelStatus].
+///
+/// Common English and Chinese status labels are recognized, case insensitively.
+///
+/// # Examples
+/// ```
+/// use buny::{helpers::metadata::parse_status, NovelStatus};
+/// assert_eq!(parse_status("OnGoing"), NovelStatus::Ongoing);
+/// assert_eq!(parse_status("完结"), NovelStatus::Completed);
+/// assert_eq!(parse_status("https://schema.org/Completed"), NovelStatus::Completed);
+/// assert_eq!(parse_status("???"), NovelStatus::Unknown);
+/// ```
+pub fn parse_status<T: AsRef<str>>(status: T) -> NovelStatus {
+	let status = status.as_ref().trim().to_lowercase();
+	// schema.org values may be given as urls
+	let status = status.rsplit('/').next().unwrap_or_default();
+	match status {
+		"ongoing" | "on going" | "publishing" | "updating" | "active" | "连载" | "连载中"
+		| "連載" | "連載中" => NovelStatus::Ongoing,
+		"completed" | "complete" | "finished" | "ended" | "完结" | "完結" | "完本" | "已完结"
+		| "已完結" => NovelStatus::Completed,
+		"hiatus" | "on hiatus" | "paused" | "on hold" | "暂停" | "休刊" => NovelStatus::Hiatus,
+		"cancelled" | "canceled" | "dropped" | "discontinued" | "abandoned" | "断更" => {
+			NovelStatus::Cancelled
+		}
+		_ => NovelStatus::Unknown,
+	}
+}
+
+// adds trimmed, non-empty values to a list if they aren't already present
+fn push_unique<'a, I: IntoIterator<Item = &'a str>>(list: &mut Vec<String>, values: I) {
+	for value in values {
+		let value = value.trim();
+		if !value.is_empty() && !list.iter().any(|v| v == value) {
+			list.push(value.to_string());
+		}
+	}
+}
+
+#[cfg(feature = "json")]
+fn json_ld_has_type(value: &Value, types: &[&str]) -> bool {
+	json_ld_strings(value.get("@type"))
+		.iter()
+		// types may be prefixed with a context, e.g. `schema:Book`
+		.any(|t| types.contains(&t.rsplit([':', '/']).next().unwrap_or_default()))
+}
+
+// gets a string from a value, a `name`/`url` property, or the first item of an array
+#[cfg(feature = "json")]
+fn json_ld_string(value: Option<&Value>) -> Option<String> {
+	match value? {
+		Value::String(string) => {
+			let string = string.trim();
+			if string.is_empty() {
+				None
+			} else {
+				Some(string.to_string())
+			}
+		}
+		Value::Array(values) => values.iter().find_map(|value| json_ld_string(Some(value))),
+		Value::Object(object) => json_ld_string(object.get("name"))
+			.or_else(|| json_ld_string(object.get("url")))
+			.or_else(|| json_ld_string(object.get("@id"))),
+		_ => None,
+	}
+}
+
+// gets the url of an image, which may be an `ImageObject` with a caption as its `name`
+#[cfg(feature = "json")]
+fn json_ld_image(value: Option<&Value>) -> Option<String> {
+	match value? {
+		Value::Array(values) => values.iter().find_map(|value| json_ld_image(Some(value))),
+		Value::Object(object) => json_ld_string(object.get("url"))
+			.or_else(|| json_ld_string(object.get("contentUrl")))
+			.or_else(|| json_ld_string(object.get("@id"))),
+		value => json_ld_string(Some(value)),
+	}
+}
+
+#[cfg(feature = "json")]
+fn json_ld_strings(value: Option<&Value>) -> Vec<String> {
+	match value {
+		Some(Value::Array(values)) => values
+			.iter()
+			.filter_map(|value| json_ld_string(Some(value)))
+			.collect(),
+		value => json_ld_string(value).into_iter().collect(),
+	}
+}
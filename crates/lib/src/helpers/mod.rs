@@ -6,5 +6,9 @@ pub mod cfemail;
 #[cfg(feature = "imports")]
 pub mod element;
 
+pub mod chapter;
+pub mod chapters;
+pub mod date;
+pub mod metadata;
 pub mod string;
 pub mod uri;
@@ -28,3 +28,23 @@ impl<S: AsRef<str>> PlainText for S {
 		markdown.replace("\r\n", "\n").replace('\n', "  \n")
 	}
 }
+
+/// Creates a ruby annotation for markdown [content](crate::ContentBlock), such as
+/// furigana over kanji.
+///
+/// Both the base text and annotation are escaped.
+///
+/// # Examples
+/// ```
+/// use buny::helpers::string::ruby;
+/// assert_eq!(ruby("漢字", "かんじ"), "{漢字|かんじ}");
+/// assert_eq!(ruby("a|b", "c"), "{a\\|b|c}");
+/// ```
+pub fn ruby<B: AsRef<str>, A: AsRef<str>>(base: B, annotation: A) -> String {
+	let mut markdown = String::from("{");
+	markdown.push_str(&base.as_ref().escape_markdown());
+	markdown.push('|');
+	markdown.push_str(&annotation.as_ref().escape_markdown());
+	markdown.push('}');
+	markdown
+}
@@ -1,7 +1,9 @@
 //! Module for interacting with user preferences.
+#[cfg(not(feature = "native"))]
+use super::FFIResult;
 use super::{
 	std::{encode, free_result, read},
-	FFIResult, Ptr,
+	Ptr,
 };
 use crate::{
 	alloc::{String, Vec},
@@ -10,12 +12,16 @@ use crate::{
 use crate::{prelude::format, structs::HashMap};
 use serde::{de::DeserializeOwned, Serialize};
 
+#[cfg(not(feature = "native"))]
 #[link(wasm_import_module = "defaults")]
 extern "C" {
 	fn get(key: *const u8, len: usize) -> FFIResult;
 	fn set(key: *const u8, len: usize, kind: u8, value: Ptr) -> FFIResult;
 }
 
+#[cfg(feature = "native")]
+use crate::native::imports::defaults::{get, set};
+
 /// A default value that can be stored in UserDefaults.
 pub enum DefaultValue {
 	Bool(bool),
@@ -102,7 +108,7 @@ pub fn defaults_set(key: &str, value: DefaultValue) {
 
 /// Sets the UserDefaults value of the specified key with serialized data.
 pub fn defaults_set_data<T: Serialize>(key: &str, value: T) {
-	let value_ptr: i32 = unsafe { encode(&value) };
+	let value_ptr: Ptr = unsafe { encode(&value) };
 	unsafe {
 		set(key.as_ptr(), key.len(), 0, value_ptr);
 	};
@@ -2,18 +2,73 @@
 use super::{html::HtmlError, js::JsError, net::RequestError};
 #[cfg(feature = "json")]
 use crate::alloc::rc::Rc;
-use crate::alloc::{string::ToString, String};
+use crate::{
+	alloc::{string::ToString, Box, String, Vec},
+	ErrorInfo,
+};
 use core::{fmt::Display, str::Utf8Error};
 
 pub type Result<T> = core::result::Result<T, BunyError>;
 
 /// An error passed back to the source runner.
+///
+/// # Wire encoding
+///
+/// When a source function registered with [register_source](crate::register_source) fails,
+/// it returns either a plain negative [error code](BunyError::code), or a pointer to an
+/// error buffer instead of a pointer to its result. The buffer starts with the negative
+/// error code so that it can be told apart from a result buffer (which starts with its
+/// positive length):
+///
+/// | Offset | Size | Value                                        |
+/// |--------|------|----------------------------------------------|
+/// | 0      | 4    | Error code (`i32`, little endian)            |
+/// | 4      | 4    | Buffer capacity (`i32`, little endian)       |
+/// | 8      | 4    | Buffer length, including the header (`i32`) |
+/// | 12     | ...  | Payload                                      |
+///
+/// Sources use version 0 of the encoding unless the runner calls the exported
+/// `set_error_version` function with a newer [version](crate::ERROR_VERSION), which returns the
+/// version that the source will use.
+///
+/// In version 0, only `Message` errors return a buffer, with the UTF-8 bytes of the message
+/// as its payload. Other errors return their plain code.
+///
+/// In version 1, every error returns a buffer with its [payload](BunyError::payload), an
+/// [ErrorInfo] serialized with postcard. It has the kind and message of the error, the
+/// messages of its underlying errors, and the url and status code of the request that
+/// caused it when available.
+///
+/// | Variant         | Code | Version 1 payload fields       |
+/// |-----------------|------|--------------------------------|
+/// | `Message`       | -1   | `message`                      |
+/// | `Unimplemented` | -2   |                                |
+/// | `RequestError`  | -3   | `sources`                      |
+/// | `LoginRequired` | -4   |                                |
+/// | `NotFound`      | -5   |                                |
+/// | `ContentLocked` | -6   |                                |
+/// | `Cloudflare`    | -7   | `url` to open in a webview     |
+/// | `RateLimited`   | -8   | `retry_after`, if known        |
+/// | `WithRequest`   | *    | `url` and `status`             |
+/// | Others          | -1   | `sources`, if any              |
+///
+/// The buffer should be freed by the runner with the exported `free_result` function.
 #[derive(Debug, Clone)]
 pub enum BunyError {
 	/// This feature is unimplemented.
 	Unimplemented,
 	/// Pass a message back to the app.
 	Message(String),
+	/// The user needs to log in to access this content.
+	LoginRequired,
+	/// A Cloudflare challenge needs to be solved in a webview at the given url.
+	Cloudflare { url: String },
+	/// The source is being rate limited, optionally with the seconds to wait before retrying.
+	RateLimited { retry_after: Option<i32> },
+	/// The requested novel or chapter doesn't exist.
+	NotFound,
+	/// The content needs to be purchased or unlocked before it can be viewed.
+	ContentLocked,
 	/// There was an error making a request.
 	RequestError(RequestError),
 	/// There was an error performing an HTML operation.
@@ -27,6 +82,14 @@ pub enum BunyError {
 	JsonParseError(Rc<serde_json::Error>),
 	/// Deserialization error.
 	DeserializeError,
+	/// An error caused by a request, with the request url and response status code.
+	///
+	/// This uses the code of the wrapped error.
+	WithRequest {
+		error: Box<BunyError>,
+		url: Option<String>,
+		status: Option<i32>,
+	},
 }
 
 impl BunyError {
@@ -34,6 +97,205 @@ impl BunyError {
 	pub fn message<S: Display>(message: S) -> Self {
 		Self::Message(message.to_string())
 	}
+
+	/// Creates a new Cloudflare challenge error for the given url.
+	pub fn cloudflare<S: Into<String>>(url: S) -> Self {
+		Self::Cloudflare { url: url.into() }
+	}
+
+	/// Attaches the url and status code of the request that caused this error.
+	///
+	/// # Examples
+	/// ```
+	/// use buny::imports::error::BunyError;
+	/// let error = BunyError::NotFound.with_request(Some("https://example.com".into()), Some(404));
+	/// assert_eq!(error.code(), -5);
+	/// assert_eq!(error.info().status, Some(404));
+	/// ```
+	pub fn with_request(self, url: Option<String>, status: Option<i32>) -> Self {
+		match self {
+			Self::WithRequest {
+				error,
+				url: old_url,
+				status: old_status,
+			} => Self::WithRequest {
+				error,
+				url: old_url.or(url),
+				status: old_status.or(status),
+			},
+			error => Self::WithRequest {
+				error: Box::new(error),
+				url,
+				status,
+			},
+		}
+	}
+
+	/// Returns the name of the error variant.
+	///
+	/// Errors with request details return the kind of the wrapped error.
+	pub fn kind(&self) -> &'static str {
+		match self {
+			Self::Unimplemented => "Unimplemented",
+			Self::Message(_) => "Message",
+			Self::LoginRequired => "LoginRequired",
+			Self::Cloudflare { .. } => "Cloudflare",
+			Self::RateLimited { .. } => "RateLimited",
+			Self::NotFound => "NotFound",
+			Self::ContentLocked => "ContentLocked",
+			Self::RequestError(_) => "RequestError",
+			Self::HtmlError(_) => "HtmlError",
+			Self::JsError(_) => "JsError",
+			Self::Utf8Error(_) => "Utf8Error",
+			#[cfg(feature = "json")]
+			Self::JsonParseError(_) => "JsonParseError",
+			Self::DeserializeError => "DeserializeError",
+			Self::WithRequest { error, .. } => error.kind(),
+		}
+	}
+
+	/// Returns the code used to pass this error to the source runner.
+	///
+	/// # Examples
+	/// ```
+	/// use buny::imports::error::BunyError;
+	/// assert_eq!(BunyError::Unimplemented.code(), -2);
+	/// assert_eq!(BunyError::RateLimited { retry_after: None }.code(), -8);
+	/// ```
+	pub fn code(&self) -> i32 {
+		match self {
+			Self::Unimplemented => -2,
+			Self::RequestError(_) => -3,
+			Self::LoginRequired => -4,
+			Self::NotFound => -5,
+			Self::ContentLocked => -6,
+			Self::Cloudflare { .. } => -7,
+			Self::RateLimited { .. } => -8,
+			Self::WithRequest { error, .. } => error.code(),
+			_ => -1,
+		}
+	}
+
+	/// Returns the details of the error that are passed to the source runner.
+	///
+	/// # Examples
+	/// ```
+	/// use buny::imports::{error::BunyError, net::RequestError};
+	/// let info = BunyError::from(RequestError::InvalidUrl).info();
+	/// assert_eq!(info.kind, "RequestError");
+	/// assert_eq!(info.message, "request error");
+	/// assert_eq!(info.sources, ["invalid url"]);
+	/// ```
+	pub fn info(&self) -> ErrorInfo {
+		let mut sources = Vec::new();
+		let mut source = core::error::Error::source(self);
+		while let Some(error) = source {
+			sources.push(error.to_string());
+			source = error.source();
+		}
+		let (url, status) = match self {
+			Self::Cloudflare { url } => (Some(url.clone()), None),
+			Self::WithRequest { url, status, .. } => (url.clone(), *status),
+			_ => (None, None),
+		};
+		let retry_after = match self {
+			Self::WithRequest { error, .. } => error.info().retry_after,
+			Self::RateLimited { retry_after } => *retry_after,
+			_ => None,
+		};
+		ErrorInfo {
+			kind: self.kind().into(),
+			message: self.to_string(),
+			sources,
+			url,
+			status,
+			retry_after,
+		}
+	}
+
+	/// Returns the payload of the error buffer passed to the source runner.
+	///
+	/// This is the postcard serialized [info](BunyError::info) of the error.
+	///
+	/// # Examples
+	/// ```
+	/// use buny::{imports::error::BunyError, ErrorInfo};
+	/// let payload = BunyError::RateLimited { retry_after: Some(30) }.payload();
+	/// let info: ErrorInfo = postcard::from_bytes(&payload).unwrap();
+	/// assert_eq!(info.retry_after, Some(30));
+	/// ```
+	pub fn payload(&self) -> Vec<u8> {
+		postcard::to_allocvec(&self.info()).unwrap_or_default()
+	}
+
+	/// Returns the error buffer passed to the source runner for an encoding version.
+	///
+	/// Returns `None` if the error is passed as a plain error code instead.
+	///
+	/// # Examples
+	/// ```
+	/// use buny::imports::error::BunyError;
+	/// assert_eq!(BunyError::NotFound.encode(0), None);
+	/// let buffer = BunyError::NotFound.encode(1).unwrap();
+	/// assert_eq!(buffer[0..4], (-5i32).to_le_bytes());
+	/// assert_eq!(buffer[8..12], (buffer.len() as i32).to_le_bytes());
+	/// ```
+	pub fn encode(&self, version: i32) -> Option<Vec<u8>> {
+		let payload = match self {
+			_ if version >= 1 => self.payload(),
+			Self::Message(message) => message.as_bytes().to_vec(),
+			_ => return None,
+		};
+		let mut buffer = self.code().to_le_bytes().to_vec();
+		buffer.extend_from_slice(&[0; 8]);
+		buffer.extend_from_slice(&payload);
+
+		let cap_bytes = (buffer.capacity() as i32).to_le_bytes();
+		buffer[4..8].copy_from_slice(&cap_bytes);
+		let len_bytes = (buffer.len() as i32).to_le_bytes();
+		buffer[8..12].copy_from_slice(&len_bytes);
+		Some(buffer)
+	}
+}
+
+impl Display for BunyError {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		match self {
+			Self::Unimplemented => write!(f, "unimplemented"),
+			Self::Message(message) => write!(f, "{message}"),
+			Self::LoginRequired => write!(f, "login required"),
+			Self::Cloudflare { url } => write!(f, "Cloudflare challenge at {url}"),
+			Self::RateLimited {
+				retry_after: Some(seconds),
+			} => write!(f, "rate limited, retry after {seconds}s"),
+			Self::RateLimited { retry_after: None } => write!(f, "rate limited"),
+			Self::NotFound => write!(f, "not found"),
+			Self::ContentLocked => write!(f, "content locked"),
+			Self::RequestError(_) => write!(f, "request error"),
+			Self::HtmlError(_) => write!(f, "HTML error"),
+			Self::JsError(_) => write!(f, "JavaScript error"),
+			Self::Utf8Error(_) => write!(f, "invalid UTF-8 data"),
+			#[cfg(feature = "json")]
+			Self::JsonParseError(_) => write!(f, "failed to parse JSON"),
+			Self::DeserializeError => write!(f, "failed to deserialize data"),
+			Self::WithRequest { error, .. } => write!(f, "{error}"),
+		}
+	}
+}
+
+impl core::error::Error for BunyError {
+	fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+		match self {
+			Self::RequestError(error) => Some(error),
+			Self::HtmlError(error) => Some(error),
+			Self::JsError(error) => Some(error),
+			Self::Utf8Error(error) => Some(error),
+			#[cfg(feature = "json")]
+			Self::JsonParseError(error) => Some(error.as_ref()),
+			Self::WithRequest { error, .. } => error.source(),
+			_ => None,
+		}
+	}
 }
 
 impl From<RequestError> for BunyError {
@@ -10,7 +10,9 @@ use super::{
 };
 use crate::alloc::String;
 use core::fmt::Display;
+use thiserror::Error;
 
+#[cfg(not(feature = "native"))]
 #[link(wasm_import_module = "html")]
 extern "C" {
 	fn parse(
@@ -54,6 +56,13 @@ extern "C" {
 	fn has_class(rid: Rid, class: *const u8, class_len: usize) -> bool;
 	fn has_attr(rid: Rid, attr: *const u8, attr_len: usize) -> bool;
 
+	fn child_nodes(rid: Rid) -> FFIResult;
+	fn walk(rid: Rid) -> FFIResult;
+	fn next_sibling_node(rid: Rid) -> FFIResult;
+	fn previous_sibling_node(rid: Rid) -> FFIResult;
+	fn node_type(rid: Rid) -> FFIResult;
+	fn node_value(rid: Rid) -> FFIResult;
+
 	fn first(rid: Rid) -> FFIResult;
 	fn last(rid: Rid) -> FFIResult;
 	#[allow(clashing_extern_declarations)]
@@ -62,14 +71,29 @@ extern "C" {
 	fn size(rid: Rid) -> FFIResult;
 }
 
+#[cfg(feature = "native")]
+use crate::native::imports::html::{
+	append, attr, base_uri, child_nodes, children, class_name, data, escape, first,
+	get as html_get, has_attr, has_class, html, id, last, next, next_sibling_node, node_type,
+	node_value, outer_html, own_text, parent, parse, parse_fragment, prepend, previous,
+	previous_sibling_node, select, select_first, set_html, set_text, siblings, size, tag_name,
+	text, unescape, untrimmed_text, walk,
+};
+
 /// Error type for HTML operations.
-#[derive(PartialEq, Eq, Debug, Clone)]
+#[derive(Error, PartialEq, Eq, Debug, Clone)]
 pub enum HtmlError {
+	#[error("invalid descriptor")]
 	InvalidDescriptor,
+	#[error("invalid string")]
 	InvalidString,
+	#[error("invalid HTML")]
 	InvalidHtml,
+	#[error("invalid selector query")]
 	InvalidQuery,
+	#[error("no result")]
 	NoResult,
+	#[error("SwiftSoup error")]
 	SwiftSoupError,
 }
 
@@ -572,6 +596,50 @@ impl Element {
 		let attr_name = attr_name.as_ref();
 		unsafe { has_attr(self.rid, attr_name.as_ptr(), attr_name.len()) }
 	}
+
+	/// Get the element's child nodes, including text and comment nodes.
+	///
+	/// Unlike [Element::children], this keeps the text between child elements.
+	///
+	/// # Examples
+	/// ```ignore
+	/// use buny::imports::html::{Html, Node};
+	/// let html = Html::parse("<p>one<br>two<i>three</i></p>").unwrap();
+	/// let p = html.select_first("p").unwrap();
+	/// let nodes = p.child_nodes().collect::<Vec<_>>();
+	/// assert_eq!(nodes.len(), 4);
+	/// assert!(matches!(nodes[1], Node::Element(_)));
+	/// assert_eq!(nodes[2].text(), Some("two".into()));
+	/// ```
+	pub fn child_nodes(&self) -> NodeList {
+		let rid = unsafe { child_nodes(self.rid) };
+		unsafe { NodeList::from(rid) }
+	}
+
+	/// Get all the descendant nodes of the element in depth-first order.
+	///
+	/// Each element is followed by its own descendants before its next sibling,
+	/// matching the order the nodes appear in the document.
+	pub fn walk(&self) -> NodeList {
+		let rid = unsafe { walk(self.rid) };
+		unsafe { NodeList::from(rid) }
+	}
+
+	/// Get the next sibling node of the element, returning `None` if there isn't one.
+	///
+	/// Unlike [Element::next], this may return a text or comment node.
+	pub fn next_sibling_node(&self) -> Option<Node> {
+		let rid = unsafe { next_sibling_node(self.rid) };
+		unsafe { Node::from(rid) }
+	}
+
+	/// Get the previous sibling node of the element, returning `None` if there isn't one.
+	///
+	/// Unlike [Element::prev], this may return a text or comment node.
+	pub fn prev_sibling_node(&self) -> Option<Node> {
+		let rid = unsafe { previous_sibling_node(self.rid) };
+		unsafe { Node::from(rid) }
+	}
 }
 
 impl Drop for Element {
@@ -738,3 +806,199 @@ impl Drop for ElementList {
 		unsafe { destroy(self.rid) }
 	}
 }
+
+/// A node of an HTML document.
+pub enum Node {
+	/// An element node.
+	Element(Element),
+	/// A text node.
+	Text(TextNode),
+	/// A comment node.
+	Comment(TextNode),
+}
+
+impl Node {
+	const ELEMENT_NODE: FFIResult = 1;
+	const TEXT_NODE: FFIResult = 3;
+	const COMMENT_NODE: FFIResult = 8;
+
+	/// Get an instance from a [Rid], returning `None` for unsupported node types.
+	unsafe fn from(rid: Rid) -> Option<Self> {
+		if HtmlError::from(rid).is_some() {
+			return None;
+		}
+		match node_type(rid) {
+			Self::ELEMENT_NODE => Some(Self::Element(Element::from(rid))),
+			Self::TEXT_NODE => Some(Self::Text(TextNode { rid })),
+			Self::COMMENT_NODE => Some(Self::Comment(TextNode { rid })),
+			_ => {
+				destroy(rid);
+				None
+			}
+		}
+	}
+
+	/// Get the text of the node as it would be presented to a reader.
+	///
+	/// For elements, this is the same as [Element::untrimmed_text]. Text nodes
+	/// return their contents, and comments return `None`.
+	pub fn text(&self) -> Option<String> {
+		match self {
+			Self::Element(element) => element.untrimmed_text(),
+			Self::Text(node) => node.text(),
+			Self::Comment(_) => None,
+		}
+	}
+
+	/// Get the name of the tag if this node is an element.
+	pub fn tag_name(&self) -> Option<String> {
+		self.as_element().and_then(|element| element.tag_name())
+	}
+
+	/// Get the node as an element, if it is one.
+	pub fn as_element(&self) -> Option<&Element> {
+		if let Self::Element(element) = self {
+			Some(element)
+		} else {
+			None
+		}
+	}
+
+	/// Get the node's child nodes.
+	///
+	/// Text and comment nodes never have children, so `None` is returned for them.
+	pub fn child_nodes(&self) -> Option<NodeList> {
+		self.as_element().map(|element| element.child_nodes())
+	}
+
+	/// Get the node's parent element, returning `None` if there isn't one.
+	pub fn parent(&self) -> Option<Element> {
+		let rid = unsafe { parent(self.rid()) };
+		if HtmlError::from(rid).is_some() {
+			return None;
+		}
+		Some(unsafe { Element::from(rid) })
+	}
+
+	/// Get the next sibling node, returning `None` if there isn't one.
+	pub fn next_sibling_node(&self) -> Option<Node> {
+		let rid = unsafe { next_sibling_node(self.rid()) };
+		unsafe { Node::from(rid) }
+	}
+
+	/// Get the previous sibling node, returning `None` if there isn't one.
+	pub fn prev_sibling_node(&self) -> Option<Node> {
+		let rid = unsafe { previous_sibling_node(self.rid()) };
+		unsafe { Node::from(rid) }
+	}
+
+	fn rid(&self) -> Rid {
+		match self {
+			Self::Element(element) => element.rid,
+			Self::Text(node) | Self::Comment(node) => node.rid,
+		}
+	}
+}
+
+/// A node holding character data, such as text or a comment.
+pub struct TextNode {
+	rid: Rid,
+}
+
+impl TextNode {
+	/// Get the raw contents of the node.
+	///
+	/// Whitespace is *not* normalized and trimmed.
+	pub fn text(&self) -> Option<String> {
+		let rid = unsafe { node_value(self.rid) };
+		if HtmlError::from(rid).is_some() {
+			return None;
+		}
+		read_string_and_destroy(rid)
+	}
+}
+
+impl Drop for TextNode {
+	fn drop(&mut self) {
+		unsafe { destroy(self.rid) }
+	}
+}
+
+/// A collection of HTML nodes.
+pub struct NodeList {
+	rid: Rid,
+	lower_bound: usize,
+	upper_bound: usize,
+	size: usize,
+}
+
+impl NodeList {
+	/// Get an instance from a [Rid].
+	unsafe fn from(rid: Rid) -> Self {
+		let size = if HtmlError::from(rid).is_some() {
+			0
+		} else {
+			size(rid) as usize
+		};
+		Self {
+			rid,
+			lower_bound: 0,
+			upper_bound: size.wrapping_sub(1),
+			size,
+		}
+	}
+
+	/// Get the node at the given index.
+	///
+	/// Returns `None` if the index is out of bounds or the node type is unsupported.
+	pub fn get(&self, index: usize) -> Option<Node> {
+		let rid = unsafe { html_get(self.rid, index) };
+		unsafe { Node::from(rid) }
+	}
+
+	/// Get the size of this node list.
+	pub fn size(&self) -> usize {
+		self.size
+	}
+
+	/// Check if this node list is empty.
+	pub fn is_empty(&self) -> bool {
+		self.size() == 0
+	}
+}
+
+impl Iterator for NodeList {
+	type Item = Node;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		while self.lower_bound <= self.upper_bound && self.upper_bound != usize::MAX {
+			let value_ref = self.get(self.lower_bound);
+			self.lower_bound += 1;
+			if value_ref.is_some() {
+				return value_ref;
+			}
+		}
+		None
+	}
+}
+
+impl DoubleEndedIterator for NodeList {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		while self.lower_bound <= self.upper_bound && self.upper_bound != usize::MAX {
+			let value_ref = self.get(self.upper_bound);
+			self.upper_bound = self.upper_bound.wrapping_sub(1);
+			if value_ref.is_some() {
+				return value_ref;
+			}
+		}
+		None
+	}
+}
+
+impl Drop for NodeList {
+	fn drop(&mut self) {
+		if self.rid >= 0 {
+			unsafe { destroy(self.rid) }
+		}
+	}
+}
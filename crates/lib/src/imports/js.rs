@@ -5,7 +5,9 @@ use super::{
 	FFIResult, Rid,
 };
 use crate::alloc::String;
+use thiserror::Error;
 
+#[cfg(not(feature = "native"))]
 #[link(wasm_import_module = "js")]
 extern "C" {
 	fn context_create() -> Rid;
@@ -25,13 +27,24 @@ extern "C" {
 	fn webview_eval(webview: Rid, string_ptr: *const u8, len: usize) -> FFIResult;
 }
 
+#[cfg(feature = "native")]
+use crate::native::imports::js::{
+	context_create, context_eval, context_get, webview_create, webview_eval, webview_load,
+	webview_load_html, webview_wait_for_load,
+};
+
 /// Error type for JavaScript operations.
-#[derive(PartialEq, Eq, Debug, Clone)]
+#[derive(Error, PartialEq, Eq, Debug, Clone)]
 pub enum JsError {
+	#[error("missing result")]
 	MissingResult,
+	#[error("invalid JavaScript context")]
 	InvalidContext,
+	#[error("invalid string")]
 	InvalidString,
+	#[error("invalid handler")]
 	InvalidHandler,
+	#[error("invalid request")]
 	InvalidRequest,
 }
 
@@ -19,4 +19,4 @@ pub(crate) type Rid = i32;
 pub(crate) type FFIResult = i32;
 
 /// A dropped pointer to pass back to the source runner.
-pub(crate) type Ptr = i32;
+pub(crate) type Ptr = isize;
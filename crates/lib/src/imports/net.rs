@@ -1,11 +1,14 @@
 //! Module for creating and sending HTTP requests.
+#[cfg(not(feature = "native"))]
+use super::FFIResult;
 use super::{
 	error::BunyError,
 	html::Document,
 	std::{destroy, read_string_and_destroy},
-	FFIResult, Rid,
+	Rid,
 };
 use crate::alloc::{String, Vec};
+use thiserror::Error;
 
 /// An HTTP request method.
 #[repr(C)]
@@ -22,6 +25,7 @@ pub enum HttpMethod {
 	Trace,
 }
 
+#[cfg(not(feature = "native"))]
 #[link(wasm_import_module = "net")]
 extern "C" {
 	fn init(method: HttpMethod) -> Rid;
@@ -50,6 +54,29 @@ extern "C" {
 	fn net_set_rate_limit(permits: i32, period: i32, unit: i32);
 }
 
+#[cfg(feature = "test")]
+#[cfg(not(feature = "native"))]
+#[link(wasm_import_module = "net")]
+extern "C" {
+	#[link_name = "mock_response"]
+	fn net_mock_response(
+		method: i32,
+		url: *const u8,
+		url_len: usize,
+		status: i32,
+		data: *const u8,
+		data_len: usize,
+	) -> FFIResult;
+}
+
+#[cfg(all(feature = "native", feature = "test"))]
+use crate::native::imports::net::mock_response as net_mock_response;
+#[cfg(feature = "native")]
+use crate::native::imports::net::{
+	data_len, get_header, get_status_code, get_url, html, init, read_data, send, send_all,
+	set_body, set_header, set_rate_limit as net_set_rate_limit, set_timeout, set_url,
+};
+
 /// A time unit for rate limiting.
 pub enum TimeUnit {
 	Seconds,
@@ -68,20 +95,33 @@ impl From<TimeUnit> for i32 {
 }
 
 /// Error type for network requests.
-#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[derive(Error, PartialEq, Eq, Debug, Clone, Copy)]
 pub enum RequestError {
+	#[error("invalid descriptor")]
 	InvalidDescriptor,
+	#[error("invalid string")]
 	InvalidString,
+	#[error("invalid method")]
 	InvalidMethod,
+	#[error("invalid url")]
 	InvalidUrl,
+	#[error("invalid HTML")]
 	InvalidHtml,
+	#[error("invalid buffer size")]
 	InvalidBufferSize,
+	#[error("missing response data")]
 	MissingData,
+	#[error("missing response")]
 	MissingResponse,
+	#[error("missing url")]
 	MissingUrl,
+	#[error("request failed")]
 	RequestError,
+	#[error("failed to write to memory")]
 	FailedMemoryWrite,
+	#[error("response is not an image")]
 	NotAnImage,
+	#[error("request is closed")]
 	Closed,
 }
 
@@ -292,29 +332,64 @@ impl Request {
 	}
 
 	/// Get the raw data from the response, closing the request.
-	pub fn data(self) -> Result<Vec<u8>, RequestError> {
-		self.send()?.get_data()
+	///
+	/// Errors include the url and status code of the request.
+	pub fn data(self) -> Result<Vec<u8>, BunyError> {
+		let url = self.url.clone();
+		let response = self
+			.send()
+			.map_err(|error| BunyError::from(error).with_request(url.clone(), None))?;
+		let status = response.status_code();
+		response
+			.get_data()
+			.map_err(|error| BunyError::from(error).with_request(url, Some(status)))
 	}
 
 	/// Gets the response data as a string.
+	///
+	/// Errors include the url and status code of the request.
 	pub fn string(self) -> Result<String, BunyError> {
-		self.send()?.get_string()
+		let url = self.url.clone();
+		let response = self
+			.send()
+			.map_err(|error| BunyError::from(error).with_request(url.clone(), None))?;
+		response
+			.get_string()
+			.map_err(|error| error.with_request(url, Some(response.status_code())))
 	}
 
 	/// Get the response data as an HTML [Document].
-	pub fn html(self) -> Result<Document, RequestError> {
-		self.send()?.get_html()
+	///
+	/// Errors include the url and status code of the request.
+	pub fn html(self) -> Result<Document, BunyError> {
+		let url = self.url.clone();
+		let response = self
+			.send()
+			.map_err(|error| BunyError::from(error).with_request(url.clone(), None))?;
+		let status = response.status_code();
+		response
+			.get_html()
+			.map_err(|error| BunyError::from(error).with_request(url, Some(status)))
 	}
 }
 
 #[cfg(feature = "json")]
 impl Request {
 	/// Get the response data as an owned JSON value.
+	///
+	/// Errors include the url and status code of the request.
 	pub fn json_owned<T>(self) -> Result<T, BunyError>
 	where
 		T: serde::de::DeserializeOwned,
 	{
-		self.send()?.get_json_owned()
+		let url = self.url.clone();
+		let response = self
+			.send()
+			.map_err(|error| BunyError::from(error).with_request(url.clone(), None))?;
+		let status = response.status_code();
+		response
+			.get_json_owned()
+			.map_err(|error| error.with_request(url, Some(status)))
 	}
 }
 
@@ -325,6 +400,47 @@ impl Response {
 		unsafe { get_status_code(self.rid) }
 	}
 
+	/// Returns the [BunyError] that the response status code indicates, if any.
+	///
+	/// - `401` and `403` are [LoginRequired](BunyError::LoginRequired), or
+	///   [Cloudflare](BunyError::Cloudflare) if the response is a Cloudflare challenge.
+	/// - `404` and `410` are [NotFound](BunyError::NotFound).
+	/// - `429` is [RateLimited](BunyError::RateLimited), using the `Retry-After` header.
+	/// - `503` is [Cloudflare](BunyError::Cloudflare) if the response is a challenge.
+	///
+	/// # Examples
+	/// ```ignore
+	/// use buny::imports::net::Request;
+	/// let response = Request::get("https://example.com")?.send()?;
+	/// if let Some(error) = response.status_error() {
+	///     return Err(error);
+	/// }
+	/// ```
+	pub fn status_error(&self) -> Option<BunyError> {
+		let is_challenge = || {
+			self.get_header("cf-mitigated")
+				.is_some_and(|value| value == "challenge")
+				|| self
+					.get_header("Server")
+					.is_some_and(|value| value.eq_ignore_ascii_case("cloudflare"))
+		};
+		let challenge = || BunyError::Cloudflare {
+			url: self.get_url().unwrap_or_default(),
+		};
+		match self.status_code() {
+			401 | 403 if is_challenge() => Some(challenge()),
+			401 | 403 => Some(BunyError::LoginRequired),
+			404 | 410 => Some(BunyError::NotFound),
+			429 => Some(BunyError::RateLimited {
+				retry_after: self
+					.get_header("Retry-After")
+					.and_then(|value| value.trim().parse().ok()),
+			}),
+			503 if is_challenge() => Some(challenge()),
+			_ => None,
+		}
+	}
+
 	/// Get the final URL for the response.
 	pub fn get_url(&self) -> Option<String> {
 		let rid = unsafe { get_url(self.rid) };
@@ -453,3 +569,43 @@ impl Drop for Response {
 pub fn set_rate_limit(permits: i32, period: i32, unit: TimeUnit) {
 	unsafe { net_set_rate_limit(permits, period, unit.into()) }
 }
+
+/// Registers a mock response for requests in the test runner.
+///
+/// Requests to the url with the given method (or any method, if `None`) aren't sent,
+/// and instead respond with the given status code and data. Later mocks for the same
+/// request replace earlier ones.
+///
+/// This is only available with the `test` feature, and is useful for testing functions
+/// that need a logged in account, such as a [LibrarySyncProvider](crate::LibrarySyncProvider).
+///
+/// # Examples
+/// ```ignore
+/// use buny::imports::net::{mock_response, HttpMethod, Request};
+/// mock_response(Some(HttpMethod::Get), "https://example.com/library", 200, "[]")?;
+/// assert_eq!(Request::get("https://example.com/library")?.string()?, "[]");
+/// ```
+#[cfg(feature = "test")]
+pub fn mock_response<U: AsRef<str>, D: AsRef<[u8]>>(
+	method: Option<HttpMethod>,
+	url: U,
+	status: i32,
+	data: D,
+) -> Result<(), RequestError> {
+	let url = url.as_ref();
+	let data = data.as_ref();
+	let result = unsafe {
+		net_mock_response(
+			method.map(|method| method as i32).unwrap_or(-1),
+			url.as_ptr(),
+			url.len(),
+			status,
+			data.as_ptr(),
+			data.len(),
+		)
+	};
+	match RequestError::from(result) {
+		Some(error) => Err(error),
+		None => Ok(()),
+	}
+}
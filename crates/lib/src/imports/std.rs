@@ -1,12 +1,20 @@
 //! Module for standard Buny source library functions.
-use super::{FFIResult, Ptr, Rid};
+#[cfg(not(feature = "native"))]
+use super::FFIResult;
+use super::{Ptr, Rid};
 use crate::{
 	alloc::{String, Vec},
 	BunyError,
 };
+#[cfg(feature = "test")]
+use crate::{
+	Chapter, Comment, ContentBlock, HomeLayout, Novel, NovelGroup, NovelPageResult,
+	ReadingProgress, SearchSuggestion, UnlockOption, UnlockResult,
+};
 use core::ptr::null;
 use serde::{de::DeserializeOwned, Serialize};
 
+#[cfg(not(feature = "native"))]
 #[link(wasm_import_module = "std")]
 extern "C" {
 	pub(crate) fn destroy(rid: Rid);
@@ -35,6 +43,7 @@ extern "C" {
 }
 
 // env module
+#[cfg(not(feature = "native"))]
 #[link(wasm_import_module = "env")]
 extern "C" {
 	// #[link_name = "abort"]
@@ -50,6 +59,42 @@ extern "C" {
 	fn _send_partial_result(value: Ptr);
 }
 
+#[cfg(feature = "test")]
+#[cfg(not(feature = "native"))]
+#[link(wasm_import_module = "std")]
+extern "C" {
+	#[link_name = "snapshot"]
+	fn _snapshot(
+		name_ptr: *const u8,
+		name_len: usize,
+		expression_ptr: *const u8,
+		expression_len: usize,
+		kind_ptr: *const u8,
+		kind_len: usize,
+		value_ptr: *const u8,
+		value_len: usize,
+	) -> FFIResult;
+
+	#[link_name = "set_clock"]
+	fn _set_clock(
+		time: i64,
+		timezone_ptr: *const u8,
+		timezone_len: usize,
+		auto_advance: bool,
+	) -> FFIResult;
+}
+
+#[cfg(all(feature = "native", feature = "test"))]
+use crate::native::imports::std::{set_clock as _set_clock, snapshot as _snapshot};
+#[cfg(feature = "native")]
+pub(crate) use crate::native::imports::{
+	env::{print as _print, send_partial_result as _send_partial_result, sleep as _sleep},
+	std::{
+		buffer_len, current_date as _current_date, destroy, parse_date as _parse_date,
+		read_buffer as _read_buffer, utc_offset,
+	},
+};
+
 /// Error type for std functions.
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
 pub enum StdError {
@@ -58,6 +103,18 @@ pub enum StdError {
 	FailedMemoryWrite,
 	InvalidString,
 	InvalidDateString,
+	/// The value doesn't match its stored snapshot.
+	SnapshotMismatch,
+	/// The runner doesn't support snapshots, e.g. outside of a test.
+	SnapshotUnavailable,
+	/// The runner failed to read or write the snapshot file.
+	FailedSnapshotWrite,
+	/// The timezone identifier isn't valid.
+	InvalidTimezone,
+	/// There's no stored snapshot, and the runner doesn't create missing snapshots in CI.
+	SnapshotMissing,
+	/// The runner can't decode the snapshot value.
+	InvalidSnapshotValue,
 }
 
 impl StdError {
@@ -68,6 +125,12 @@ impl StdError {
 			-3 => Some(Self::FailedMemoryWrite),
 			-4 => Some(Self::InvalidString),
 			-5 => Some(Self::InvalidDateString),
+			-6 => Some(Self::SnapshotMismatch),
+			-7 => Some(Self::SnapshotUnavailable),
+			-8 => Some(Self::FailedSnapshotWrite),
+			-9 => Some(Self::InvalidTimezone),
+			-10 => Some(Self::SnapshotMissing),
+			-11 => Some(Self::InvalidSnapshotValue),
 			_ => None,
 		}
 	}
@@ -130,7 +193,8 @@ pub unsafe fn free_result(ptr: Ptr) {
 			cap_and_len[6],
 			cap_and_len[7],
 		]);
-		if len == -1 {
+		// error buffers start with a negative error code, followed by the length
+		if len < 0 {
 			let real_len_slice = ::core::slice::from_raw_parts(ptr.offset(8), 4);
 			let real_len = i32::from_le_bytes([
 				real_len_slice[0],
@@ -315,3 +379,101 @@ pub fn parse_date_with_options<T: AsRef<str>, U: AsRef<str>, V: AsRef<str>, W: A
 	}
 	Some(result as i64)
 }
+
+/// A value that can be compared with a snapshot by [assert_snapshot](crate::assert_snapshot).
+///
+/// Snapshot values are sent to the runner encoded with postcard, along with the name of their
+/// type, so the runner can decode them and format them the same way as `buny run` does.
+#[cfg(feature = "test")]
+pub trait Snapshot: Serialize {
+	/// The name of the type, which the runner uses to decode the value.
+	const KIND: &'static str;
+}
+
+#[cfg(feature = "test")]
+macro_rules! impl_snapshot {
+	($($name:ident),* $(,)?) => {
+		$(
+			impl Snapshot for $name {
+				const KIND: &'static str = stringify!($name);
+			}
+			impl Snapshot for Vec<$name> {
+				const KIND: &'static str = concat!("Vec<", stringify!($name), ">");
+			}
+		)*
+	};
+}
+
+#[cfg(feature = "test")]
+impl_snapshot!(
+	String,
+	Novel,
+	Chapter,
+	NovelPageResult,
+	ContentBlock,
+	HomeLayout,
+	Comment,
+	NovelGroup,
+	SearchSuggestion,
+	ReadingProgress,
+	UnlockOption,
+	UnlockResult,
+);
+
+/// Compares a value with its stored snapshot in the test runner.
+///
+/// This function is exposed for the [assert_snapshot](crate::assert_snapshot) macro and
+/// should not be used directly.
+#[cfg(feature = "test")]
+pub fn snapshot<T: Snapshot>(
+	name: Option<&str>,
+	expression: &str,
+	value: &T,
+) -> Result<(), StdError> {
+	let name = name.unwrap_or_default();
+	let kind = T::KIND;
+	let value = ::postcard::to_allocvec(value).map_err(|_| StdError::InvalidSnapshotValue)?;
+	let result = unsafe {
+		_snapshot(
+			name.as_ptr(),
+			name.len(),
+			expression.as_ptr(),
+			expression.len(),
+			kind.as_ptr(),
+			kind.len(),
+			value.as_ptr(),
+			value.len(),
+		)
+	};
+	match StdError::from(result) {
+		Some(error) => Err(error),
+		None => Ok(()),
+	}
+}
+
+/// Replaces the clock of the test runner with a virtual clock.
+///
+/// The virtual clock starts at the given Unix timestamp, and [current_date] returns its time
+/// instead of the system time. The timezone is used for [get_utc_offset] and [parse_local_date],
+/// and defaults to UTC. If `auto_advance` is set, [sleep] moves the clock forward instead of
+/// blocking; otherwise, the clock stays at the same time and sleeping returns immediately.
+///
+/// This is only available with the `test` feature, and makes tests that depend on the current
+/// time reproducible.
+///
+/// # Examples
+/// ```ignore
+/// use buny::imports::std::{current_date, mock_clock, sleep};
+/// mock_clock(1751374800, Some("Asia/Tokyo"), true).unwrap();
+/// sleep(60);
+/// assert_eq!(current_date(), 1751374860);
+/// ```
+#[cfg(feature = "test")]
+pub fn mock_clock(time: i64, timezone: Option<&str>, auto_advance: bool) -> Result<(), StdError> {
+	let timezone = timezone.unwrap_or_default();
+	let result = unsafe { _set_clock(time, timezone.as_ptr(), timezone.len(), auto_advance) };
+	match StdError::from(result) {
+		Some(error) => Err(error),
+		None => Ok(()),
+	}
+}
@@ -1,5 +1,5 @@
 #![doc = include_str!("../README.md")]
-#![no_std]
+#![cfg_attr(not(feature = "native"), no_std)]
 
 #[cfg(feature = "helpers")]
 pub mod helpers;
@@ -10,6 +10,9 @@ pub mod imports;
 #[cfg(feature = "imports")]
 mod macros;
 
+#[cfg(feature = "native")]
+pub mod native;
+
 mod structs;
 
 pub use structs::*;
@@ -59,6 +62,8 @@ pub mod alloc {
 /// The prelude macros.
 pub mod prelude {
 	pub use super::alloc::format;
+	#[cfg(feature = "test")]
+	pub use crate::assert_snapshot;
 	#[cfg(feature = "imports")]
 	pub use crate::{bail, debug, error, println, register_source};
 }
@@ -61,6 +61,49 @@ macro_rules! bail {
 	};
 }
 
+/// Asserts that a value matches its stored snapshot in the test runner.
+///
+/// The value is sent to the runner, which formats it the same way as `buny run` and compares it
+/// with a `.snap` file in the `snapshots` directory of the source, named after the test (and the
+/// snapshot name, if given). Values need to implement
+/// [Snapshot](crate::imports::std::Snapshot), which covers the source result types and lists of
+/// them.
+///
+/// Missing snapshots are created, except in CI (when the `CI` environment variable is set),
+/// where they fail the test. Mismatched snapshots are rewritten when the runner is given the
+/// `--update-snapshots` flag, which also creates missing snapshots in CI.
+///
+/// This is only available with the `test` feature.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[buny_test]
+/// fn test_search() -> buny::Result<()> {
+///     let results = MySource::new().get_search_novel_list(None, 1, Vec::new())?;
+///     assert_snapshot!(results);
+///     assert_snapshot!("first", results.entries[0]);
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "test")]
+#[macro_export]
+macro_rules! assert_snapshot {
+	(@inner $name:expr, $value:expr) => {
+		if let ::core::result::Result::Err(error) =
+			$crate::imports::std::snapshot($name, ::core::stringify!($value), &$value)
+		{
+			::core::panic!("snapshot assertion failed for `{}`: {:?}", ::core::stringify!($value), error);
+		}
+	};
+	($value:expr $(,)?) => {
+		$crate::assert_snapshot!(@inner ::core::option::Option::None, $value)
+	};
+	($name:expr, $value:expr $(,)?) => {
+		$crate::assert_snapshot!(@inner ::core::option::Option::Some($name), $value)
+	};
+}
+
 /// Registers a source for use with Buny.
 ///
 /// The first argument should be the struct that implements the Source trait, and the
@@ -82,6 +125,7 @@ macro_rules! register_source {
 	($source_type:ty $(, $param:ident)*) => {
 		static mut SOURCE: ::core::option::Option<$crate::alloc::Box<$source_type>> =
 			::core::option::Option::None;
+		static mut SOURCE_ERROR_VERSION: i32 = 0;
 
 		fn __source() -> &'static mut $source_type {
 			unsafe { SOURCE.as_deref_mut().unwrap() }
@@ -109,26 +153,14 @@ macro_rules! register_source {
 		}
 
 		fn __handle_error(error: &$crate::imports::error::BunyError) -> i32 {
-			$crate::prelude::println!("Error: {:?}", error);
-			match error {
-				$crate::imports::error::BunyError::Unimplemented => -2,
-				$crate::imports::error::BunyError::RequestError(_) => -3,
-				$crate::imports::error::BunyError::Message(string) => {
-					let mut buffer = (-1 as i32).to_le_bytes().to_vec();
-
-					buffer.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0]);
-					buffer.extend_from_slice(&string.as_bytes());
-
-					let cap_bytes = (buffer.capacity() as i32).to_le_bytes();
-					buffer[4..8].copy_from_slice(&cap_bytes);
-					let len_bytes = (buffer.len() as i32).to_le_bytes();
-					buffer[8..12].copy_from_slice(&len_bytes);
-
+			$crate::prelude::println!("Error: {}", error.info());
+			match error.encode(unsafe { SOURCE_ERROR_VERSION }) {
+				::core::option::Option::Some(buffer) => {
 					let ptr = buffer.as_ptr() as i32;
 					::core::mem::forget(buffer);
 					ptr
 				}
-				_ => -1,
+				::core::option::Option::None => error.code(),
 			}
 		}
 
@@ -141,10 +173,24 @@ macro_rules! register_source {
 			};
 		}
 
+		#[no_mangle]
+		#[export_name = "set_error_version"]
+		pub extern "C" fn __wasm_set_error_version(version: i32) -> i32 {
+			let version = version.clamp(0, $crate::ERROR_VERSION);
+			unsafe { SOURCE_ERROR_VERSION = version };
+			version
+		}
+
+		#[no_mangle]
+		#[export_name = "set_struct_version"]
+		pub extern "C" fn __wasm_set_struct_version(version: i32) -> i32 {
+			$crate::set_struct_version(version)
+		}
+
 		#[no_mangle]
 		#[export_name = "free_result"]
 		pub unsafe extern "C" fn __wasm_free_result(ptr: i32) {
-			$crate::imports::std::free_result(ptr);
+			$crate::imports::std::free_result(ptr as _);
 		}
 
 		#[no_mangle]
@@ -411,6 +457,219 @@ macro_rules! register_source {
 		}
 	};
 
+	(@single LibrarySyncProvider) => {
+		#[no_mangle]
+		#[export_name = "get_library"]
+		pub unsafe extern "C" fn __wasm_get_library(page: i32) -> i32 {
+			use $crate::LibrarySyncProvider;
+			let result = __source().get_library(page);
+			__handle_result(result)
+		}
+
+		#[no_mangle]
+		#[export_name = "add_to_library"]
+		pub unsafe extern "C" fn __wasm_add_to_library(novel_descriptor: i32) -> i32 {
+			let ::core::result::Result::Ok(novel) =
+				$crate::imports::std::read::<$crate::Novel>(novel_descriptor)
+			else {
+				return -1;
+			};
+			use $crate::LibrarySyncProvider;
+			let result = __source().add_to_library(novel);
+			__handle_result(result)
+		}
+
+		#[no_mangle]
+		#[export_name = "remove_from_library"]
+		pub unsafe extern "C" fn __wasm_remove_from_library(novel_descriptor: i32) -> i32 {
+			let ::core::result::Result::Ok(novel) =
+				$crate::imports::std::read::<$crate::Novel>(novel_descriptor)
+			else {
+				return -1;
+			};
+			use $crate::LibrarySyncProvider;
+			let result = __source().remove_from_library(novel);
+			__handle_result(result)
+		}
+	};
+
+	(@single CommentProvider) => {
+		#[no_mangle]
+		#[export_name = "get_comments"]
+		pub unsafe extern "C" fn __wasm_get_comments(
+			novel_descriptor: i32,
+			chapter_descriptor: i32,
+			page: i32,
+		) -> i32 {
+			let ::core::result::Result::Ok(novel) =
+				$crate::imports::std::read::<$crate::Novel>(novel_descriptor)
+			else {
+				return -1;
+			};
+			let chapter: ::core::option::Option<$crate::Chapter> = if chapter_descriptor < 0 {
+				None
+			} else if let ::core::result::Result::Ok(chapter) =
+				$crate::imports::std::read::<$crate::Chapter>(chapter_descriptor)
+			{
+				Some(chapter)
+			} else {
+				return -2;
+			};
+			use $crate::CommentProvider;
+			let result = __source().get_comments(novel, chapter, page);
+			__handle_result(result)
+		}
+
+		#[no_mangle]
+		#[export_name = "get_comment_replies"]
+		pub unsafe extern "C" fn __wasm_get_comment_replies(
+			novel_descriptor: i32,
+			chapter_descriptor: i32,
+			comment_descriptor: i32,
+			page: i32,
+		) -> i32 {
+			let ::core::result::Result::Ok(novel) =
+				$crate::imports::std::read::<$crate::Novel>(novel_descriptor)
+			else {
+				return -1;
+			};
+			let chapter: ::core::option::Option<$crate::Chapter> = if chapter_descriptor < 0 {
+				None
+			} else if let ::core::result::Result::Ok(chapter) =
+				$crate::imports::std::read::<$crate::Chapter>(chapter_descriptor)
+			{
+				Some(chapter)
+			} else {
+				return -2;
+			};
+			let ::core::result::Result::Ok(comment) =
+				$crate::imports::std::read::<$crate::Comment>(comment_descriptor)
+			else {
+				return -3;
+			};
+			use $crate::CommentProvider;
+			let result = __source().get_comment_replies(novel, chapter, comment, page);
+			__handle_result(result)
+		}
+	};
+
+	(@single SearchSuggestionProvider) => {
+		#[no_mangle]
+		#[export_name = "get_search_suggestions"]
+		pub unsafe extern "C" fn __wasm_get_search_suggestions(query_descriptor: i32) -> i32 {
+			let ::core::result::Result::Ok(query) =
+				$crate::imports::std::read::<$crate::alloc::String>(query_descriptor)
+			else {
+				return -1;
+			};
+			use $crate::SearchSuggestionProvider;
+			let result = __source().get_search_suggestions(query);
+			__handle_result(result)
+		}
+	};
+
+	(@single RecommendationProvider) => {
+		#[no_mangle]
+		#[export_name = "get_related_novels"]
+		pub unsafe extern "C" fn __wasm_get_related_novels(novel_descriptor: i32) -> i32 {
+			let ::core::result::Result::Ok(novel) =
+				$crate::imports::std::read::<$crate::Novel>(novel_descriptor)
+			else {
+				return -1;
+			};
+			use $crate::RecommendationProvider;
+			let result = __source().get_related_novels(novel);
+			__handle_result(result)
+		}
+	};
+
+	(@single ProgressSyncHandler) => {
+		#[no_mangle]
+		#[export_name = "mark_chapter_read"]
+		pub unsafe extern "C" fn __wasm_mark_chapter_read(
+			novel_descriptor: i32,
+			chapter_descriptor: i32,
+			position: f32,
+		) -> i32 {
+			let ::core::result::Result::Ok(novel) =
+				$crate::imports::std::read::<$crate::Novel>(novel_descriptor)
+			else {
+				return -1;
+			};
+			let ::core::result::Result::Ok(chapter) =
+				$crate::imports::std::read::<$crate::Chapter>(chapter_descriptor)
+			else {
+				return -2;
+			};
+			use $crate::ProgressSyncHandler;
+			let result = __source().mark_chapter_read(novel, chapter, position);
+			__handle_result(result)
+		}
+
+		#[no_mangle]
+		#[export_name = "get_remote_progress"]
+		pub unsafe extern "C" fn __wasm_get_remote_progress(novel_descriptor: i32) -> i32 {
+			let ::core::result::Result::Ok(novel) =
+				$crate::imports::std::read::<$crate::Novel>(novel_descriptor)
+			else {
+				return -1;
+			};
+			use $crate::ProgressSyncHandler;
+			let result = __source().get_remote_progress(novel);
+			__handle_result(result)
+		}
+	};
+
+	(@single ChapterUnlockHandler) => {
+		#[no_mangle]
+		#[export_name = "get_unlock_options"]
+		pub unsafe extern "C" fn __wasm_get_unlock_options(
+			novel_descriptor: i32,
+			chapter_descriptor: i32,
+		) -> i32 {
+			let ::core::result::Result::Ok(novel) =
+				$crate::imports::std::read::<$crate::Novel>(novel_descriptor)
+			else {
+				return -1;
+			};
+			let ::core::result::Result::Ok(chapter) =
+				$crate::imports::std::read::<$crate::Chapter>(chapter_descriptor)
+			else {
+				return -2;
+			};
+			use $crate::ChapterUnlockHandler;
+			let result = __source().get_unlock_options(novel, chapter);
+			__handle_result(result)
+		}
+
+		#[no_mangle]
+		#[export_name = "unlock_chapter"]
+		pub unsafe extern "C" fn __wasm_unlock_chapter(
+			novel_descriptor: i32,
+			chapter_descriptor: i32,
+			option_descriptor: i32,
+		) -> i32 {
+			let ::core::result::Result::Ok(novel) =
+				$crate::imports::std::read::<$crate::Novel>(novel_descriptor)
+			else {
+				return -1;
+			};
+			let ::core::result::Result::Ok(chapter) =
+				$crate::imports::std::read::<$crate::Chapter>(chapter_descriptor)
+			else {
+				return -2;
+			};
+			let ::core::result::Result::Ok(option) =
+				$crate::imports::std::read::<$crate::UnlockOption>(option_descriptor)
+			else {
+				return -3;
+			};
+			use $crate::ChapterUnlockHandler;
+			let result = __source().unlock_chapter(novel, chapter, option);
+			__handle_result(result)
+		}
+	};
+
 	(@single MigrationHandler) => {
 		#[no_mangle]
 		#[export_name = "handle_key_migration"]
@@ -0,0 +1,76 @@
+use chrono::{Local, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// The clock of the source runner.
+///
+/// By default, the system clock is used. A virtual clock starts at a fixed time and only moves
+/// when the source sleeps (if it auto-advances), so tests that depend on the current time are
+/// reproducible and don't have to wait.
+#[derive(Debug, Clone, Default)]
+pub struct Clock {
+	/// The current time of the virtual clock, or `None` for the system clock.
+	pub time: Option<i64>,
+	/// The local timezone, or `None` for the timezone of the system.
+	pub timezone: Option<Tz>,
+	/// Whether sleeping advances the virtual clock instead of blocking the thread.
+	pub auto_advance: bool,
+}
+
+impl Clock {
+	/// Creates a virtual clock starting at a Unix timestamp, in UTC, which advances when sleeping.
+	pub fn fixed(time: i64) -> Self {
+		Self {
+			time: Some(time),
+			timezone: Some(Tz::UTC),
+			auto_advance: true,
+		}
+	}
+
+	/// Returns the current Unix timestamp.
+	pub fn now(&self) -> i64 {
+		self.time.unwrap_or_else(|| Utc::now().timestamp())
+	}
+
+	/// Returns the difference between UTC and the local time at the current time, in seconds.
+	pub fn utc_offset(&self) -> i64 {
+		let now = Utc
+			.timestamp_opt(self.now(), 0)
+			.single()
+			.unwrap_or_default()
+			.naive_utc();
+		let offset = match self.timezone {
+			Some(timezone) => timezone.offset_from_utc_datetime(&now).fix(),
+			None => Local.offset_from_utc_datetime(&now),
+		};
+		offset.utc_minus_local() as i64
+	}
+
+	/// Returns the name of the local timezone, if it's known.
+	pub fn timezone_name(&self) -> Option<&'static str> {
+		self.timezone.map(|timezone| timezone.name())
+	}
+
+	/// Resolves the timezone identifier of a date, where `current` or no identifier is the
+	/// local timezone.
+	pub fn resolve_timezone<'a>(&self, timezone: Option<&'a str>) -> Option<&'a str> {
+		match timezone {
+			None | Some("current") => self.timezone_name(),
+			timezone => timezone,
+		}
+	}
+
+	/// Waits for a number of seconds, advancing the virtual clock if there is one.
+	///
+	/// A virtual clock that doesn't auto-advance returns immediately without moving.
+	pub fn sleep(&mut self, seconds: i32) {
+		let seconds = seconds.max(0);
+		match self.time.as_mut() {
+			Some(time) => {
+				if self.auto_advance {
+					*time += seconds as i64;
+				}
+			}
+			None => std::thread::sleep(std::time::Duration::from_secs(seconds as u64)),
+		}
+	}
+}
@@ -0,0 +1,546 @@
+/// The localized names used by dates in a language.
+///
+/// Each list contains every form of the names (wide, abbreviated, and standalone), since
+/// parsing accepts any of them regardless of the width of the pattern.
+pub(super) struct Locale {
+	/// The names of the months, starting with January.
+	pub months: &'static [[&'static str; 12]],
+	/// The names of the weekdays, starting with Sunday.
+	pub weekdays: &'static [[&'static str; 7]],
+	/// The names of the morning period.
+	pub am: &'static [&'static str],
+	/// The names of the afternoon period.
+	pub pm: &'static [&'static str],
+	/// The names of the era before the common era.
+	pub bc: &'static [&'static str],
+	/// The names of the common era.
+	pub ad: &'static [&'static str],
+}
+
+impl Locale {
+	/// Returns the names of a locale identifier, such as `fr_FR` or `zh-Hant`.
+	///
+	/// Only the language of the identifier is used, and unknown languages use English.
+	pub fn get(identifier: &str) -> &'static Locale {
+		let language = identifier
+			.split(['_', '-', '@'])
+			.next()
+			.unwrap_or_default()
+			.to_ascii_lowercase();
+		match language.as_str() {
+			"de" => &DE,
+			"es" => &ES,
+			"fr" => &FR,
+			"id" | "in" => &ID,
+			"it" => &IT,
+			"ja" => &JA,
+			"ko" => &KO,
+			"pt" => &PT,
+			"ru" => &RU,
+			"tr" => &TR,
+			"vi" => &VI,
+			"zh" => &ZH,
+			_ => &EN,
+		}
+	}
+}
+
+const AM_PM: (&[&str], &[&str]) = (&["AM", "a.m."], &["PM", "p.m."]);
+
+const NUMBERED_MONTHS: [&str; 12] = [
+	"1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+];
+
+static EN: Locale = Locale {
+	months: &[
+		[
+			"January",
+			"February",
+			"March",
+			"April",
+			"May",
+			"June",
+			"July",
+			"August",
+			"September",
+			"October",
+			"November",
+			"December",
+		],
+		[
+			"Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+		],
+		[
+			"Jan", "Feb", "Mar", "Apr", "May", "June", "July", "Aug", "Sept", "Oct", "Nov", "Dec",
+		],
+	],
+	weekdays: &[
+		[
+			"Sunday",
+			"Monday",
+			"Tuesday",
+			"Wednesday",
+			"Thursday",
+			"Friday",
+			"Saturday",
+		],
+		["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"],
+		["Sun", "Mon", "Tues", "Wed", "Thur", "Fri", "Sat"],
+	],
+	am: AM_PM.0,
+	pm: AM_PM.1,
+	bc: &["BC", "BCE", "Before Christ"],
+	ad: &["AD", "CE", "Anno Domini"],
+};
+
+static DE: Locale = Locale {
+	months: &[
+		[
+			"Januar",
+			"Februar",
+			"März",
+			"April",
+			"Mai",
+			"Juni",
+			"Juli",
+			"August",
+			"September",
+			"Oktober",
+			"November",
+			"Dezember",
+		],
+		[
+			"Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.", "Okt.", "Nov.",
+			"Dez.",
+		],
+		[
+			"Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+		],
+	],
+	weekdays: &[
+		[
+			"Sonntag",
+			"Montag",
+			"Dienstag",
+			"Mittwoch",
+			"Donnerstag",
+			"Freitag",
+			"Samstag",
+		],
+		["So.", "Mo.", "Di.", "Mi.", "Do.", "Fr.", "Sa."],
+	],
+	am: &["AM", "vorm."],
+	pm: &["PM", "nachm."],
+	bc: &["v. Chr.", "v.Chr."],
+	ad: &["n. Chr.", "n.Chr."],
+};
+
+static ES: Locale = Locale {
+	months: &[
+		[
+			"enero",
+			"febrero",
+			"marzo",
+			"abril",
+			"mayo",
+			"junio",
+			"julio",
+			"agosto",
+			"septiembre",
+			"octubre",
+			"noviembre",
+			"diciembre",
+		],
+		[
+			"ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic",
+		],
+		[
+			"ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic",
+		],
+	],
+	weekdays: &[
+		[
+			"domingo",
+			"lunes",
+			"martes",
+			"miércoles",
+			"jueves",
+			"viernes",
+			"sábado",
+		],
+		["dom", "lun", "mar", "mié", "jue", "vie", "sáb"],
+	],
+	am: &["a. m.", "a.m."],
+	pm: &["p. m.", "p.m."],
+	bc: &["a. C.", "antes de Cristo"],
+	ad: &["d. C.", "después de Cristo"],
+};
+
+static FR: Locale = Locale {
+	months: &[
+		[
+			"janvier",
+			"février",
+			"mars",
+			"avril",
+			"mai",
+			"juin",
+			"juillet",
+			"août",
+			"septembre",
+			"octobre",
+			"novembre",
+			"décembre",
+		],
+		[
+			"janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+			"nov.", "déc.",
+		],
+	],
+	weekdays: &[
+		[
+			"dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+		],
+		["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."],
+	],
+	am: AM_PM.0,
+	pm: AM_PM.1,
+	bc: &["av. J.-C.", "avant Jésus-Christ"],
+	ad: &["ap. J.-C.", "après Jésus-Christ"],
+};
+
+static ID: Locale = Locale {
+	months: &[
+		[
+			"Januari",
+			"Februari",
+			"Maret",
+			"April",
+			"Mei",
+			"Juni",
+			"Juli",
+			"Agustus",
+			"September",
+			"Oktober",
+			"November",
+			"Desember",
+		],
+		[
+			"Jan", "Feb", "Mar", "Apr", "Mei", "Jun", "Jul", "Agu", "Sep", "Okt", "Nov", "Des",
+		],
+		[
+			"Jan", "Feb", "Mar", "Apr", "Mei", "Jun", "Jul", "Agt", "Sep", "Okt", "Nov", "Des",
+		],
+	],
+	weekdays: &[
+		[
+			"Minggu", "Senin", "Selasa", "Rabu", "Kamis", "Jumat", "Sabtu",
+		],
+		["Min", "Sen", "Sel", "Rab", "Kam", "Jum", "Sab"],
+	],
+	am: AM_PM.0,
+	pm: AM_PM.1,
+	bc: &["SM", "Sebelum Masehi"],
+	ad: &["M", "Masehi"],
+};
+
+static IT: Locale = Locale {
+	months: &[
+		[
+			"gennaio",
+			"febbraio",
+			"marzo",
+			"aprile",
+			"maggio",
+			"giugno",
+			"luglio",
+			"agosto",
+			"settembre",
+			"ottobre",
+			"novembre",
+			"dicembre",
+		],
+		[
+			"gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+		],
+	],
+	weekdays: &[
+		[
+			"domenica",
+			"lunedì",
+			"martedì",
+			"mercoledì",
+			"giovedì",
+			"venerdì",
+			"sabato",
+		],
+		["dom", "lun", "mar", "mer", "gio", "ven", "sab"],
+	],
+	am: AM_PM.0,
+	pm: AM_PM.1,
+	bc: &["a.C.", "avanti Cristo"],
+	ad: &["d.C.", "dopo Cristo"],
+};
+
+static JA: Locale = Locale {
+	months: &[NUMBERED_MONTHS],
+	weekdays: &[
+		[
+			"日曜日",
+			"月曜日",
+			"火曜日",
+			"水曜日",
+			"木曜日",
+			"金曜日",
+			"土曜日",
+		],
+		["日", "月", "火", "水", "木", "金", "土"],
+	],
+	am: &["午前"],
+	pm: &["午後"],
+	bc: &["紀元前"],
+	ad: &["西暦"],
+};
+
+static KO: Locale = Locale {
+	months: &[[
+		"1월", "2월", "3월", "4월", "5월", "6월", "7월", "8월", "9월", "10월", "11월", "12월",
+	]],
+	weekdays: &[
+		[
+			"일요일",
+			"월요일",
+			"화요일",
+			"수요일",
+			"목요일",
+			"금요일",
+			"토요일",
+		],
+		["일", "월", "화", "수", "목", "금", "토"],
+	],
+	am: &["오전"],
+	pm: &["오후"],
+	bc: &["기원전", "BC"],
+	ad: &["서기", "AD"],
+};
+
+static PT: Locale = Locale {
+	months: &[
+		[
+			"janeiro",
+			"fevereiro",
+			"março",
+			"abril",
+			"maio",
+			"junho",
+			"julho",
+			"agosto",
+			"setembro",
+			"outubro",
+			"novembro",
+			"dezembro",
+		],
+		[
+			"jan.", "fev.", "mar.", "abr.", "mai.", "jun.", "jul.", "ago.", "set.", "out.", "nov.",
+			"dez.",
+		],
+	],
+	weekdays: &[
+		[
+			"domingo",
+			"segunda-feira",
+			"terça-feira",
+			"quarta-feira",
+			"quinta-feira",
+			"sexta-feira",
+			"sábado",
+		],
+		["dom.", "seg.", "ter.", "qua.", "qui.", "sex.", "sáb."],
+	],
+	am: AM_PM.0,
+	pm: AM_PM.1,
+	bc: &["a.C.", "antes de Cristo"],
+	ad: &["d.C.", "depois de Cristo"],
+};
+
+static RU: Locale = Locale {
+	months: &[
+		// the genitive forms are used with a day, e.g. "1 января"
+		[
+			"января",
+			"февраля",
+			"марта",
+			"апреля",
+			"мая",
+			"июня",
+			"июля",
+			"августа",
+			"сентября",
+			"октября",
+			"ноября",
+			"декабря",
+		],
+		[
+			"январь",
+			"февраль",
+			"март",
+			"апрель",
+			"май",
+			"июнь",
+			"июль",
+			"август",
+			"сентябрь",
+			"октябрь",
+			"ноябрь",
+			"декабрь",
+		],
+		[
+			"янв.",
+			"февр.",
+			"мар.",
+			"апр.",
+			"мая",
+			"июн.",
+			"июл.",
+			"авг.",
+			"сент.",
+			"окт.",
+			"нояб.",
+			"дек.",
+		],
+		[
+			"янв.",
+			"февр.",
+			"март",
+			"апр.",
+			"май",
+			"июнь",
+			"июль",
+			"авг.",
+			"сент.",
+			"окт.",
+			"нояб.",
+			"дек.",
+		],
+	],
+	weekdays: &[
+		[
+			"воскресенье",
+			"понедельник",
+			"вторник",
+			"среда",
+			"четверг",
+			"пятница",
+			"суббота",
+		],
+		["вс", "пн", "вт", "ср", "чт", "пт", "сб"],
+	],
+	am: AM_PM.0,
+	pm: AM_PM.1,
+	bc: &["до н. э.", "до н.э."],
+	ad: &["н. э.", "н.э."],
+};
+
+static TR: Locale = Locale {
+	months: &[
+		[
+			"Ocak", "Şubat", "Mart", "Nisan", "Mayıs", "Haziran", "Temmuz", "Ağustos", "Eylül",
+			"Ekim", "Kasım", "Aralık",
+		],
+		[
+			"Oca", "Şub", "Mar", "Nis", "May", "Haz", "Tem", "Ağu", "Eyl", "Eki", "Kas", "Ara",
+		],
+	],
+	weekdays: &[
+		[
+			"Pazar",
+			"Pazartesi",
+			"Salı",
+			"Çarşamba",
+			"Perşembe",
+			"Cuma",
+			"Cumartesi",
+		],
+		["Paz", "Pzt", "Sal", "Çar", "Per", "Cum", "Cmt"],
+	],
+	am: &["ÖÖ"],
+	pm: &["ÖS"],
+	bc: &["MÖ", "Milattan Önce"],
+	ad: &["MS", "Milattan Sonra"],
+};
+
+static VI: Locale = Locale {
+	months: &[
+		[
+			"tháng 1",
+			"tháng 2",
+			"tháng 3",
+			"tháng 4",
+			"tháng 5",
+			"tháng 6",
+			"tháng 7",
+			"tháng 8",
+			"tháng 9",
+			"tháng 10",
+			"tháng 11",
+			"tháng 12",
+		],
+		[
+			"thg 1", "thg 2", "thg 3", "thg 4", "thg 5", "thg 6", "thg 7", "thg 8", "thg 9",
+			"thg 10", "thg 11", "thg 12",
+		],
+	],
+	weekdays: &[
+		[
+			"Chủ Nhật",
+			"Thứ Hai",
+			"Thứ Ba",
+			"Thứ Tư",
+			"Thứ Năm",
+			"Thứ Sáu",
+			"Thứ Bảy",
+		],
+		["CN", "Th 2", "Th 3", "Th 4", "Th 5", "Th 6", "Th 7"],
+	],
+	am: &["SA"],
+	pm: &["CH"],
+	bc: &["Trước CN", "TCN"],
+	ad: &["Sau CN", "SCN"],
+};
+
+static ZH: Locale = Locale {
+	months: &[
+		NUMBERED_MONTHS,
+		[
+			"一月",
+			"二月",
+			"三月",
+			"四月",
+			"五月",
+			"六月",
+			"七月",
+			"八月",
+			"九月",
+			"十月",
+			"十一月",
+			"十二月",
+		],
+	],
+	weekdays: &[
+		[
+			"星期日",
+			"星期一",
+			"星期二",
+			"星期三",
+			"星期四",
+			"星期五",
+			"星期六",
+		],
+		["周日", "周一", "周二", "周三", "周四", "周五", "周六"],
+		["週日", "週一", "週二", "週三", "週四", "週五", "週六"],
+	],
+	am: &["上午"],
+	pm: &["下午"],
+	bc: &["公元前", "西元前"],
+	ad: &["公元", "西元"],
+};
@@ -0,0 +1,538 @@
+use chrono::{Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use locale::Locale;
+
+mod locale;
+
+/// Parses a date string with a `DateFormatter` format in a locale (or `en_US_POSIX`) and a
+/// timezone (or UTC), returning its Unix timestamp.
+///
+/// The format uses the Unicode date field symbols that `DateFormatter` supports, and the
+/// localized names of months, weekdays, day periods, and eras are read in the language of the
+/// locale. Only the Gregorian calendar is supported. Like `DateFormatter`, fields that aren't
+/// in the format default to the start of January 1st, 2000, and a timezone in the string
+/// overrides the given timezone.
+pub fn parse_date(
+	string: &str,
+	format: &str,
+	locale: Option<&str>,
+	timezone: Option<&str>,
+) -> Option<i64> {
+	let locale = Locale::get(locale.unwrap_or_default());
+	let tokens = tokenize(format)?;
+	let mut fields = Fields::default();
+	let mut input = Input(string);
+
+	for (idx, token) in tokens.iter().enumerate() {
+		match token {
+			Token::Literal(literal) => {
+				for c in literal.chars() {
+					input.literal(c)?;
+				}
+			}
+			Token::Field(symbol, count) => {
+				// numeric fields that are directly followed by another numeric field have a fixed width
+				let abutting = matches!(
+					tokens.get(idx + 1),
+					Some(Token::Field(next, next_count)) if is_numeric(*next, *next_count)
+				);
+				fields.parse(&mut input, locale, *symbol, *count, abutting)?;
+			}
+		}
+	}
+	input.skip_whitespace();
+	if !input.0.is_empty() {
+		return None;
+	}
+
+	let timezone = timezone
+		.and_then(|timezone| timezone.parse().ok())
+		.unwrap_or(Tz::UTC);
+	fields.timestamp(timezone)
+}
+
+enum Token {
+	Literal(String),
+	Field(char, usize),
+}
+
+// splits a format into pattern fields and quoted or unquoted literal text
+fn tokenize(format: &str) -> Option<Vec<Token>> {
+	let mut tokens = Vec::new();
+	let mut literal = String::new();
+	let mut chars = format.chars().peekable();
+	let mut quoted = false;
+
+	while let Some(c) = chars.next() {
+		if c == '\'' {
+			if chars.peek() == Some(&'\'') {
+				// two single quotes are an escaped quote, in or out of quoted text
+				chars.next();
+				literal.push('\'');
+			} else {
+				quoted = !quoted;
+			}
+		} else if quoted || !c.is_ascii_alphabetic() {
+			literal.push(c);
+		} else {
+			if !is_supported(c) {
+				return None;
+			}
+			let mut count = 1;
+			while chars.peek() == Some(&c) {
+				chars.next();
+				count += 1;
+			}
+			if !literal.is_empty() {
+				tokens.push(Token::Literal(core::mem::take(&mut literal)));
+			}
+			tokens.push(Token::Field(c, count));
+		}
+	}
+	if !literal.is_empty() {
+		tokens.push(Token::Literal(literal));
+	}
+	Some(tokens)
+}
+
+fn is_supported(symbol: char) -> bool {
+	"GyYurQqMLwWdDFgEecabBhHKkmsSAzZOvVXx".contains(symbol)
+}
+
+fn is_numeric(symbol: char, count: usize) -> bool {
+	match symbol {
+		'M' | 'L' | 'Q' | 'q' | 'e' | 'c' => count <= 2,
+		'y' | 'Y' | 'u' | 'r' | 'w' | 'W' | 'd' | 'D' | 'F' | 'g' | 'h' | 'H' | 'K' | 'k' | 'm'
+		| 's' | 'S' | 'A' => true,
+		_ => false,
+	}
+}
+
+/// The remaining text of the date string being parsed.
+struct Input<'a>(&'a str);
+
+impl Input<'_> {
+	fn skip_whitespace(&mut self) {
+		self.0 = self.0.trim_start();
+	}
+
+	fn literal(&mut self, c: char) -> Option<()> {
+		if c.is_whitespace() {
+			// any amount of whitespace matches whitespace in the format
+			self.skip_whitespace();
+			return Some(());
+		}
+		let mut chars = self.0.chars();
+		match chars.next() {
+			Some(next) if next.to_lowercase().eq(c.to_lowercase()) => {
+				self.0 = chars.as_str();
+				Some(())
+			}
+			// periods are optional, since abbreviated names may already include them
+			_ if c == '.' => Some(()),
+			_ => None,
+		}
+	}
+
+	fn digits(&mut self, min: usize, max: usize) -> Option<i64> {
+		let len = self
+			.0
+			.bytes()
+			.take(max)
+			.take_while(|byte| byte.is_ascii_digit())
+			.count();
+		if len < min {
+			return None;
+		}
+		let (digits, rest) = self.0.split_at(len);
+		self.0 = rest;
+		digits.parse().ok()
+	}
+
+	// reads the longest name that the text starts with, ignoring case
+	fn name<'n>(&mut self, names: impl IntoIterator<Item = &'n str>) -> Option<usize> {
+		let input: &Self = self;
+		let (idx, len) = names
+			.into_iter()
+			.enumerate()
+			.flat_map(|(idx, name)| {
+				// abbreviations can be written without their period
+				let short = name.strip_suffix('.');
+				[Some(name), short]
+					.into_iter()
+					.flatten()
+					.filter_map(move |name| input.prefix_len(name).map(|len| (idx, len)))
+			})
+			.max_by_key(|(_, len)| *len)?;
+		self.0 = &self.0[len..];
+		Some(idx)
+	}
+
+	fn prefix_len(&self, name: &str) -> Option<usize> {
+		let mut rest = self.0.chars();
+		for c in name.chars() {
+			let next = rest.next()?;
+			if !next.to_lowercase().eq(c.to_lowercase()) {
+				return None;
+			}
+		}
+		Some(self.0.len() - rest.as_str().len())
+	}
+
+	fn offset(&mut self) -> Option<i32> {
+		let sign = match self.0.chars().next()? {
+			'+' => 1,
+			'-' | '−' => -1,
+			_ => return None,
+		};
+		self.0 = &self.0[self.0.chars().next()?.len_utf8()..];
+		let start = self.0;
+		let (hours, minutes) = match self.digits(1, 4)? {
+			// hours and minutes without a separator
+			value if self.0.len() + 2 < start.len() => (value / 100, value % 100),
+			hours => {
+				let minutes = if self.0.starts_with(':') {
+					self.0 = &self.0[1..];
+					self.digits(2, 2)?
+				} else {
+					0
+				};
+				(hours, minutes)
+			}
+		};
+		if hours > 23 || minutes > 59 {
+			return None;
+		}
+		Some(sign * (hours * 3600 + minutes * 60) as i32)
+	}
+
+	fn zone(&mut self) -> Option<Zone> {
+		if let Some(offset) = self.offset() {
+			return Some(Zone::Offset(offset));
+		}
+		// localized offsets, e.g. GMT+9 or UTC-08:00
+		for prefix in ["GMT", "UTC", "UT"] {
+			if let Some(len) = self.prefix_len(prefix) {
+				self.0 = &self.0[len..];
+				return Some(Zone::Offset(self.offset().unwrap_or(0)));
+			}
+		}
+		// timezone identifiers, e.g. America/Los_Angeles
+		let len = self
+			.0
+			.find(|c: char| !c.is_ascii_alphanumeric() && !"/_-+".contains(c))
+			.unwrap_or(self.0.len());
+		if self.0[..len].contains('/') {
+			if let Ok(timezone) = self.0[..len].parse::<Tz>() {
+				self.0 = &self.0[len..];
+				return Some(Zone::Timezone(timezone));
+			}
+		}
+		if let Some(len) = self.prefix_len("Z") {
+			if !self.0[len..].starts_with(|c: char| c.is_alphabetic()) {
+				self.0 = &self.0[len..];
+				return Some(Zone::Offset(0));
+			}
+		}
+		let idx = self.name(ZONE_NAMES.iter().map(|(name, _)| *name))?;
+		let zone = match ZONE_NAMES[idx].1 {
+			ZoneName::Offset(offset) => Zone::Offset(offset),
+			ZoneName::Generic(timezone) => Zone::Timezone(timezone),
+		};
+		Some(zone)
+	}
+}
+
+#[derive(Clone, Copy)]
+enum Zone {
+	Offset(i32),
+	Timezone(Tz),
+}
+
+enum ZoneName {
+	Offset(i32),
+	Generic(Tz),
+}
+
+const HOUR: i32 = 3600;
+
+// the english names of common timezones, which are used for all locales like `DateFormatter` does
+const ZONE_NAMES: &[(&str, ZoneName)] = &[
+	("Coordinated Universal Time", ZoneName::Offset(0)),
+	("Greenwich Mean Time", ZoneName::Offset(0)),
+	("WET", ZoneName::Offset(0)),
+	("WEST", ZoneName::Offset(HOUR)),
+	("BST", ZoneName::Offset(HOUR)),
+	("CET", ZoneName::Offset(HOUR)),
+	("CEST", ZoneName::Offset(2 * HOUR)),
+	("Central European Standard Time", ZoneName::Offset(HOUR)),
+	("Central European Summer Time", ZoneName::Offset(2 * HOUR)),
+	("EET", ZoneName::Offset(2 * HOUR)),
+	("EEST", ZoneName::Offset(3 * HOUR)),
+	("MSK", ZoneName::Offset(3 * HOUR)),
+	("IST", ZoneName::Offset(5 * HOUR + 30 * 60)),
+	("India Standard Time", ZoneName::Offset(5 * HOUR + 30 * 60)),
+	("ICT", ZoneName::Offset(7 * HOUR)),
+	("WIB", ZoneName::Offset(7 * HOUR)),
+	("HKT", ZoneName::Offset(8 * HOUR)),
+	("SGT", ZoneName::Offset(8 * HOUR)),
+	("AWST", ZoneName::Offset(8 * HOUR)),
+	("China Standard Time", ZoneName::Offset(8 * HOUR)),
+	("JST", ZoneName::Offset(9 * HOUR)),
+	("Japan Standard Time", ZoneName::Offset(9 * HOUR)),
+	("KST", ZoneName::Offset(9 * HOUR)),
+	("Korean Standard Time", ZoneName::Offset(9 * HOUR)),
+	("ACST", ZoneName::Offset(9 * HOUR + 30 * 60)),
+	("AEST", ZoneName::Offset(10 * HOUR)),
+	("AEDT", ZoneName::Offset(11 * HOUR)),
+	("NZST", ZoneName::Offset(12 * HOUR)),
+	("NZDT", ZoneName::Offset(13 * HOUR)),
+	("HST", ZoneName::Offset(-10 * HOUR)),
+	("AKST", ZoneName::Offset(-9 * HOUR)),
+	("AKDT", ZoneName::Offset(-8 * HOUR)),
+	("PST", ZoneName::Offset(-8 * HOUR)),
+	("PDT", ZoneName::Offset(-7 * HOUR)),
+	("Pacific Standard Time", ZoneName::Offset(-8 * HOUR)),
+	("Pacific Daylight Time", ZoneName::Offset(-7 * HOUR)),
+	("MST", ZoneName::Offset(-7 * HOUR)),
+	("MDT", ZoneName::Offset(-6 * HOUR)),
+	("Mountain Standard Time", ZoneName::Offset(-7 * HOUR)),
+	("Mountain Daylight Time", ZoneName::Offset(-6 * HOUR)),
+	("CST", ZoneName::Offset(-6 * HOUR)),
+	("CDT", ZoneName::Offset(-5 * HOUR)),
+	("Central Standard Time", ZoneName::Offset(-6 * HOUR)),
+	("Central Daylight Time", ZoneName::Offset(-5 * HOUR)),
+	("EST", ZoneName::Offset(-5 * HOUR)),
+	("EDT", ZoneName::Offset(-4 * HOUR)),
+	("Eastern Standard Time", ZoneName::Offset(-5 * HOUR)),
+	("Eastern Daylight Time", ZoneName::Offset(-4 * HOUR)),
+	("PT", ZoneName::Generic(chrono_tz::America::Los_Angeles)),
+	(
+		"Pacific Time",
+		ZoneName::Generic(chrono_tz::America::Los_Angeles),
+	),
+	("MT", ZoneName::Generic(chrono_tz::America::Denver)),
+	(
+		"Mountain Time",
+		ZoneName::Generic(chrono_tz::America::Denver),
+	),
+	("CT", ZoneName::Generic(chrono_tz::America::Chicago)),
+	(
+		"Central Time",
+		ZoneName::Generic(chrono_tz::America::Chicago),
+	),
+	("ET", ZoneName::Generic(chrono_tz::America::New_York)),
+	(
+		"Eastern Time",
+		ZoneName::Generic(chrono_tz::America::New_York),
+	),
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum DayPeriod {
+	Am,
+	Pm,
+	Noon,
+	Midnight,
+	Night,
+}
+
+const DAY_PERIODS: &[(&str, DayPeriod)] = &[
+	("noon", DayPeriod::Noon),
+	("midnight", DayPeriod::Midnight),
+	("in the morning", DayPeriod::Am),
+	("in the afternoon", DayPeriod::Pm),
+	("in the evening", DayPeriod::Pm),
+	("at night", DayPeriod::Night),
+];
+
+/// The values of the fields read from a date string.
+#[derive(Default)]
+struct Fields {
+	before_common_era: bool,
+	year: Option<i64>,
+	two_digit_year: bool,
+	month: Option<u32>,
+	day: Option<u32>,
+	day_of_year: Option<u32>,
+	julian_day: Option<i64>,
+	hour: Option<(char, u32)>,
+	period: Option<DayPeriod>,
+	minute: Option<u32>,
+	second: Option<u32>,
+	milliseconds_in_day: Option<i64>,
+	zone: Option<Zone>,
+}
+
+impl Fields {
+	fn parse(
+		&mut self,
+		input: &mut Input,
+		locale: &Locale,
+		symbol: char,
+		count: usize,
+		abutting: bool,
+	) -> Option<()> {
+		// the range of digits that a numeric field can have
+		let width = |max: usize| {
+			if abutting && count > 1 {
+				(count, count)
+			} else {
+				(1, max.max(count))
+			}
+		};
+		let number = |input: &mut Input, max: usize| {
+			let (min, max) = width(max);
+			input.digits(min, max)
+		};
+
+		match symbol {
+			'G' => {
+				let names = locale.bc.iter().chain(locale.ad).copied();
+				let idx = input.name(names)?;
+				self.before_common_era = idx < locale.bc.len();
+			}
+			'y' | 'Y' | 'u' | 'r' => {
+				let start = input.0.len();
+				let year = number(input, 9)?;
+				// two digit years are only abbreviated if they're written with two digits
+				self.two_digit_year = symbol != 'u' && count == 2 && start - input.0.len() == 2;
+				self.year = Some(year);
+			}
+			'Q' | 'q' if count <= 2 => {
+				number(input, 2)?;
+			}
+			'Q' | 'q' => {
+				if input.name(["Q"]).is_some() {
+					input.digits(1, 1)?;
+				} else {
+					input.name(["1st quarter", "2nd quarter", "3rd quarter", "4th quarter"])?;
+				}
+			}
+			'M' | 'L' if count <= 2 => {
+				self.month = Some(number(input, 2)? as u32);
+			}
+			'M' | 'L' => {
+				let idx = input.name(locale.months.iter().flatten().copied())?;
+				self.month = Some((idx % 12) as u32 + 1);
+			}
+			'w' | 'W' | 'F' => {
+				number(input, 2)?;
+			}
+			'd' => self.day = Some(number(input, 2)? as u32),
+			'D' => self.day_of_year = Some(number(input, 3)? as u32),
+			'g' => self.julian_day = Some(number(input, 9)?),
+			'e' | 'c' if count <= 2 => {
+				number(input, 1)?;
+			}
+			'E' | 'e' | 'c' => {
+				// the weekday doesn't change the date, but it has to be valid
+				input.name(locale.weekdays.iter().flatten().copied())?;
+			}
+			'a' | 'b' | 'B' => {
+				let names = locale.am.iter().chain(locale.pm).copied();
+				let periods = if symbol == 'a' { &[][..] } else { DAY_PERIODS };
+				let names = names.chain(periods.iter().map(|(name, _)| *name));
+				let idx = input.name(names)?;
+				let period = if idx < locale.am.len() {
+					DayPeriod::Am
+				} else if idx < locale.am.len() + locale.pm.len() {
+					DayPeriod::Pm
+				} else {
+					periods[idx - locale.am.len() - locale.pm.len()].1
+				};
+				self.period = Some(period);
+			}
+			'h' | 'H' | 'K' | 'k' => self.hour = Some((symbol, number(input, 2)? as u32)),
+			'm' => self.minute = Some(number(input, 2)? as u32),
+			's' => self.second = Some(number(input, 2)? as u32),
+			'S' => {
+				// fractions of a second are read, but timestamps are in seconds
+				number(input, 9)?;
+			}
+			'A' => self.milliseconds_in_day = Some(number(input, 8)?),
+			'z' | 'Z' | 'O' | 'v' | 'V' | 'X' | 'x' => self.zone = Some(input.zone()?),
+			_ => return None,
+		}
+		Some(())
+	}
+
+	fn hour(&self) -> Option<u32> {
+		let Some((symbol, hour)) = self.hour else {
+			return Some(0);
+		};
+		let hour = match symbol {
+			'H' if hour < 24 => hour,
+			'k' if (1..=24).contains(&hour) => hour % 24,
+			'h' | 'K' if (symbol == 'h' && (1..=12).contains(&hour)) || hour < 12 => {
+				let hour = hour % 12;
+				match self.period {
+					Some(DayPeriod::Pm | DayPeriod::Noon) => hour + 12,
+					Some(DayPeriod::Night) if hour >= 6 => hour + 12,
+					_ => hour,
+				}
+			}
+			_ => return None,
+		};
+		Some(hour)
+	}
+
+	fn year(&self) -> i64 {
+		let Some(year) = self.year else {
+			return 2000;
+		};
+		let year = if self.two_digit_year {
+			// two digit years are in the century that ends twenty years from now
+			let end = Utc::now().year() as i64 + 20;
+			let year = end - end.rem_euclid(100) + year;
+			if year > end {
+				year - 100
+			} else {
+				year
+			}
+		} else {
+			year
+		};
+		if self.before_common_era {
+			1 - year
+		} else {
+			year
+		}
+	}
+
+	fn timestamp(&self, timezone: Tz) -> Option<i64> {
+		let date = if let Some(julian_day) = self.julian_day {
+			// modified julian days start on november 17th, 1858
+			NaiveDate::from_ymd_opt(1858, 11, 17)?.checked_add_signed(Duration::days(julian_day))?
+		} else {
+			let year = self.year().try_into().ok()?;
+			match self.day_of_year {
+				Some(day) if self.month.is_none() => NaiveDate::from_yo_opt(year, day)?,
+				_ => NaiveDate::from_ymd_opt(year, self.month.unwrap_or(1), self.day.unwrap_or(1))?,
+			}
+		};
+		let time = match self.milliseconds_in_day {
+			Some(milliseconds) if self.hour.is_none() => {
+				let seconds = u32::try_from(milliseconds / 1000).ok()?;
+				NaiveTime::from_num_seconds_from_midnight_opt(seconds, 0)?
+			}
+			_ => NaiveTime::from_hms_opt(
+				self.hour()?,
+				self.minute.unwrap_or(0),
+				self.second.unwrap_or(0),
+			)?,
+		};
+		let date = NaiveDateTime::new(date, time);
+		let date = match self.zone {
+			Some(Zone::Offset(offset)) => FixedOffset::east_opt(offset)?
+				.from_local_datetime(&date)
+				.earliest()?
+				.timestamp(),
+			Some(Zone::Timezone(timezone)) => {
+				timezone.from_local_datetime(&date).earliest()?.timestamp()
+			}
+			None => timezone.from_local_datetime(&date).earliest()?.timestamp(),
+		};
+		Some(date)
+	}
+}
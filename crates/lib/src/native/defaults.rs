@@ -12,6 +12,34 @@ pub enum DefaultValue {
 	HashMap(HashMap<String, String>),
 }
 
+impl DefaultValue {
+	/// Decodes a value of a kind that sources can set.
+	pub fn decode(kind: DefaultKind, data: Vec<u8>) -> Result<Self, postcard::Error> {
+		match kind {
+			DefaultKind::Data => Ok(DefaultValue::Data(data)),
+			DefaultKind::Bool => postcard::from_bytes(&data).map(DefaultValue::Bool),
+			DefaultKind::Int => postcard::from_bytes(&data).map(DefaultValue::Int),
+			DefaultKind::Float => postcard::from_bytes(&data).map(DefaultValue::Float),
+			DefaultKind::String => postcard::from_bytes(&data).map(DefaultValue::String),
+			DefaultKind::StringArray => postcard::from_bytes(&data).map(DefaultValue::StringArray),
+			DefaultKind::Null => Ok(DefaultValue::Null),
+		}
+	}
+
+	/// Encodes the value for a source to read, or returns `None` if it can't be read.
+	pub fn encode(&self) -> Option<Result<Vec<u8>, postcard::Error>> {
+		match self {
+			DefaultValue::Data(data) => Some(Ok(data.clone())),
+			DefaultValue::Bool(bool) => Some(postcard::to_allocvec(bool)),
+			DefaultValue::Int(int) => Some(postcard::to_allocvec(int)),
+			DefaultValue::Float(float) => Some(postcard::to_allocvec(float)),
+			DefaultValue::String(string) => Some(postcard::to_allocvec(string)),
+			DefaultValue::StringArray(array) => Some(postcard::to_allocvec(array)),
+			DefaultValue::Null | DefaultValue::HashMap(_) => None,
+		}
+	}
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DefaultKind {
 	Data,
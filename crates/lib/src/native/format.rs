@@ -0,0 +1,347 @@
+use crate::{
+	Chapter, Comment, ContentBlock, HomeComponentValue, HomeLayout, LinkValue, Novel, NovelGroup,
+	NovelPageResult, NovelStatus, ParagraphStyle, ReadingProgress, SearchSuggestion, TextAlignment,
+	TextDirection, TranslationStatus, UnlockOption, UnlockResult,
+};
+use serde::de::DeserializeOwned;
+use std::fmt::Write;
+
+/// Formats the details of a novel as `name: value` lines, skipping empty fields.
+pub fn format_novel(novel: &Novel) -> String {
+	let mut output = String::new();
+	let mut line = |name: &str, value: Option<String>| {
+		if let Some(value) = value {
+			_ = writeln!(output, "{name}: {value}");
+		}
+	};
+	let list = |values: &Option<Vec<String>>| values.as_ref().map(|values| values.join(", "));
+
+	line("key", Some(novel.key.clone()));
+	line("title", Some(novel.title.clone()));
+	line("alt titles", list(&novel.alt_titles));
+	line("authors", list(&novel.authors));
+	line("artists", list(&novel.artists));
+	line("cover", novel.cover.clone());
+	line("url", novel.url.clone());
+	line("tags", list(&novel.tags));
+	line(
+		"status",
+		(novel.status != NovelStatus::Unknown).then(|| format!("{:?}", novel.status)),
+	);
+	line(
+		"translation",
+		(novel.translation_status != TranslationStatus::Unknown)
+			.then(|| format!("{:?}", novel.translation_status)),
+	);
+	line("original language", novel.original_language.clone());
+	line(
+		"rating",
+		novel.rating.map(|rating| match novel.rating_count {
+			Some(count) => format!("{rating} ({count} ratings)"),
+			None => rating.to_string(),
+		}),
+	);
+	line("views", novel.views.map(|views| views.to_string()));
+	line("follows", novel.follows.map(|follows| follows.to_string()));
+	line("words", novel.word_count.map(|count| count.to_string()));
+	line(
+		"chapters",
+		novel
+			.chapters
+			.as_ref()
+			.map(|chapters| chapters.len() as i32)
+			.or(novel.chapter_count)
+			.map(|count| match novel.chapter_count {
+				Some(total) if total != count => format!("{count} of {total}"),
+				_ => count.to_string(),
+			}),
+	);
+	line("description", novel.description.clone());
+	output
+}
+
+/// Formats a chapter as a single line, with its number, title, and flags.
+pub fn format_chapter(chapter: &Chapter) -> String {
+	let mut output = String::new();
+	if chapter.is_volume_header {
+		output.push_str("[volume] ");
+	}
+	if let Some(volume) = chapter.volume_number {
+		_ = write!(output, "Vol. {volume} ");
+	}
+	if let Some(number) = chapter.chapter_number {
+		_ = write!(output, "Ch. {number} ");
+	}
+	if let Some(title) = &chapter.title {
+		_ = write!(output, "{title} ");
+	}
+	_ = write!(output, "({})", chapter.key);
+	if chapter.locked {
+		match chapter.unlock_cost {
+			Some(cost) => _ = write!(output, " [locked, {cost}]"),
+			None => output.push_str(" [locked]"),
+		}
+	}
+	if let Some(free_at) = chapter.free_at {
+		_ = write!(output, " [free at {free_at}]");
+	}
+	if let Some(words) = chapter.word_count {
+		_ = write!(output, " [{words} words]");
+	}
+	if let Some(url) = &chapter.external_url {
+		_ = write!(output, " [external: {url}]");
+	}
+	output
+}
+
+/// Formats an option for unlocking a chapter as a single line.
+pub fn format_unlock_option(option: &UnlockOption) -> String {
+	let mut output = format!("{} ({:?}, {})", option.title, option.kind, option.id);
+	if let Some(cost) = option.cost {
+		_ = write!(output, " [cost {cost}]");
+	}
+	if let Some(balance) = option.balance {
+		_ = write!(output, " [balance {balance}]");
+	}
+	if let Some(available_at) = option.available_at {
+		_ = write!(output, " [available at {available_at}]");
+	}
+	output
+}
+
+/// Formats the result of unlocking a chapter.
+pub fn format_unlock_result(result: &UnlockResult) -> String {
+	match result {
+		UnlockResult::Unlocked => "unlocked".into(),
+		UnlockResult::Scheduled { available_at } => format!("unlocks at {available_at}"),
+		UnlockResult::InsufficientBalance => "insufficient balance".into(),
+		UnlockResult::OpenUrl(url) => format!("open {url} to unlock"),
+	}
+}
+
+/// Formats a comment as a header line with its author and counts, followed by its body.
+pub fn format_comment(comment: &Comment) -> String {
+	let mut output = format!("{} ({})", comment.author, comment.key);
+	if let Some(posted_at) = comment.posted_at {
+		_ = write!(output, " [posted {posted_at}]");
+	}
+	if let Some(likes) = comment.likes {
+		_ = write!(output, " [{likes} likes]");
+	}
+	if let Some(replies) = comment.replies_count {
+		_ = write!(output, " [{replies} replies]");
+	}
+	for line in comment.body_markdown.lines() {
+		_ = write!(output, "\n  {line}");
+	}
+	output
+}
+
+/// Formats a group of novels as its title followed by a line for each novel.
+pub fn format_novel_group(group: &NovelGroup) -> String {
+	let mut output = format!("{} ({} entries)", group.title, group.entries.len());
+	for novel in &group.entries {
+		_ = write!(output, "\n  {} ({})", novel.title, novel.key);
+	}
+	output
+}
+
+/// Formats a search suggestion as a single line.
+pub fn format_search_suggestion(suggestion: &SearchSuggestion) -> String {
+	match &suggestion.cover {
+		Some(cover) => format!("{} ({}) [cover: {cover}]", suggestion.title, suggestion.key),
+		None => format!("{} ({})", suggestion.title, suggestion.key),
+	}
+}
+
+/// Formats reading progress as a single line, with the position as a percentage.
+pub fn format_reading_progress(progress: &ReadingProgress) -> String {
+	let mut output = format!(
+		"{} at {:.0}%",
+		progress.chapter_key,
+		progress.position * 100.0
+	);
+	if let Some(updated_at) = progress.updated_at {
+		_ = write!(output, " [updated {updated_at}]");
+	}
+	output
+}
+
+/// Formats a page of novels as a line for each novel, followed by whether there's a next page.
+pub fn format_novel_page(page: &NovelPageResult) -> String {
+	let mut output = String::new();
+	for novel in &page.entries {
+		_ = writeln!(output, "{} ({})", novel.title, novel.key);
+	}
+	_ = write!(
+		output,
+		"{} entries{}",
+		page.entries.len(),
+		if page.has_next_page {
+			", has next page"
+		} else {
+			""
+		}
+	);
+	output
+}
+
+/// Formats a block of chapter content as text.
+///
+/// Block quotes are prefixed with `>`, tables are written as rows of cells separated with `|`,
+/// and styled paragraphs are prefixed with their non-default style attributes.
+pub fn format_content_block(block: &ContentBlock) -> String {
+	match block {
+		ContentBlock::BlockQuote(text) => text
+			.lines()
+			.map(|line| format!("> {line}"))
+			.collect::<Vec<_>>()
+			.join("\n"),
+		ContentBlock::Paragraph(text, _) => text.clone(),
+		ContentBlock::Table(rows) => rows
+			.iter()
+			.map(|row| format!("| {} |", row.join(" | ")))
+			.collect::<Vec<_>>()
+			.join("\n"),
+		ContentBlock::Divider => "---".into(),
+		ContentBlock::StyledParagraph(text, style) => match format_paragraph_style(style) {
+			Some(style) => format!("[{style}] {text}"),
+			None => text.clone(),
+		},
+	}
+}
+
+fn format_paragraph_style(style: &ParagraphStyle) -> Option<String> {
+	let mut attributes = Vec::new();
+	if style.alignment != TextAlignment::Natural {
+		attributes.push(format!("{:?}", style.alignment).to_lowercase());
+	}
+	if style.direction != TextDirection::Natural {
+		attributes.push(format!("{:?}", style.direction).to_lowercase());
+	}
+	if style.italic {
+		attributes.push("italic".into());
+	}
+	if let Some(font_size) = &style.font_size {
+		attributes.push(format!("size {font_size}"));
+	}
+	(!attributes.is_empty()).then(|| attributes.join(", "))
+}
+
+/// Formats a home layout as a header line for each component, followed by its entries.
+pub fn format_home_layout(layout: &HomeLayout) -> String {
+	let mut output = String::new();
+	for (idx, component) in layout.components.iter().enumerate() {
+		if idx > 0 {
+			output.push('\n');
+		}
+		let kind = match &component.value {
+			HomeComponentValue::ImageScroller { .. } => "image scroller",
+			HomeComponentValue::Details { .. } => "details",
+			HomeComponentValue::Scroller { .. } => "scroller",
+			HomeComponentValue::Stack { .. } => "stack",
+			HomeComponentValue::Vertical { .. } => "vertical",
+			HomeComponentValue::ChapterFeed { .. } => "chapter feed",
+			HomeComponentValue::Filters(_) => "filters",
+			HomeComponentValue::Links(_) => "links",
+		};
+		match &component.title {
+			Some(title) => _ = write!(output, "{title} [{kind}]"),
+			None => _ = write!(output, "[{kind}]"),
+		}
+		if let Some(subtitle) = &component.subtitle {
+			_ = write!(output, "\n  {subtitle}");
+		}
+		let mut entry = |line: String| _ = write!(output, "\n  - {line}");
+		match &component.value {
+			HomeComponentValue::ImageScroller { links, .. } | HomeComponentValue::Links(links) => {
+				for link in links {
+					let value = match &link.value {
+						Some(LinkValue::Url(url)) => url.clone(),
+						Some(LinkValue::Listing(listing)) => format!("listing {}", listing.id),
+						Some(LinkValue::Novel(novel)) => novel.key.clone(),
+						Some(LinkValue::Chapter(item)) => {
+							format!("{} {}", item.novel.key, item.chapter.key)
+						}
+						None => "none".into(),
+					};
+					entry(format!("{} ({value})", link.title));
+				}
+			}
+			HomeComponentValue::Details { entries, .. }
+			| HomeComponentValue::Scroller { entries, .. }
+			| HomeComponentValue::Stack { entries, .. }
+			| HomeComponentValue::Vertical { entries, .. } => {
+				for novel in entries {
+					entry(format!("{} ({})", novel.title, novel.key));
+				}
+			}
+			HomeComponentValue::ChapterFeed { entries, .. } => {
+				for item in entries {
+					entry(format!(
+						"{}: {}",
+						item.novel.title,
+						format_chapter(&item.chapter)
+					));
+				}
+			}
+			HomeComponentValue::Filters(items) => {
+				for item in items {
+					entry(item.title.clone());
+				}
+			}
+		}
+	}
+	output
+}
+
+/// Formats a snapshot value with the formatter of its type.
+///
+/// Snapshot values are sent by sources as postcard bytes, along with the name of their type
+/// (see the `Snapshot` trait of the std imports). Lists are formatted with an entry per line,
+/// or separated by empty lines if their entries span multiple lines. Returns `None` if the type
+/// isn't known or the value can't be decoded.
+pub fn format_snapshot(kind: &str, bytes: &[u8]) -> Option<String> {
+	fn decode<T: DeserializeOwned>(bytes: &[u8], format: impl Fn(&T) -> String) -> Option<String> {
+		postcard::from_bytes::<T>(bytes)
+			.ok()
+			.map(|value| format(&value))
+	}
+	fn decode_list<T: DeserializeOwned>(
+		bytes: &[u8],
+		format: impl Fn(&T) -> String,
+		separator: &str,
+	) -> Option<String> {
+		postcard::from_bytes::<Vec<T>>(bytes)
+			.ok()
+			.map(|items| items.iter().map(format).collect::<Vec<_>>().join(separator))
+	}
+
+	match kind {
+		"String" => decode(bytes, String::clone),
+		"Vec<String>" => decode_list(bytes, String::clone, "\n"),
+		"Novel" => decode(bytes, format_novel),
+		"Vec<Novel>" => decode_list(bytes, format_novel, "\n\n"),
+		"Chapter" => decode(bytes, format_chapter),
+		"Vec<Chapter>" => decode_list(bytes, format_chapter, "\n"),
+		"NovelPageResult" => decode(bytes, format_novel_page),
+		"Vec<NovelPageResult>" => decode_list(bytes, format_novel_page, "\n\n"),
+		"ContentBlock" => decode(bytes, format_content_block),
+		"Vec<ContentBlock>" => decode_list(bytes, format_content_block, "\n\n"),
+		"HomeLayout" => decode(bytes, format_home_layout),
+		"Vec<HomeLayout>" => decode_list(bytes, format_home_layout, "\n\n"),
+		"Comment" => decode(bytes, format_comment),
+		"Vec<Comment>" => decode_list(bytes, format_comment, "\n"),
+		"NovelGroup" => decode(bytes, format_novel_group),
+		"Vec<NovelGroup>" => decode_list(bytes, format_novel_group, "\n"),
+		"SearchSuggestion" => decode(bytes, format_search_suggestion),
+		"Vec<SearchSuggestion>" => decode_list(bytes, format_search_suggestion, "\n"),
+		"ReadingProgress" => decode(bytes, format_reading_progress),
+		"Vec<ReadingProgress>" => decode_list(bytes, format_reading_progress, "\n"),
+		"UnlockOption" => decode(bytes, format_unlock_option),
+		"Vec<UnlockOption>" => decode_list(bytes, format_unlock_option, "\n"),
+		"UnlockResult" => decode(bytes, format_unlock_result),
+		"Vec<UnlockResult>" => decode_list(bytes, format_unlock_result, "\n"),
+		_ => None,
+	}
+}
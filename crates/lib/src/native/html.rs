@@ -1,5 +1,5 @@
 use ego_tree::NodeId;
-use scraper::{CaseSensitivity, ElementRef, Html, Selector};
+use scraper::{CaseSensitivity, ElementRef, Html, Node, Selector};
 use url::Url;
 
 #[derive(Debug, Clone)]
@@ -18,6 +18,59 @@ pub struct HtmlElement {
 #[derive(Debug, Clone)]
 pub struct HtmlElementList(pub Vec<HtmlElement>);
 
+/// A node of any kind, e.g. an element, text, or comment.
+#[derive(Debug, Clone)]
+pub struct HtmlNode {
+	pub html: Html,
+	pub id: NodeId,
+	pub base_uri: Option<Url>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HtmlNodeList(pub Vec<HtmlNode>);
+
+impl HtmlDocument {
+	/// Parses a document, using the base url to resolve `abs:` attributes.
+	pub fn parse(html: &str, base_url: &str) -> Self {
+		Self {
+			html: Html::parse_document(html),
+			base_uri: Url::parse(base_url).ok(),
+		}
+	}
+
+	/// Parses a fragment of a document, using the base url to resolve `abs:` attributes.
+	pub fn parse_fragment(html: &str, base_url: &str) -> Self {
+		Self {
+			html: Html::parse_fragment(html),
+			base_uri: Url::parse(base_url).ok(),
+		}
+	}
+
+	fn element(&self, element: ElementRef) -> HtmlElement {
+		HtmlElement {
+			html: self.html.clone(),
+			id: element.id(),
+			base_uri: self.base_uri.clone(),
+		}
+	}
+
+	pub fn select(&self, selector: &Selector) -> HtmlElementList {
+		HtmlElementList(
+			self.html
+				.select(selector)
+				.map(|element| self.element(element))
+				.collect(),
+		)
+	}
+
+	pub fn select_first(&self, selector: &Selector) -> Option<HtmlElement> {
+		self.html
+			.select(selector)
+			.next()
+			.map(|element| self.element(element))
+	}
+}
+
 impl HtmlElement {
 	pub fn select(&self, selector: &Selector) -> Option<HtmlElementList> {
 		let node = self.html.tree.get(self.id)?;
@@ -162,6 +215,28 @@ impl HtmlElement {
 			.map(|text| text.to_string())
 	}
 
+	/// Combined data of scripts, styles, and comments within this element.
+	pub fn data(&self) -> Option<String> {
+		let node = self.html.tree.get(self.id)?;
+		let mut data = String::new();
+		for descendant in node.descendants() {
+			match descendant.value() {
+				Node::Text(text) => {
+					let is_data = descendant
+						.parent()
+						.and_then(|parent| parent.value().as_element())
+						.is_some_and(|element| matches!(element.name(), "script" | "style"));
+					if is_data {
+						data.push_str(text);
+					}
+				}
+				Node::Comment(comment) => data.push_str(comment),
+				_ => {}
+			}
+		}
+		Some(data)
+	}
+
 	pub fn id(&self) -> Option<String> {
 		let node = self.html.tree.get(self.id)?;
 		let element = ElementRef::wrap(node)?;
@@ -201,6 +276,100 @@ impl HtmlElement {
 		};
 		element.value().attrs().any(|(k, _)| k == name)
 	}
+
+	pub fn as_node(&self) -> HtmlNode {
+		HtmlNode {
+			html: self.html.clone(),
+			id: self.id,
+			base_uri: self.base_uri.clone(),
+		}
+	}
+}
+
+impl HtmlNode {
+	fn with_id(&self, id: NodeId) -> HtmlNode {
+		HtmlNode {
+			html: self.html.clone(),
+			id,
+			base_uri: self.base_uri.clone(),
+		}
+	}
+
+	/// Returns the DOM node type constant for the node.
+	pub fn node_type(&self) -> Option<i32> {
+		let node = self.html.tree.get(self.id)?;
+		Some(match node.value() {
+			Node::Element(_) => 1,
+			Node::Text(_) => 3,
+			Node::ProcessingInstruction(_) => 7,
+			Node::Comment(_) => 8,
+			Node::Document => 9,
+			Node::Doctype(_) => 10,
+			Node::Fragment => 11,
+		})
+	}
+
+	/// Returns the contents of a text or comment node.
+	pub fn value(&self) -> Option<String> {
+		let node = self.html.tree.get(self.id)?;
+		match node.value() {
+			Node::Text(text) => Some(text.to_string()),
+			Node::Comment(comment) => Some(comment.to_string()),
+			_ => None,
+		}
+	}
+
+	/// Converts the node into an element, if it is one.
+	pub fn as_element(&self) -> Option<HtmlElement> {
+		let node = self.html.tree.get(self.id)?;
+		ElementRef::wrap(node).map(|element| HtmlElement {
+			html: self.html.clone(),
+			id: element.id(),
+			base_uri: self.base_uri.clone(),
+		})
+	}
+
+	pub fn child_nodes(&self) -> Option<HtmlNodeList> {
+		let node = self.html.tree.get(self.id)?;
+		Some(HtmlNodeList(
+			node.children()
+				.map(|child| self.with_id(child.id()))
+				.collect(),
+		))
+	}
+
+	/// Returns the descendants of the node in depth-first order, excluding the node itself.
+	pub fn descendants(&self) -> Option<HtmlNodeList> {
+		let node = self.html.tree.get(self.id)?;
+		Some(HtmlNodeList(
+			node.descendants()
+				.skip(1)
+				.map(|child| self.with_id(child.id()))
+				.collect(),
+		))
+	}
+
+	pub fn parent(&self) -> Option<HtmlElement> {
+		let node = self.html.tree.get(self.id)?;
+		let parent = ElementRef::wrap(node.parent()?)?;
+		Some(HtmlElement {
+			html: self.html.clone(),
+			id: parent.id(),
+			base_uri: self.base_uri.clone(),
+		})
+	}
+
+	pub fn next_sibling(&self) -> Option<HtmlNode> {
+		let node = self.html.tree.get(self.id)?;
+		node.next_sibling()
+			.map(|sibling| self.with_id(sibling.id()))
+	}
+
+	pub fn prev_sibling(&self) -> Option<HtmlNode> {
+		let node = self.html.tree.get(self.id)?;
+		node.prev_sibling()
+			.map(|sibling| self.with_id(sibling.id()))
+	}
 }
 
 impl HtmlElementList {
@@ -259,3 +428,46 @@ impl HtmlElementList {
 		)
 	}
 }
+
+/// Escapes the characters of a string that are special in html.
+pub fn escape_html(text: &str) -> String {
+	let mut escaped = String::with_capacity(text.len());
+	for c in text.chars() {
+		match c {
+			'&' => escaped.push_str("&amp;"),
+			'<' => escaped.push_str("&lt;"),
+			'>' => escaped.push_str("&gt;"),
+			_ => escaped.push(c),
+		}
+	}
+	escaped
+}
+
+/// Unescapes the html entities of a string that [escape_html] escapes.
+pub fn unescape_html(text: &str) -> String {
+	let mut unescaped = String::with_capacity(text.len());
+	let mut chars = text.chars().peekable();
+
+	while let Some(c) = chars.next() {
+		if c == '&' {
+			let mut entity = String::from("&");
+			while let Some(&next_c) = chars.peek() {
+				entity.push(next_c);
+				if next_c == ';' || entity.len() > 10 {
+					chars.next();
+					break;
+				}
+				chars.next();
+			}
+			match entity.as_str() {
+				"&amp;" => unescaped.push('&'),
+				"&lt;" => unescaped.push('<'),
+				"&gt;" => unescaped.push('>'),
+				_ => unescaped.push_str(&entity),
+			}
+		} else {
+			unescaped.push(c);
+		}
+	}
+	unescaped
+}
@@ -0,0 +1,60 @@
+use super::{read_bytes, read_string};
+use crate::native::{with_env, DefaultValue, StoreItem};
+
+enum Result {
+	Success,
+	InvalidKey,
+	InvalidValue,
+	FailedEncoding,
+	FailedDecoding,
+}
+
+impl From<Result> for i32 {
+	fn from(result: Result) -> Self {
+		match result {
+			Result::Success => 0,
+			Result::InvalidKey => -1,
+			Result::InvalidValue => -2,
+			Result::FailedEncoding => -3,
+			Result::FailedDecoding => -4,
+		}
+	}
+}
+
+pub(crate) unsafe fn get(key: *const u8, len: usize) -> i32 {
+	let Some(key) = (unsafe { read_string(key, len) }) else {
+		return Result::InvalidKey.into();
+	};
+	with_env(|env| {
+		let Some(value) = env.defaults.get(&key) else {
+			return Result::InvalidValue.into();
+		};
+		match value.encode() {
+			Some(Ok(data)) => env.store.store(StoreItem::Encoded(data)),
+			Some(Err(_)) => Result::FailedEncoding.into(),
+			None => Result::InvalidValue.into(),
+		}
+	})
+}
+
+pub(crate) unsafe fn set(key: *const u8, len: usize, kind: u8, value: isize) -> i32 {
+	let Some(key) = (unsafe { read_string(key, len) }) else {
+		return Result::InvalidKey.into();
+	};
+	if kind > 6 {
+		return Result::InvalidValue.into();
+	}
+
+	// the value is encoded with its length (including the 8 byte header) at the start
+	let ptr = value as *const u8;
+	let header = unsafe { read_bytes(ptr, 4) };
+	let len = u32::from_le_bytes([header[0], header[1], header[2], header[3]]) as usize;
+	let data = unsafe { read_bytes(ptr.add(8), len.saturating_sub(8)) }.to_vec();
+
+	let Ok(value) = DefaultValue::decode(kind.into(), data) else {
+		return Result::FailedDecoding.into();
+	};
+	with_env(|env| env.defaults.set(key, value));
+
+	Result::Success.into()
+}
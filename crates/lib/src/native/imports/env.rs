@@ -0,0 +1,17 @@
+use super::read_string;
+use crate::native::with_env;
+
+pub(crate) unsafe fn print(string: *const u8, size: usize) {
+	match unsafe { read_string(string, size) } {
+		Some(string) => println!("{string}"),
+		None => println!("error: failed to read string for printing."),
+	}
+}
+
+pub(crate) unsafe fn sleep(seconds: i32) {
+	with_env(|env| env.clock.sleep(seconds));
+}
+
+pub(crate) unsafe fn send_partial_result(_value: isize) {
+	// partial results are only used by the app to show results while loading
+}
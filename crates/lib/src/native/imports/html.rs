@@ -0,0 +1,411 @@
+use super::read_string;
+use crate::native::{
+	escape_html, unescape_html, with_env, HtmlDocument, HtmlElement, HtmlElementList, HtmlNode,
+	Rid, StoreItem,
+};
+use scraper::Selector;
+
+enum Result {
+	// Success,
+	InvalidDescriptor,
+	InvalidString,
+	// InvalidHtml,
+	InvalidQuery,
+	#[allow(clippy::enum_variant_names)]
+	NoResult,
+	// SwiftSoupError,
+}
+
+impl From<Result> for i32 {
+	fn from(result: Result) -> Self {
+		match result {
+			// Result::Success => 0,
+			Result::InvalidDescriptor => -1,
+			Result::InvalidString => -2,
+			Result::InvalidQuery => -4,
+			Result::NoResult => -5,
+			// Result::SwiftSoupError => -6,
+		}
+	}
+}
+
+// stores the item created from a stored item, or returns the error code
+fn map_item(
+	rid: Rid,
+	f: impl FnOnce(&mut StoreItem) -> core::result::Result<StoreItem, Result>,
+) -> i32 {
+	with_env(|env| {
+		let Some(item) = env.store.get_mut(rid) else {
+			return Result::InvalidDescriptor.into();
+		};
+		match f(item) {
+			Ok(item) => env.store.store(item),
+			Err(error) => error.into(),
+		}
+	})
+}
+
+// stores a string created from an element or element list
+fn map_string(
+	rid: Rid,
+	element_string: impl FnOnce(&HtmlElement) -> Option<String>,
+	list_string: impl FnOnce(&HtmlElementList) -> Option<String>,
+) -> i32 {
+	map_item(rid, |item| {
+		let string = if let Some(element) = item.as_html_element() {
+			element_string(element)
+		} else if let Some(elements) = item.as_html_element_list() {
+			list_string(elements)
+		} else {
+			return Err(Result::InvalidDescriptor);
+		};
+		string.map(StoreItem::String).ok_or(Result::NoResult)
+	})
+}
+
+// stores a string created from an element
+fn map_element_string(rid: Rid, f: impl FnOnce(&HtmlElement) -> Option<String>) -> i32 {
+	map_item(rid, |item| {
+		let element = item.as_html_element().ok_or(Result::InvalidDescriptor)?;
+		f(element).map(StoreItem::String).ok_or(Result::NoResult)
+	})
+}
+
+fn item_as_node(item: &mut StoreItem) -> Option<HtmlNode> {
+	if let Some(element) = item.as_html_element() {
+		Some(element.as_node())
+	} else {
+		item.as_html_node().cloned()
+	}
+}
+
+// elements are stored as elements so that all element functions can be used on them
+fn node_item(node: HtmlNode) -> StoreItem {
+	match node.as_element() {
+		Some(element) => StoreItem::HtmlElement(element),
+		None => StoreItem::HtmlNode(node),
+	}
+}
+
+unsafe fn parse_with(
+	html_ptr: *const u8,
+	html_len: usize,
+	base_url_ptr: *const u8,
+	base_url_len: usize,
+	parse: fn(&str, &str) -> HtmlDocument,
+) -> i32 {
+	let Some(html) = (unsafe { read_string(html_ptr, html_len) }) else {
+		return Result::InvalidString.into();
+	};
+	let Some(base_url) = (unsafe { read_string(base_url_ptr, base_url_len) }) else {
+		return Result::InvalidString.into();
+	};
+	let document = parse(&html, &base_url);
+	with_env(|env| env.store.store(StoreItem::HtmlDocument(document)))
+}
+
+pub(crate) unsafe fn parse(
+	html: *const u8,
+	html_len: usize,
+	base_url: *const u8,
+	base_url_len: usize,
+) -> i32 {
+	unsafe { parse_with(html, html_len, base_url, base_url_len, HtmlDocument::parse) }
+}
+pub(crate) unsafe fn parse_fragment(
+	html: *const u8,
+	html_len: usize,
+	base_url: *const u8,
+	base_url_len: usize,
+) -> i32 {
+	unsafe {
+		parse_with(
+			html,
+			html_len,
+			base_url,
+			base_url_len,
+			HtmlDocument::parse_fragment,
+		)
+	}
+}
+pub(crate) unsafe fn escape(text: *const u8, text_len: usize) -> i32 {
+	let Some(text) = (unsafe { read_string(text, text_len) }) else {
+		return Result::InvalidString.into();
+	};
+	with_env(|env| env.store.store(StoreItem::String(escape_html(&text))))
+}
+pub(crate) unsafe fn unescape(text: *const u8, text_len: usize) -> i32 {
+	let Some(text) = (unsafe { read_string(text, text_len) }) else {
+		return Result::InvalidString.into();
+	};
+	with_env(|env| env.store.store(StoreItem::String(unescape_html(&text))))
+}
+
+unsafe fn read_selector(query: *const u8, query_len: usize) -> core::result::Result<Selector, i32> {
+	let Some(query) = (unsafe { read_string(query, query_len) }) else {
+		return Err(Result::InvalidString.into());
+	};
+	Selector::parse(&query).map_err(|_| Result::InvalidQuery.into())
+}
+
+pub(crate) unsafe fn select(rid: Rid, query: *const u8, query_len: usize) -> i32 {
+	let selector = match unsafe { read_selector(query, query_len) } {
+		Ok(selector) => selector,
+		Err(error) => return error,
+	};
+	map_item(rid, |item| {
+		let elements = if let Some(document) = item.as_html_document() {
+			Some(document.select(&selector))
+		} else if let Some(element) = item.as_html_element() {
+			element.select(&selector)
+		} else if let Some(elements) = item.as_html_element_list() {
+			elements.select(&selector)
+		} else {
+			return Err(Result::InvalidDescriptor);
+		};
+		elements
+			.map(StoreItem::HtmlElementList)
+			.ok_or(Result::NoResult)
+	})
+}
+pub(crate) unsafe fn select_first(rid: Rid, query: *const u8, query_len: usize) -> i32 {
+	let selector = match unsafe { read_selector(query, query_len) } {
+		Ok(selector) => selector,
+		Err(error) => return error,
+	};
+	map_item(rid, |item| {
+		let element = if let Some(document) = item.as_html_document() {
+			document.select_first(&selector)
+		} else if let Some(element) = item.as_html_element() {
+			element.select_first(&selector)
+		} else if let Some(elements) = item.as_html_element_list() {
+			elements.select_first(&selector)
+		} else {
+			return Err(Result::InvalidDescriptor);
+		};
+		element.map(StoreItem::HtmlElement).ok_or(Result::NoResult)
+	})
+}
+pub(crate) unsafe fn attr(rid: Rid, key: *const u8, key_len: usize) -> i32 {
+	let Some(key) = (unsafe { read_string(key, key_len) }) else {
+		return Result::InvalidString.into();
+	};
+	map_string(rid, |element| element.attr(&key), |list| list.attr(&key))
+}
+pub(crate) unsafe fn text(rid: Rid) -> i32 {
+	map_string(rid, |element| element.text(true), |list| list.text(true))
+}
+pub(crate) unsafe fn untrimmed_text(rid: Rid) -> i32 {
+	map_string(rid, |element| element.text(false), |list| list.text(false))
+}
+pub(crate) unsafe fn html(rid: Rid) -> i32 {
+	map_string(rid, |element| element.html(), |list| list.html())
+}
+pub(crate) unsafe fn outer_html(rid: Rid) -> i32 {
+	map_string(
+		rid,
+		|element| element.outer_html(),
+		|list| list.outer_html(),
+	)
+}
+
+pub(crate) unsafe fn set_text(_rid: Rid, _text: *const u8, _text_len: usize) -> i32 {
+	-1
+}
+pub(crate) unsafe fn set_html(_rid: Rid, _html: *const u8, _html_len: usize) -> i32 {
+	-1
+}
+pub(crate) unsafe fn prepend(_rid: Rid, _html: *const u8, _html_len: usize) -> i32 {
+	-1
+}
+pub(crate) unsafe fn append(_rid: Rid, _html: *const u8, _html_len: usize) -> i32 {
+	-1
+}
+pub(crate) unsafe fn parent(rid: Rid) -> i32 {
+	map_item(rid, |item| {
+		let parent = if let Some(element) = item.as_html_element() {
+			element.parent()
+		} else if let Some(node) = item.as_html_node() {
+			node.parent()
+		} else {
+			return Err(Result::InvalidDescriptor);
+		};
+		parent.map(StoreItem::HtmlElement).ok_or(Result::NoResult)
+	})
+}
+pub(crate) unsafe fn children(rid: Rid) -> i32 {
+	map_item(rid, |item| {
+		let element = item.as_html_element().ok_or(Result::InvalidDescriptor)?;
+		element
+			.children()
+			.map(StoreItem::HtmlElementList)
+			.ok_or(Result::NoResult)
+	})
+}
+pub(crate) unsafe fn siblings(rid: Rid) -> i32 {
+	map_item(rid, |item| {
+		let element = item.as_html_element().ok_or(Result::InvalidDescriptor)?;
+		element
+			.siblings()
+			.map(StoreItem::HtmlElementList)
+			.ok_or(Result::NoResult)
+	})
+}
+pub(crate) unsafe fn next(rid: Rid) -> i32 {
+	map_item(rid, |item| {
+		let element = item.as_html_element().ok_or(Result::InvalidDescriptor)?;
+		element
+			.next_sibling()
+			.map(StoreItem::HtmlElement)
+			.ok_or(Result::NoResult)
+	})
+}
+pub(crate) unsafe fn previous(rid: Rid) -> i32 {
+	map_item(rid, |item| {
+		let element = item.as_html_element().ok_or(Result::InvalidDescriptor)?;
+		element
+			.prev_sibling()
+			.map(StoreItem::HtmlElement)
+			.ok_or(Result::NoResult)
+	})
+}
+pub(crate) unsafe fn base_uri(rid: Rid) -> i32 {
+	map_element_string(rid, |element| {
+		element.base_uri.as_ref().map(|uri| uri.to_string())
+	})
+}
+pub(crate) unsafe fn own_text(rid: Rid) -> i32 {
+	map_element_string(rid, |element| element.own_text())
+}
+pub(crate) unsafe fn data(rid: Rid) -> i32 {
+	map_element_string(rid, |element| element.data())
+}
+pub(crate) unsafe fn id(rid: Rid) -> i32 {
+	map_element_string(rid, |element| element.id())
+}
+pub(crate) unsafe fn tag_name(rid: Rid) -> i32 {
+	map_element_string(rid, |element| element.tag_name())
+}
+pub(crate) unsafe fn class_name(rid: Rid) -> i32 {
+	map_element_string(rid, |element| element.class_name())
+}
+pub(crate) unsafe fn has_class(rid: Rid, class: *const u8, class_len: usize) -> bool {
+	let Some(class) = (unsafe { read_string(class, class_len) }) else {
+		return false;
+	};
+	with_env(|env| {
+		env.store
+			.get_mut(rid)
+			.and_then(|item| item.as_html_element())
+			.is_some_and(|element| element.has_class(&class))
+	})
+}
+pub(crate) unsafe fn has_attr(rid: Rid, attr: *const u8, attr_len: usize) -> bool {
+	let Some(attr) = (unsafe { read_string(attr, attr_len) }) else {
+		return false;
+	};
+	with_env(|env| {
+		env.store
+			.get_mut(rid)
+			.and_then(|item| item.as_html_element())
+			.is_some_and(|element| element.has_attr(&attr))
+	})
+}
+
+pub(crate) unsafe fn child_nodes(rid: Rid) -> i32 {
+	map_item(rid, |item| {
+		let node = item_as_node(item).ok_or(Result::InvalidDescriptor)?;
+		node.child_nodes()
+			.map(StoreItem::HtmlNodeList)
+			.ok_or(Result::NoResult)
+	})
+}
+pub(crate) unsafe fn walk(rid: Rid) -> i32 {
+	map_item(rid, |item| {
+		let node = item_as_node(item).ok_or(Result::InvalidDescriptor)?;
+		node.descendants()
+			.map(StoreItem::HtmlNodeList)
+			.ok_or(Result::NoResult)
+	})
+}
+pub(crate) unsafe fn next_sibling_node(rid: Rid) -> i32 {
+	map_item(rid, |item| {
+		let node = item_as_node(item).ok_or(Result::InvalidDescriptor)?;
+		node.next_sibling().map(node_item).ok_or(Result::NoResult)
+	})
+}
+pub(crate) unsafe fn previous_sibling_node(rid: Rid) -> i32 {
+	map_item(rid, |item| {
+		let node = item_as_node(item).ok_or(Result::InvalidDescriptor)?;
+		node.prev_sibling().map(node_item).ok_or(Result::NoResult)
+	})
+}
+pub(crate) unsafe fn node_type(rid: Rid) -> i32 {
+	with_env(|env| {
+		env.store
+			.get_mut(rid)
+			.and_then(item_as_node)
+			.and_then(|node| node.node_type())
+			.unwrap_or(Result::InvalidDescriptor.into())
+	})
+}
+pub(crate) unsafe fn node_value(rid: Rid) -> i32 {
+	map_item(rid, |item| {
+		let node = item_as_node(item).ok_or(Result::InvalidDescriptor)?;
+		node.value().map(StoreItem::String).ok_or(Result::NoResult)
+	})
+}
+
+pub(crate) unsafe fn first(rid: Rid) -> i32 {
+	map_item(rid, |item| {
+		let elements = item.as_html_element_list();
+		elements
+			.and_then(|elements| elements.0.first().cloned())
+			.map(StoreItem::HtmlElement)
+			.ok_or(Result::InvalidDescriptor)
+	})
+}
+pub(crate) unsafe fn last(rid: Rid) -> i32 {
+	map_item(rid, |item| {
+		let elements = item.as_html_element_list();
+		elements
+			.and_then(|elements| elements.0.last().cloned())
+			.map(StoreItem::HtmlElement)
+			.ok_or(Result::InvalidDescriptor)
+	})
+}
+pub(crate) unsafe fn get(rid: Rid, index: usize) -> i32 {
+	map_item(rid, |item| {
+		if let Some(elements) = item.as_html_element_list() {
+			elements
+				.0
+				.get(index)
+				.cloned()
+				.map(StoreItem::HtmlElement)
+				.ok_or(Result::InvalidDescriptor)
+		} else if let Some(nodes) = item.as_html_node_list() {
+			nodes
+				.0
+				.get(index)
+				.cloned()
+				.map(node_item)
+				.ok_or(Result::InvalidDescriptor)
+		} else {
+			Err(Result::InvalidDescriptor)
+		}
+	})
+}
+pub(crate) unsafe fn size(rid: Rid) -> i32 {
+	with_env(|env| {
+		let Some(item) = env.store.get_mut(rid) else {
+			return Result::InvalidDescriptor.into();
+		};
+		if let Some(elements) = item.as_html_element_list() {
+			elements.0.len() as i32
+		} else if let Some(nodes) = item.as_html_node_list() {
+			nodes.0.len() as i32
+		} else {
+			Result::InvalidDescriptor.into()
+		}
+	})
+}
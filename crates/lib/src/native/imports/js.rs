@@ -0,0 +1,78 @@
+use super::read_string;
+use crate::native::{eval_js, get_js_global, with_env, Rid, StoreItem};
+use boa_engine::Context;
+
+enum Result {
+	// Success,
+	#[allow(clippy::enum_variant_names)]
+	MissingResult,
+	InvalidContext,
+	InvalidString,
+	// InvalidHandler,
+	// InvalidRequest,
+}
+
+impl From<Result> for i32 {
+	fn from(result: Result) -> Self {
+		match result {
+			// Result::Success => 0,
+			Result::MissingResult => -1,
+			Result::InvalidContext => -2,
+			Result::InvalidString => -3,
+			// Result::InvalidHandler => -4,
+			// Result::InvalidRequest => -5,
+		}
+	}
+}
+
+// stores the result of a function of a stored context
+fn with_context(rid: Rid, f: impl FnOnce(&mut Context) -> Option<String>) -> i32 {
+	with_env(|env| {
+		let Some(context) = env.store.get_mut(rid).and_then(|item| item.as_js_context()) else {
+			return Result::InvalidContext.into();
+		};
+		match f(context) {
+			Some(result) => env.store.store(StoreItem::String(result)),
+			None => Result::MissingResult.into(),
+		}
+	})
+}
+
+pub(crate) unsafe fn context_create() -> Rid {
+	let context = Context::default();
+	with_env(|env| env.store.store(StoreItem::JsContext(Box::new(context))))
+}
+pub(crate) unsafe fn context_eval(context: Rid, string_ptr: *const u8, len: usize) -> i32 {
+	let Some(string) = (unsafe { read_string(string_ptr, len) }) else {
+		return Result::InvalidString.into();
+	};
+	with_context(context, |context| eval_js(context, &string))
+}
+pub(crate) unsafe fn context_get(context: Rid, string_ptr: *const u8, len: usize) -> i32 {
+	let Some(string) = (unsafe { read_string(string_ptr, len) }) else {
+		return Result::InvalidString.into();
+	};
+	with_context(context, |context| get_js_global(context, &string))
+}
+
+pub(crate) unsafe fn webview_create() -> Rid {
+	-1
+}
+pub(crate) unsafe fn webview_load(_webview: Rid, _request: Rid) -> i32 {
+	-1
+}
+pub(crate) unsafe fn webview_load_html(
+	_webview: Rid,
+	_string_ptr: *const u8,
+	_len: usize,
+	_url_ptr: *const u8,
+	_url_len: usize,
+) -> i32 {
+	-1
+}
+pub(crate) unsafe fn webview_wait_for_load(_webview: Rid) -> i32 {
+	-1
+}
+pub(crate) unsafe fn webview_eval(_webview: Rid, _string_ptr: *const u8, _len: usize) -> i32 {
+	-1
+}
@@ -0,0 +1,44 @@
+//! Native implementations of the functions that sources import from the source runner.
+//!
+//! These mirror the imports of the test runner, except that pointers point to the memory of
+//! the current process instead of the memory of a wasm instance.
+pub(crate) mod defaults;
+pub(crate) mod env;
+pub(crate) mod html;
+pub(crate) mod js;
+pub(crate) mod net;
+pub(crate) mod std;
+
+/// Reads the bytes of a buffer passed by the source.
+///
+/// # Safety
+/// The pointer must be valid for reads of `len` bytes, unless `len` is zero.
+unsafe fn read_bytes<'a>(ptr: *const u8, len: usize) -> &'a [u8] {
+	if len == 0 {
+		&[]
+	} else {
+		unsafe { core::slice::from_raw_parts(ptr, len) }
+	}
+}
+
+/// Reads a string passed by the source.
+///
+/// # Safety
+/// The pointer must be valid for reads of `len` bytes, unless `len` is zero.
+unsafe fn read_string(ptr: *const u8, len: usize) -> Option<String> {
+	core::str::from_utf8(unsafe { read_bytes(ptr, len) })
+		.ok()
+		.map(String::from)
+}
+
+/// Writes data to a buffer passed by the source, if the buffer isn't larger than the data.
+///
+/// # Safety
+/// The pointer must be valid for writes of `len` bytes.
+unsafe fn write_bytes(ptr: *mut u8, len: usize, data: &[u8]) -> bool {
+	if len > data.len() {
+		return false;
+	}
+	unsafe { core::ptr::copy_nonoverlapping(data.as_ptr(), ptr, len) };
+	true
+}
@@ -0,0 +1,260 @@
+use super::{read_bytes, read_string, write_bytes};
+use crate::native::{with_env, HttpMethod, NetRequest, NetResponse, Rid, SendError, StoreItem};
+use reqwest::header::{HeaderName, HeaderValue};
+use std::str::FromStr;
+use url::Url;
+
+enum Result {
+	Success,
+	InvalidDescriptor,
+	InvalidString,
+	InvalidMethod,
+	InvalidUrl,
+	// InvalidHtml,
+	// InvalidBufferSize,
+	MissingData,
+	MissingResponse,
+	// MissingUrl,
+	RequestError,
+	FailedMemoryWrite,
+}
+
+impl From<Result> for i32 {
+	fn from(result: Result) -> Self {
+		match result {
+			Result::Success => 0,
+			Result::InvalidDescriptor => -1,
+			Result::InvalidString => -2,
+			Result::InvalidMethod => -3,
+			Result::InvalidUrl => -4,
+			// Result::InvalidHtml => -5,
+			// Result::InvalidBufferSize => -6,
+			Result::MissingData => -7,
+			Result::MissingResponse => -8,
+			// Result::MissingUrl => -9,
+			Result::RequestError => -10,
+			Result::FailedMemoryWrite => -11,
+		}
+	}
+}
+
+// calls a function with a stored request
+fn with_request(rid: Rid, f: impl FnOnce(&mut NetRequest) -> i32) -> i32 {
+	with_env(
+		|env| match env.store.get_mut(rid).and_then(|item| item.as_request()) {
+			Some(request) => f(request),
+			None => Result::InvalidDescriptor.into(),
+		},
+	)
+}
+
+// stores the item created from the response of a stored request
+fn map_response(
+	rid: Rid,
+	f: impl FnOnce(&NetResponse) -> core::result::Result<StoreItem, Result>,
+) -> i32 {
+	with_env(|env| {
+		let Some(request) = env.store.get_mut(rid).and_then(|item| item.as_request()) else {
+			return Result::InvalidDescriptor.into();
+		};
+		let Some(response) = request.response.as_ref() else {
+			return Result::MissingResponse.into();
+		};
+		match f(response) {
+			Ok(item) => env.store.store(item),
+			Err(error) => error.into(),
+		}
+	})
+}
+
+pub(crate) unsafe fn init(method: crate::imports::net::HttpMethod) -> Rid {
+	let Some(method) = HttpMethod::from_code(method as i32) else {
+		return Result::InvalidMethod.into();
+	};
+	let request = NetRequest::new(method);
+	with_env(|env| env.store.store(StoreItem::Request(Box::new(request))))
+}
+
+fn common_send(rid: Rid) -> i32 {
+	with_env(|env| {
+		let Some(request) = env.store.get_mut(rid).and_then(|item| item.as_request()) else {
+			return Result::InvalidDescriptor.into();
+		};
+		match request.send(&env.mocks) {
+			Ok(()) => Result::Success.into(),
+			Err(SendError::MissingUrl) => Result::InvalidUrl.into(),
+			Err(SendError::RequestFailed) => Result::RequestError.into(),
+		}
+	})
+}
+pub(crate) unsafe fn send(rid: Rid) -> i32 {
+	common_send(rid)
+}
+pub(crate) unsafe fn send_all(rd: *mut Rid, len: usize) -> i32 {
+	if len == 0 {
+		return Result::Success.into();
+	}
+	// the results of the requests replace their descriptors
+	let rids = unsafe { core::slice::from_raw_parts_mut(rd, len) };
+	let mut was_error = false;
+	for rid in rids {
+		*rid = common_send(*rid);
+		if *rid != Into::<i32>::into(Result::Success) {
+			was_error = true;
+		}
+	}
+	if was_error {
+		Result::RequestError.into()
+	} else {
+		Result::Success.into()
+	}
+}
+
+pub(crate) unsafe fn set_url(rid: Rid, value: *const u8, len: usize) -> i32 {
+	let Some(string) = (unsafe { read_string(value, len) }) else {
+		return Result::InvalidString.into();
+	};
+	let Ok(url) = Url::parse(&string) else {
+		return Result::InvalidUrl.into();
+	};
+	with_request(rid, |request| {
+		request.url = Some(url);
+		Result::Success.into()
+	})
+}
+pub(crate) unsafe fn set_header(
+	rid: Rid,
+	key: *const u8,
+	key_len: usize,
+	val: *const u8,
+	val_len: usize,
+) -> i32 {
+	let Some(key) = (unsafe { read_string(key, key_len) }) else {
+		return Result::InvalidString.into();
+	};
+	let Some(val) = (unsafe { read_string(val, val_len) }) else {
+		return Result::InvalidString.into();
+	};
+	let Ok(name) = HeaderName::from_str(&key) else {
+		return Result::InvalidString.into();
+	};
+	let Ok(value) = HeaderValue::from_str(&val) else {
+		return Result::InvalidString.into();
+	};
+	with_request(rid, |request| {
+		request.headers.insert(name, value);
+		Result::Success.into()
+	})
+}
+pub(crate) unsafe fn set_body(rid: Rid, value: *const u8, len: usize) -> i32 {
+	let body = unsafe { read_bytes(value, len) }.to_vec();
+	with_request(rid, |request| {
+		request.body = Some(body);
+		Result::Success.into()
+	})
+}
+pub(crate) unsafe fn set_timeout(rid: Rid, value: f64) -> i32 {
+	with_request(rid, |request| {
+		request.timeout = Some(value);
+		Result::Success.into()
+	})
+}
+
+pub(crate) unsafe fn data_len(rid: Rid) -> i32 {
+	with_request(rid, |request| match request.response.as_ref() {
+		Some(response) => response.data.len() as i32,
+		None => Result::MissingResponse.into(),
+	})
+}
+pub(crate) unsafe fn read_data(rid: Rid, buffer: *mut u8, size: usize) -> i32 {
+	with_request(rid, |request| {
+		let Some(response) = request.response.as_ref() else {
+			return Result::MissingResponse.into();
+		};
+		if unsafe { write_bytes(buffer, size, &response.data) } {
+			Result::Success.into()
+		} else {
+			Result::FailedMemoryWrite.into()
+		}
+	})
+}
+pub(crate) unsafe fn get_header(rid: Rid, key: *const u8, key_len: usize) -> i32 {
+	let Some(key) = (unsafe { read_string(key, key_len) }) else {
+		return Result::InvalidString.into();
+	};
+	let Ok(name) = HeaderName::from_str(&key) else {
+		return Result::InvalidString.into();
+	};
+	map_response(rid, |response| {
+		response
+			.header(&name)
+			.map(StoreItem::String)
+			.ok_or(Result::MissingData)
+	})
+}
+pub(crate) unsafe fn get_status_code(rid: Rid) -> i32 {
+	with_request(rid, |request| match request.response.as_ref() {
+		Some(response) => response.status.as_u16() as i32,
+		None => Result::MissingResponse.into(),
+	})
+}
+pub(crate) unsafe fn get_url(rid: Rid) -> i32 {
+	map_response(rid, |response| {
+		Ok(StoreItem::String(response.url.as_str().into()))
+	})
+}
+pub(crate) unsafe fn html(rid: Rid) -> i32 {
+	map_response(rid, |response| {
+		response
+			.html()
+			.map(StoreItem::HtmlDocument)
+			.ok_or(Result::InvalidString)
+	})
+}
+
+pub(crate) unsafe fn set_rate_limit(_permits: i32, _period: i32, _unit: i32) {
+	// requests aren't rate limited natively
+}
+
+#[cfg(feature = "test")]
+pub(crate) unsafe fn mock_response(
+	method: i32,
+	url: *const u8,
+	url_len: usize,
+	status: i32,
+	data: *const u8,
+	data_len: usize,
+) -> i32 {
+	use crate::native::MockResponse;
+	use reqwest::StatusCode;
+
+	let method = match method {
+		-1 => None,
+		method => match HttpMethod::from_code(method) {
+			Some(method) => Some(method),
+			None => return Result::InvalidMethod.into(),
+		},
+	};
+	let Some(url) = (unsafe { read_string(url, url_len) }) else {
+		return Result::InvalidString.into();
+	};
+	let Ok(url) = Url::parse(&url) else {
+		return Result::InvalidUrl.into();
+	};
+	let Some(status) = u16::try_from(status)
+		.ok()
+		.and_then(|status| StatusCode::from_u16(status).ok())
+	else {
+		return Result::RequestError.into();
+	};
+	let data = unsafe { read_bytes(data, data_len) }.to_vec();
+	with_env(|env| {
+		env.mocks.push(MockResponse {
+			method,
+			url,
+			status,
+			data,
+		})
+	});
+	Result::Success.into()
+}
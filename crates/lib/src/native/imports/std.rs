@@ -0,0 +1,211 @@
+use super::{read_string, write_bytes};
+#[cfg(feature = "test")]
+use crate::native::Clock;
+use crate::native::{parse_date as parse, with_env, Rid, ENV};
+
+enum Result {
+	Success,
+	InvalidDescriptor,
+	// InvalidBufferSize,
+	FailedMemoryWrite,
+	InvalidString,
+	InvalidDateString,
+	#[cfg(feature = "test")]
+	SnapshotMismatch,
+	#[cfg(feature = "test")]
+	FailedSnapshotWrite,
+	#[cfg(feature = "test")]
+	InvalidTimezone,
+	#[cfg(feature = "test")]
+	SnapshotMissing,
+	#[cfg(feature = "test")]
+	InvalidSnapshotValue,
+}
+
+impl From<Result> for i32 {
+	fn from(result: Result) -> i32 {
+		match result {
+			Result::Success => 0,
+			Result::InvalidDescriptor => -1,
+			// Result::InvalidBufferSize => -2,
+			Result::FailedMemoryWrite => -3,
+			Result::InvalidString => -4,
+			Result::InvalidDateString => -5,
+			#[cfg(feature = "test")]
+			Result::SnapshotMismatch => -6,
+			#[cfg(feature = "test")]
+			Result::FailedSnapshotWrite => -8,
+			#[cfg(feature = "test")]
+			Result::InvalidTimezone => -9,
+			#[cfg(feature = "test")]
+			Result::SnapshotMissing => -10,
+			#[cfg(feature = "test")]
+			Result::InvalidSnapshotValue => -11,
+		}
+	}
+}
+
+pub(crate) unsafe fn destroy(rid: Rid) {
+	// values can be dropped while the thread is exiting, after its state has been destroyed
+	_ = ENV.try_with(|env| env.borrow_mut().store.remove(rid));
+}
+
+pub(crate) unsafe fn buffer_len(rid: Rid) -> i32 {
+	with_env(
+		|env| match env.store.get(rid).and_then(|item| item.as_encoded()) {
+			Some(data) => data.len() as i32,
+			None => Result::InvalidDescriptor.into(),
+		},
+	)
+}
+
+pub(crate) unsafe fn read_buffer(rid: Rid, buf: *mut u8, len: usize) -> i32 {
+	with_env(|env| {
+		let Some(data) = env.store.get(rid).and_then(|item| item.as_encoded()) else {
+			return Result::InvalidDescriptor.into();
+		};
+		if unsafe { write_bytes(buf, len, data) } {
+			Result::Success.into()
+		} else {
+			Result::FailedMemoryWrite.into()
+		}
+	})
+}
+
+pub(crate) unsafe fn current_date() -> f64 {
+	with_env(|env| env.clock.now() as f64)
+}
+
+pub(crate) unsafe fn utc_offset() -> i64 {
+	with_env(|env| env.clock.utc_offset())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) unsafe fn parse_date(
+	string_ptr: *const u8,
+	string_len: usize,
+	format_ptr: *const u8,
+	format_len: usize,
+	locale_ptr: *const u8,
+	locale_len: usize,
+	timezone_ptr: *const u8,
+	timezone_len: usize,
+) -> f64 {
+	let Some(string) = (unsafe { read_string(string_ptr, string_len) }) else {
+		return Into::<i32>::into(Result::InvalidString) as f64;
+	};
+	let Some(format) = (unsafe { read_string(format_ptr, format_len) }) else {
+		return Into::<i32>::into(Result::InvalidString) as f64;
+	};
+	let locale = unsafe { read_string(locale_ptr, locale_len) };
+	let timezone = unsafe { read_string(timezone_ptr, timezone_len) };
+	let timezone = with_env(|env| {
+		env.clock
+			.resolve_timezone(timezone.as_deref())
+			.map(String::from)
+	});
+	match parse(&string, &format, locale.as_deref(), timezone.as_deref()) {
+		Some(timestamp) => timestamp as f64,
+		None => Into::<i32>::into(Result::InvalidDateString) as f64,
+	}
+}
+
+#[cfg(feature = "test")]
+#[allow(clippy::too_many_arguments)]
+pub(crate) unsafe fn snapshot(
+	name_ptr: *const u8,
+	name_len: usize,
+	expression_ptr: *const u8,
+	expression_len: usize,
+	kind_ptr: *const u8,
+	kind_len: usize,
+	value_ptr: *const u8,
+	value_len: usize,
+) -> i32 {
+	use crate::native::{format_snapshot, SnapshotResult, Snapshots};
+
+	let Some(name) = (unsafe { read_string(name_ptr, name_len) }) else {
+		return Result::InvalidString.into();
+	};
+	let Some(expression) = (unsafe { read_string(expression_ptr, expression_len) }) else {
+		return Result::InvalidString.into();
+	};
+	let Some(kind) = (unsafe { read_string(kind_ptr, kind_len) }) else {
+		return Result::InvalidString.into();
+	};
+	let bytes = unsafe { std::slice::from_raw_parts(value_ptr, value_len) };
+	let Some(value) = format_snapshot(&kind, bytes) else {
+		return Result::InvalidSnapshotValue.into();
+	};
+	let name = (!name.is_empty()).then_some(name.as_str());
+
+	with_env(|env| {
+		// cargo runs each test in a thread named after its path in the crate, which is
+		// prefixed with the crate name to match the test names of the source runner
+		let snapshots = env.snapshots.get_or_insert_with(|| {
+			let thread = std::thread::current();
+			let path = thread.name().unwrap_or("main");
+			let test_name = match std::env::var("CARGO_PKG_NAME") {
+				Ok(crate_name) => format!("{}::{path}", crate_name.replace('-', "_")),
+				Err(_) => path.into(),
+			};
+			let update =
+				std::env::var_os("BUNY_UPDATE_SNAPSHOTS").is_some_and(|value| value != "0");
+			Snapshots::new(Snapshots::default_dir(), test_name, update)
+		});
+		match snapshots.assert(name, &expression, &value) {
+			Ok(SnapshotResult::Matched) => Result::Success.into(),
+			Ok(SnapshotResult::Created(path)) => {
+				println!("created snapshot {}", path.display());
+				Result::Success.into()
+			}
+			Ok(SnapshotResult::Updated(path)) => {
+				println!("updated snapshot {}", path.display());
+				Result::Success.into()
+			}
+			Ok(SnapshotResult::Mismatched { path, diff }) => {
+				print!(
+					"snapshot {} doesn't match (rerun with BUNY_UPDATE_SNAPSHOTS=1 to update it):\n{diff}",
+					path.display()
+				);
+				Result::SnapshotMismatch.into()
+			}
+			Ok(SnapshotResult::Missing(path)) => {
+				println!(
+					"snapshot {} doesn't exist (rerun with BUNY_UPDATE_SNAPSHOTS=1 to create it)",
+					path.display()
+				);
+				Result::SnapshotMissing.into()
+			}
+			Err(error) => {
+				println!("failed to write snapshot: {error}");
+				Result::FailedSnapshotWrite.into()
+			}
+		}
+	})
+}
+
+#[cfg(feature = "test")]
+pub(crate) unsafe fn set_clock(
+	time: i64,
+	timezone_ptr: *const u8,
+	timezone_len: usize,
+	auto_advance: bool,
+) -> i32 {
+	let timezone = match unsafe { read_string(timezone_ptr, timezone_len) } {
+		Some(timezone) if timezone.is_empty() => chrono_tz::UTC,
+		Some(timezone) => match timezone.parse() {
+			Ok(timezone) => timezone,
+			Err(_) => return Result::InvalidTimezone.into(),
+		},
+		None => return Result::InvalidString.into(),
+	};
+	with_env(|env| {
+		env.clock = Clock {
+			time: Some(time),
+			timezone: Some(timezone),
+			auto_advance,
+		}
+	});
+	Result::Success.into()
+}
@@ -0,0 +1,21 @@
+use boa_engine::{Context, JsString, JsValue, Source};
+
+/// Evaluates a script in a context, returning its result as a string.
+pub fn eval_js(context: &mut Context, script: &str) -> Option<String> {
+	let result = context.eval(Source::from_bytes(script)).ok()?;
+	js_to_string(context, result)
+}
+
+/// Returns a global variable of a context as a string.
+pub fn get_js_global(context: &mut Context, name: &str) -> Option<String> {
+	let key: JsString = name.into();
+	let result = context.global_object().get(key, context).ok()?;
+	js_to_string(context, result)
+}
+
+fn js_to_string(context: &mut Context, value: JsValue) -> Option<String> {
+	value
+		.to_string(context)
+		.ok()
+		.and_then(|string| string.to_std_string().ok())
+}
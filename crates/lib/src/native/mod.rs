@@ -0,0 +1,66 @@
+//! Native implementations of the source runner.
+//!
+//! With the `native` feature, the functions that sources import from the source runner are
+//! implemented natively instead. This allows sources to be built for the host target, so they
+//! can be run with `cargo test` and stepped through with a debugger.
+//!
+//! The data types in this module are shared with the test runner. Each thread has its own
+//! runner state, which can be accessed with [with_env], so tests don't share descriptors,
+//! defaults, mocked requests, or clocks.
+use std::cell::RefCell;
+
+mod clock;
+mod date;
+mod defaults;
+mod format;
+mod html;
+#[cfg(feature = "imports")]
+pub(crate) mod imports;
+mod js;
+mod net;
+mod snapshot;
+mod store;
+
+pub use clock::*;
+pub use date::*;
+pub use defaults::*;
+pub use format::*;
+pub use html::*;
+pub use js::*;
+pub use net::*;
+pub use snapshot::*;
+pub use store::*;
+
+/// A standard descriptor, used for data exchange between the runner and the source (reference id).
+///
+/// Valid descriptors will always be positive.
+pub type Rid = i32;
+
+/// The state of the native source runner.
+#[derive(Default)]
+pub struct NativeEnv {
+	pub store: GlobalStore,
+	pub defaults: UserDefaults,
+	pub mocks: Vec<MockResponse>,
+	pub clock: Clock,
+	/// The snapshots of the running test, created when the first snapshot is asserted.
+	pub snapshots: Option<Snapshots>,
+}
+
+thread_local! {
+	static ENV: RefCell<NativeEnv> = RefCell::default();
+}
+
+/// Calls a function with the runner state of the current thread.
+///
+/// This can be used to set up the runner before calling a source function, e.g. to set a
+/// default value that the source reads:
+///
+/// ```ignore
+/// use buny::native::{with_env, DefaultValue};
+///
+/// with_env(|env| env.defaults.set("url".into(), DefaultValue::String("https://example.com".into())));
+/// ```
+pub fn with_env<T>(f: impl FnOnce(&mut NativeEnv) -> T) -> T {
+	ENV.with_borrow_mut(f)
+}
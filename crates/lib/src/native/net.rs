@@ -0,0 +1,196 @@
+use super::{HtmlDocument, ImageData};
+use image::ImageReader;
+use reqwest::{
+	header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT},
+	StatusCode,
+};
+use std::io::Cursor;
+use url::Url;
+
+const DEFAULT_USER_AGENT: &str = "Buny/1 CFNetwork/3826.500.131 Darwin/24.5.0";
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum HttpMethod {
+	Get,
+	Post,
+	Put,
+	Head,
+	Delete,
+	Patch,
+	Options,
+	Connect,
+	Trace,
+}
+
+impl HttpMethod {
+	/// Returns the method with the code that sources pass to the runner.
+	pub fn from_code(code: i32) -> Option<Self> {
+		match code {
+			0 => Some(Self::Get),
+			1 => Some(Self::Post),
+			2 => Some(Self::Put),
+			3 => Some(Self::Head),
+			4 => Some(Self::Delete),
+			5 => Some(Self::Patch),
+			6 => Some(Self::Options),
+			7 => Some(Self::Connect),
+			8 => Some(Self::Trace),
+			_ => None,
+		}
+	}
+}
+
+/// An error from sending a request.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum SendError {
+	/// The request doesn't have a url.
+	MissingUrl,
+	/// The request failed, e.g. because of a connection error.
+	RequestFailed,
+}
+
+#[derive(Debug)]
+pub struct NetResponse {
+	pub url: Url,
+	pub status: StatusCode,
+	pub headers: HeaderMap,
+	pub data: Vec<u8>,
+}
+
+/// A response returned for matching requests instead of sending them.
+impl NetResponse {
+	/// Returns the values of a header, joined with commas.
+	pub fn header(&self, name: &HeaderName) -> Option<String> {
+		let value = self
+			.headers
+			.get_all(name)
+			.iter()
+			.filter_map(|value| String::from_utf8(value.as_bytes().to_vec()).ok())
+			.collect::<Vec<String>>()
+			.join(", ");
+		(!value.is_empty()).then_some(value)
+	}
+
+	/// Decodes the response data as an image.
+	pub fn image(&self) -> Option<ImageData> {
+		let image = ImageReader::new(Cursor::new(&self.data))
+			.with_guessed_format()
+			.ok()?
+			.decode()
+			.ok()?
+			.to_rgb8();
+		Some(ImageData {
+			width: image.width() as i32,
+			height: image.height() as i32,
+			data: image.into_raw(),
+		})
+	}
+
+	/// Parses the response data as an html document, with the response url as its base.
+	pub fn html(&self) -> Option<HtmlDocument> {
+		let text = std::str::from_utf8(&self.data).ok()?;
+		Some(HtmlDocument::parse(text, self.url.as_str()))
+	}
+}
+
+#[derive(Debug)]
+pub struct MockResponse {
+	/// The method of requests to match, or any method if `None`.
+	pub method: Option<HttpMethod>,
+	pub url: Url,
+	pub status: StatusCode,
+	pub data: Vec<u8>,
+}
+
+impl MockResponse {
+	pub fn matches(&self, method: HttpMethod, url: &Url) -> bool {
+		self.method.is_none_or(|m| m == method) && &self.url == url
+	}
+}
+
+#[derive(Debug)]
+pub struct NetRequest {
+	pub method: HttpMethod,
+	pub url: Option<Url>,
+	pub headers: HeaderMap,
+	pub body: Option<Vec<u8>>,
+	pub timeout: Option<f64>,
+	pub response: Option<NetResponse>,
+}
+
+impl NetRequest {
+	pub fn new(method: HttpMethod) -> Self {
+		Self {
+			method,
+			url: None,
+			headers: HeaderMap::new(),
+			body: None,
+			timeout: None,
+			response: None,
+		}
+	}
+
+	/// Sends the request and stores its response.
+	///
+	/// If any of the mocks match the request, the most recent one is used as the response
+	/// instead of sending the request.
+	pub fn send(&mut self, mocks: &[MockResponse]) -> Result<(), SendError> {
+		if let Some(mock) = self.url.as_ref().and_then(|url| {
+			mocks
+				.iter()
+				.rev()
+				.find(|mock| mock.matches(self.method, url))
+		}) {
+			self.response = Some(NetResponse {
+				url: mock.url.clone(),
+				status: mock.status,
+				headers: Default::default(),
+				data: mock.data.clone(),
+			});
+			return Ok(());
+		}
+		// add a default user agent if none is provided
+		if !self.headers.contains_key(USER_AGENT) {
+			let default_ua = HeaderValue::from_static(DEFAULT_USER_AGENT);
+			self.headers.insert(USER_AGENT, default_ua);
+		}
+		let url = self.url.as_ref().ok_or(SendError::MissingUrl)?;
+		let mut builder = reqwest::blocking::Client::new()
+			.request(
+				match self.method {
+					HttpMethod::Get => reqwest::Method::GET,
+					HttpMethod::Post => reqwest::Method::POST,
+					HttpMethod::Put => reqwest::Method::PUT,
+					HttpMethod::Delete => reqwest::Method::DELETE,
+					HttpMethod::Head => reqwest::Method::HEAD,
+					HttpMethod::Patch => reqwest::Method::PATCH,
+					HttpMethod::Options => reqwest::Method::OPTIONS,
+					HttpMethod::Connect => reqwest::Method::CONNECT,
+					HttpMethod::Trace => reqwest::Method::TRACE,
+				},
+				url.to_string(),
+			)
+			.headers(self.headers.clone());
+		if let Some(body) = self.body.take() {
+			builder = builder.body(body);
+		}
+		if let Some(timeout) = self.timeout.take() {
+			let secs = timeout.trunc() as u64;
+			let nanos = ((timeout.fract()) * 1_000_000_000.0).round() as u32;
+			builder = builder.timeout(std::time::Duration::new(secs, nanos));
+		}
+		// make a blocking request with reqwest
+		let response = builder.send().map_err(|_| SendError::RequestFailed)?;
+		let url = response.url().clone();
+		let status = response.status();
+		let headers = response.headers().clone();
+		let bytes = response.bytes().map_err(|_| SendError::RequestFailed)?;
+		self.response = Some(NetResponse {
+			url,
+			status,
+			headers,
+			data: bytes.into(),
+		});
+		Ok(())
+	}
+}
@@ -0,0 +1,184 @@
+use std::{
+	fmt::Write,
+	path::{Path, PathBuf},
+};
+
+/// The result of comparing a value with its stored snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotResult {
+	/// The value matches the snapshot.
+	Matched,
+	/// There was no snapshot, so one was created.
+	Created(PathBuf),
+	/// There's no snapshot, and missing snapshots aren't created.
+	Missing(PathBuf),
+	/// The value didn't match, and the snapshot was rewritten.
+	Updated(PathBuf),
+	/// The value doesn't match the snapshot, with a diff of the changed lines.
+	Mismatched { path: PathBuf, diff: String },
+}
+
+/// The snapshots of a test, stored as `.snap` files in a directory.
+#[derive(Debug, Clone)]
+pub struct Snapshots {
+	/// The directory the snapshot files are stored in.
+	pub dir: PathBuf,
+	/// The name of the test, used to name the snapshot files.
+	pub test_name: String,
+	/// Whether mismatched snapshots should be rewritten.
+	pub update: bool,
+	/// Whether missing snapshots should be created without [update](Self::update), which is
+	/// disabled in CI so that snapshots that weren't committed fail the test.
+	pub create_missing: bool,
+	count: usize,
+}
+
+impl Snapshots {
+	pub fn new(dir: impl Into<PathBuf>, test_name: impl Into<String>, update: bool) -> Self {
+		Self {
+			dir: dir.into(),
+			test_name: test_name.into(),
+			update,
+			create_missing: !is_ci(),
+			count: 0,
+		}
+	}
+
+	/// Returns the default snapshot directory, `snapshots` in the directory of the crate being
+	/// tested (which cargo sets when running tests) or the current directory.
+	pub fn default_dir() -> PathBuf {
+		std::env::var_os("CARGO_MANIFEST_DIR")
+			.map(PathBuf::from)
+			.or_else(|| std::env::current_dir().ok())
+			.unwrap_or_default()
+			.join("snapshots")
+	}
+
+	/// Returns the path of the next snapshot of the test.
+	///
+	/// Named snapshots are stored as `test@name.snap`. Unnamed snapshots are stored as
+	/// `test.snap`, followed by `test-2.snap`, `test-3.snap`, etc.
+	pub fn path(&mut self, name: Option<&str>) -> PathBuf {
+		let name = match name {
+			Some(name) => format!("{}@{name}", self.test_name),
+			None => {
+				self.count += 1;
+				if self.count == 1 {
+					self.test_name.clone()
+				} else {
+					format!("{}-{}", self.test_name, self.count)
+				}
+			}
+		};
+		self.dir.join(format!("{}.snap", file_name(&name)))
+	}
+
+	/// Compares a value with its snapshot, creating or updating the snapshot file if needed.
+	///
+	/// Missing snapshots are only created in update mode or if [create_missing](Self::create_missing)
+	/// is set.
+	pub fn assert(
+		&mut self,
+		name: Option<&str>,
+		expression: &str,
+		value: &str,
+	) -> std::io::Result<SnapshotResult> {
+		let path = self.path(name);
+		let Some(snapshot) = read_snapshot(&path)? else {
+			if !self.update && !self.create_missing {
+				return Ok(SnapshotResult::Missing(path));
+			}
+			write_snapshot(&path, expression, value)?;
+			return Ok(SnapshotResult::Created(path));
+		};
+		if snapshot == value {
+			Ok(SnapshotResult::Matched)
+		} else if self.update {
+			write_snapshot(&path, expression, value)?;
+			Ok(SnapshotResult::Updated(path))
+		} else {
+			let diff = diff_lines(&snapshot, value);
+			Ok(SnapshotResult::Mismatched { path, diff })
+		}
+	}
+}
+
+// CI services set the `CI` environment variable, e.g. `CI=true` on GitHub Actions
+fn is_ci() -> bool {
+	std::env::var_os("CI").is_some_and(|value| value != "0" && value != "false")
+}
+
+// replace the characters that can't be used in file names, e.g. the `::` in test paths
+fn file_name(name: &str) -> String {
+	name.replace("::", "__")
+		.chars()
+		.map(|c| {
+			if c.is_alphanumeric() || matches!(c, '-' | '_' | '@' | '.') {
+				c
+			} else {
+				'_'
+			}
+		})
+		.collect()
+}
+
+// snapshot files have a header with the expression, separated from the value by a `---` line
+fn read_snapshot(path: &Path) -> std::io::Result<Option<String>> {
+	let contents = match std::fs::read_to_string(path) {
+		Ok(contents) => contents,
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+		Err(error) => return Err(error),
+	};
+	let value = contents
+		.strip_prefix("---\n")
+		.and_then(|contents| contents.split_once("\n---\n"))
+		.map(|(_, value)| value)
+		.unwrap_or(&contents);
+	Ok(Some(value.strip_suffix('\n').unwrap_or(value).into()))
+}
+
+fn write_snapshot(path: &Path, expression: &str, value: &str) -> std::io::Result<()> {
+	if let Some(dir) = path.parent() {
+		std::fs::create_dir_all(dir)?;
+	}
+	std::fs::write(
+		path,
+		format!("---\nexpression: {expression}\n---\n{value}\n"),
+	)
+}
+
+/// Returns a line diff of two strings, with removed lines prefixed by `-` and added lines
+/// prefixed by `+`.
+pub fn diff_lines(old: &str, new: &str) -> String {
+	let old = old.lines().collect::<Vec<_>>();
+	let new = new.lines().collect::<Vec<_>>();
+
+	// lengths of the longest common subsequences of the remaining lines
+	let mut lengths = vec![vec![0; new.len() + 1]; old.len() + 1];
+	for i in (0..old.len()).rev() {
+		for j in (0..new.len()).rev() {
+			lengths[i][j] = if old[i] == new[j] {
+				lengths[i + 1][j + 1] + 1
+			} else {
+				lengths[i + 1][j].max(lengths[i][j + 1])
+			};
+		}
+	}
+
+	let mut output = String::new();
+	let (mut i, mut j) = (0, 0);
+	while i < old.len() || j < new.len() {
+		if i < old.len() && j < new.len() && old[i] == new[j] {
+			_ = writeln!(output, "  {}", old[i]);
+			i += 1;
+			j += 1;
+		} else if i < old.len() && (j == new.len() || lengths[i + 1][j] >= lengths[i][j + 1]) {
+			_ = writeln!(output, "- {}", old[i]);
+			i += 1;
+		} else {
+			_ = writeln!(output, "+ {}", new[j]);
+			j += 1;
+		}
+	}
+	output
+}
@@ -1,7 +1,5 @@
-use super::{HtmlDocument, HtmlElement, HtmlElementList, NetRequest, Rid};
+use super::{HtmlDocument, HtmlElement, HtmlElementList, HtmlNode, HtmlNodeList, NetRequest, Rid};
 use boa_engine::Context;
-use font_kit::font::Font;
-use raqote::DrawTarget;
 use serde::Serialize;
 use std::collections::HashMap;
 
@@ -17,10 +15,10 @@ pub enum StoreItem {
 	HtmlDocument(HtmlDocument),
 	HtmlElement(HtmlElement),
 	HtmlElementList(HtmlElementList),
+	HtmlNode(HtmlNode),
+	HtmlNodeList(HtmlNodeList),
 	JsContext(Box<Context>),
 	Encoded(Vec<u8>),
-	Canvas(DrawTarget),
-	Font(Font),
 	ImageData(ImageData),
 }
 
@@ -69,17 +67,25 @@ impl StoreItem {
 		}
 	}
 
-	pub fn as_js_context(&mut self) -> Option<&mut Context> {
-		if let StoreItem::JsContext(c) = self {
-			Some(c)
+	pub fn as_html_node(&mut self) -> Option<&mut HtmlNode> {
+		if let StoreItem::HtmlNode(h) = self {
+			Some(h)
 		} else {
 			None
 		}
 	}
 
-	pub fn as_font(&self) -> Option<&Font> {
-		if let StoreItem::Font(s) = self {
-			Some(s)
+	pub fn as_html_node_list(&mut self) -> Option<&mut HtmlNodeList> {
+		if let StoreItem::HtmlNodeList(h) = self {
+			Some(h)
+		} else {
+			None
+		}
+	}
+
+	pub fn as_js_context(&mut self) -> Option<&mut Context> {
+		if let StoreItem::JsContext(c) = self {
+			Some(c)
 		} else {
 			None
 		}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+extern crate alloc;
+use alloc::{string::String, vec::Vec};
+
+/// A comment on a novel or chapter.
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Comment {
+	/// Unique identifier for the comment, used to fetch its replies.
+	pub key: String,
+	/// Name of the user that posted the comment.
+	pub author: String,
+	/// Link to the avatar image of the author.
+	pub avatar: Option<String>,
+	/// Markdown text content of the comment.
+	pub body_markdown: String,
+	/// Date the comment was posted.
+	pub posted_at: Option<i64>,
+	/// Number of likes or upvotes.
+	pub likes: Option<i32>,
+	/// Number of replies to the comment.
+	pub replies_count: Option<i32>,
+}
+
+/// A page of comments.
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CommentPageResult {
+	/// List of comments.
+	pub entries: Vec<Comment>,
+	/// Whether the next page is available or not.
+	pub has_next_page: bool,
+}
@@ -0,0 +1,55 @@
+use core::fmt::Display;
+use serde::{Deserialize, Serialize};
+
+extern crate alloc;
+use alloc::{string::String, vec::Vec};
+
+/// The latest version of the error buffer encoding used by sources.
+///
+/// See the `BunyError` documentation for the differences between versions.
+pub const ERROR_VERSION: i32 = 1;
+
+/// Details of an error returned by a source, passed to the source runner.
+///
+/// The [Display] implementation formats a multi-line report, e.g.:
+///
+/// ```text
+/// RequestError: request error
+/// caused by: invalid url
+/// url: https://example.com/novel
+/// status: 404
+/// ```
+#[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorInfo {
+	/// The name of the error variant, e.g. `RequestError`.
+	pub kind: String,
+	/// The error message.
+	pub message: String,
+	/// The messages of the underlying errors, starting with the direct cause.
+	pub sources: Vec<String>,
+	/// The url of the request that caused the error, if known.
+	pub url: Option<String>,
+	/// The status code of the response that caused the error, if known.
+	pub status: Option<i32>,
+	/// The seconds to wait before retrying, for rate limit errors.
+	pub retry_after: Option<i32>,
+}
+
+impl Display for ErrorInfo {
+	fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+		write!(f, "{}: {}", self.kind, self.message)?;
+		for source in &self.sources {
+			write!(f, "\ncaused by: {source}")?;
+		}
+		if let Some(url) = &self.url {
+			write!(f, "\nurl: {url}")?;
+		}
+		if let Some(status) = self.status {
+			write!(f, "\nstatus: {status}")?;
+		}
+		if let Some(seconds) = self.retry_after {
+			write!(f, "\nretry after: {seconds}s")?;
+		}
+		Ok(())
+	}
+}
@@ -1,4 +1,9 @@
-use serde::{ser::SerializeStruct, Deserialize, Serialize};
+use super::next_field;
+use serde::{
+	de::{self, SeqAccess, Visitor},
+	ser::SerializeStruct,
+	Deserialize, Deserializer, Serialize,
+};
 
 extern crate alloc;
 use alloc::{borrow::Cow, string::String, vec::Vec};
@@ -342,6 +347,112 @@ impl Serialize for Filter {
 	}
 }
 
+// the fields of the longest filter kind (multi-select), which bound the fields that are read
+const FILTER_FIELDS: &[&str] = &[
+	"id",
+	"title",
+	"hide_from_header",
+	"type",
+	"is_genre",
+	"can_exclude",
+	"uses_tag_style",
+	"options",
+	"ids",
+	"default_included",
+	"default_excluded",
+];
+
+const FILTER_KINDS: &[&str] = &[
+	"text",
+	"sort",
+	"check",
+	"select",
+	"multi-select",
+	"note",
+	"range",
+];
+
+impl<'de> Deserialize<'de> for Filter {
+	fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		struct FilterVisitor;
+
+		impl<'de> Visitor<'de> for FilterVisitor {
+			type Value = Filter;
+
+			fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+				formatter.write_str("a filter")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> core::result::Result<Filter, A::Error>
+			where
+				A: SeqAccess<'de>,
+			{
+				let id: Option<Cow<'static, str>> = next_field(&mut seq, "id")?;
+				let title = next_field(&mut seq, "title")?;
+				let hide_from_header = next_field(&mut seq, "hide_from_header")?;
+				let kind: String = next_field(&mut seq, "type")?;
+				let kind = match kind.as_str() {
+					"text" => FilterKind::Text {
+						placeholder: next_field(&mut seq, "placeholder")?,
+					},
+					"sort" => FilterKind::Sort {
+						can_ascend: next_field::<Option<bool>, _>(&mut seq, "can_ascend")?
+							.unwrap_or_default(),
+						options: next_field(&mut seq, "options")?,
+						default: next_field(&mut seq, "default")?,
+					},
+					"check" => FilterKind::Check {
+						name: next_field(&mut seq, "name")?,
+						can_exclude: next_field::<Option<bool>, _>(&mut seq, "can_exclude")?
+							.unwrap_or_default(),
+						default: next_field(&mut seq, "default")?,
+					},
+					"select" => FilterKind::Select {
+						is_genre: next_field::<Option<bool>, _>(&mut seq, "is_genre")?
+							.unwrap_or_default(),
+						uses_tag_style: next_field::<Option<bool>, _>(&mut seq, "uses_tag_style")?
+							.unwrap_or_default(),
+						options: next_field(&mut seq, "options")?,
+						ids: next_field(&mut seq, "ids")?,
+						default: next_field(&mut seq, "default")?,
+					},
+					"multi-select" => FilterKind::MultiSelect {
+						is_genre: next_field::<Option<bool>, _>(&mut seq, "is_genre")?
+							.unwrap_or_default(),
+						can_exclude: next_field::<Option<bool>, _>(&mut seq, "can_exclude")?
+							.unwrap_or_default(),
+						uses_tag_style: next_field::<Option<bool>, _>(&mut seq, "uses_tag_style")?
+							.unwrap_or_default(),
+						options: next_field(&mut seq, "options")?,
+						ids: next_field(&mut seq, "ids")?,
+						default_included: next_field(&mut seq, "default_included")?,
+						default_excluded: next_field(&mut seq, "default_excluded")?,
+					},
+					"note" => FilterKind::Note(next_field(&mut seq, "text")?),
+					"range" => FilterKind::Range {
+						min: next_field(&mut seq, "min")?,
+						max: next_field(&mut seq, "max")?,
+						decimal: next_field::<Option<bool>, _>(&mut seq, "decimal")?
+							.unwrap_or_default(),
+					},
+					kind => return Err(de::Error::unknown_variant(kind, FILTER_KINDS)),
+				};
+				Ok(Filter {
+					id: id.unwrap_or_default(),
+					title,
+					hide_from_header,
+					kind,
+				})
+			}
+		}
+
+		deserializer.deserialize_struct("Filter", FILTER_FIELDS, FilterVisitor)
+	}
+}
+
 /// A configured filter value.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FilterValue {
@@ -1,8 +1,8 @@
-use super::{FilterValue, Listing, Novel};
+use super::{FilterValue, Listing, Novel, NovelWithChapter};
 use serde::{Deserialize, Serialize};
 
 extern crate alloc;
-use alloc::{string::String, vec::Vec};
+use alloc::{format, string::String, vec::Vec};
 
 /// A partial result for the home page.
 ///
@@ -83,6 +83,14 @@ pub enum HomeComponentValue {
 	///
 	/// Only the link title and values are used.
 	Links(Vec<Link>),
+	/// List of recently updated chapters along with their novels.
+	///
+	/// The page size is the number of entries shown on each page of the list.
+	ChapterFeed {
+		page_size: Option<i32>,
+		entries: Vec<NovelWithChapter>,
+		listing: Option<Listing>,
+	},
 }
 
 impl HomeComponentValue {
@@ -172,6 +180,15 @@ impl HomeComponentValue {
 	pub fn empty_links() -> Self {
 		Self::Links(Vec::new())
 	}
+
+	/// Creates an empty chapter feed component.
+	pub fn empty_chapter_feed() -> Self {
+		Self::ChapterFeed {
+			page_size: None,
+			entries: Vec::new(),
+			listing: None,
+		}
+	}
 }
 
 /// A link to a listing that uses the provided filters.
@@ -209,11 +226,14 @@ pub struct Link {
 }
 
 /// A link value that can be opened by the Aidoku app.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum LinkValue {
 	Url(String),
 	Listing(Listing),
 	Novel(Novel),
+	/// A chapter, which is opened in the reader.
+	Chapter(NovelWithChapter),
 }
 
 impl Default for LinkValue {
@@ -238,3 +258,21 @@ impl From<Novel> for Link {
 		}
 	}
 }
+
+impl From<NovelWithChapter> for Link {
+	/// Creates a link from a chapter, using the title of its novel.
+	fn from(value: NovelWithChapter) -> Self {
+		let chapter = &value.chapter;
+		let subtitle = match (chapter.chapter_number, &chapter.title) {
+			(Some(number), Some(title)) => Some(format!("Chapter {number}: {title}")),
+			(Some(number), None) => Some(format!("Chapter {number}")),
+			(None, title) => title.clone(),
+		};
+		Link {
+			title: value.novel.title.clone(),
+			subtitle,
+			image_url: chapter.thumbnail.clone().or(value.novel.cover.clone()),
+			value: Some(LinkValue::Chapter(value)),
+		}
+	}
+}
@@ -1,17 +1,29 @@
 //! Serializable data structures that are sent between Buny and sources.
 
 use super::alloc::{String, Vec};
-use serde::{Deserialize, Serialize};
+use serde::{
+	de::{self, SeqAccess},
+	ser::SerializeStruct,
+	Deserialize, Deserializer, Serialize,
+};
 
 pub use hashbrown::HashMap;
 
+mod comment;
+mod error;
 mod filter;
 mod home;
 mod setting;
+mod unlock;
+mod version;
 
+pub use comment::*;
+pub use error::*;
 pub use filter::*;
 pub use home::*;
 pub use setting::*;
+pub use unlock::*;
+pub use version::*;
 
 #[cfg(feature = "imports")]
 mod source;
@@ -55,8 +67,24 @@ pub enum UpdateStrategy {
 	Never,
 }
 
+/// The translation status of a novel.
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum TranslationStatus {
+	#[default]
+	Unknown = 0,
+	/// The novel is in its original language.
+	Raw,
+	/// The novel is translated by people.
+	Translated,
+	/// The novel is machine translated.
+	MachineTranslated,
+}
+
 /// A web novel object.
-#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+///
+/// The fields after `has_more_chapters` are only encoded for hosts that negotiate
+/// [STRUCT_VERSION] 1 or later.
+#[derive(Default, Clone, Debug, PartialEq)]
 pub struct Novel {
 	/// Unique identifier for the novel.
 	pub key: String,
@@ -64,8 +92,6 @@ pub struct Novel {
 	pub title: String,
 	/// Link to the novel cover image.
 	pub cover: Option<String>,
-	// /// Optional list of artists.
-	// pub artists: Option<Vec<String>>,
 	/// Optional list of authors.
 	pub authors: Option<Vec<String>>,
 	/// Description of the novel.
@@ -86,6 +112,26 @@ pub struct Novel {
 	pub chapters: Option<Vec<Chapter>>,
 	/// Has more chapters.
 	pub has_more_chapters: Option<bool>,
+	/// Optional list of alternative titles.
+	pub alt_titles: Option<Vec<String>>,
+	/// Optional list of artists, e.g. cover or illustration artists.
+	pub artists: Option<Vec<String>>,
+	/// Average rating, from 0 to 5.
+	pub rating: Option<f32>,
+	/// Number of ratings the average rating is based on.
+	pub rating_count: Option<i32>,
+	/// Number of views.
+	pub views: Option<i64>,
+	/// Number of users following or bookmarking the novel.
+	pub follows: Option<i64>,
+	/// Total number of words.
+	pub word_count: Option<i64>,
+	/// Total number of chapters, which may differ from the length of the chapter list.
+	pub chapter_count: Option<i32>,
+	/// Language code of the original work, e.g. `ko`.
+	pub original_language: Option<String>,
+	/// Translation status of the novel.
+	pub translation_status: TranslationStatus,
 }
 
 impl Novel {
@@ -96,9 +142,6 @@ impl Novel {
 		if let Some(cover) = novel.cover {
 			self.cover = Some(cover);
 		}
-		// if let Some(artists) = novel.artists {
-		// 	self.artists = Some(artists);
-		// }
 		if let Some(authors) = novel.authors {
 			self.authors = Some(authors);
 		}
@@ -122,11 +165,157 @@ impl Novel {
 			self.chapters = Some(chapters);
 			self.has_more_chapters = novel.has_more_chapters;
 		}
+		if let Some(alt_titles) = novel.alt_titles {
+			self.alt_titles = Some(alt_titles);
+		}
+		if let Some(artists) = novel.artists {
+			self.artists = Some(artists);
+		}
+		if let Some(rating) = novel.rating {
+			self.rating = Some(rating);
+		}
+		if let Some(rating_count) = novel.rating_count {
+			self.rating_count = Some(rating_count);
+		}
+		if let Some(views) = novel.views {
+			self.views = Some(views);
+		}
+		if let Some(follows) = novel.follows {
+			self.follows = Some(follows);
+		}
+		if let Some(word_count) = novel.word_count {
+			self.word_count = Some(word_count);
+		}
+		if let Some(chapter_count) = novel.chapter_count {
+			self.chapter_count = Some(chapter_count);
+		}
+		if let Some(original_language) = novel.original_language {
+			self.original_language = Some(original_language);
+		}
+		if novel.translation_status != TranslationStatus::Unknown {
+			self.translation_status = novel.translation_status;
+		}
+	}
+}
+
+impl Serialize for Novel {
+	fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		let version = struct_version();
+		let mut state = serializer.serialize_struct("Novel", if version >= 1 { 23 } else { 13 })?;
+		state.serialize_field("key", &self.key)?;
+		state.serialize_field("title", &self.title)?;
+		state.serialize_field("cover", &self.cover)?;
+		state.serialize_field("authors", &self.authors)?;
+		state.serialize_field("description", &self.description)?;
+		state.serialize_field("url", &self.url)?;
+		state.serialize_field("tags", &self.tags)?;
+		state.serialize_field("status", &self.status)?;
+		state.serialize_field("content_rating", &self.content_rating)?;
+		state.serialize_field("update_strategy", &self.update_strategy)?;
+		state.serialize_field("next_update_time", &self.next_update_time)?;
+		state.serialize_field("chapters", &self.chapters)?;
+		state.serialize_field("has_more_chapters", &self.has_more_chapters)?;
+		if version >= 1 {
+			state.serialize_field("alt_titles", &self.alt_titles)?;
+			state.serialize_field("artists", &self.artists)?;
+			state.serialize_field("rating", &self.rating)?;
+			state.serialize_field("rating_count", &self.rating_count)?;
+			state.serialize_field("views", &self.views)?;
+			state.serialize_field("follows", &self.follows)?;
+			state.serialize_field("word_count", &self.word_count)?;
+			state.serialize_field("chapter_count", &self.chapter_count)?;
+			state.serialize_field("original_language", &self.original_language)?;
+			state.serialize_field("translation_status", &self.translation_status)?;
+		}
+		state.end()
 	}
 }
 
+impl<'de> Deserialize<'de> for Novel {
+	fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		struct NovelVisitor;
+
+		impl<'de> de::Visitor<'de> for NovelVisitor {
+			type Value = Novel;
+
+			fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+				formatter.write_str("a novel")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> core::result::Result<Novel, A::Error>
+			where
+				A: SeqAccess<'de>,
+			{
+				let mut novel = Novel {
+					key: next_field(&mut seq, "key")?,
+					title: next_field(&mut seq, "title")?,
+					cover: next_field(&mut seq, "cover")?,
+					authors: next_field(&mut seq, "authors")?,
+					description: next_field(&mut seq, "description")?,
+					url: next_field(&mut seq, "url")?,
+					tags: next_field(&mut seq, "tags")?,
+					status: next_field(&mut seq, "status")?,
+					content_rating: next_field(&mut seq, "content_rating")?,
+					update_strategy: next_field(&mut seq, "update_strategy")?,
+					next_update_time: next_field(&mut seq, "next_update_time")?,
+					chapters: next_field(&mut seq, "chapters")?,
+					has_more_chapters: next_field(&mut seq, "has_more_chapters")?,
+					..Default::default()
+				};
+				if struct_version() >= 1 {
+					novel.alt_titles = next_field(&mut seq, "alt_titles")?;
+					novel.artists = next_field(&mut seq, "artists")?;
+					novel.rating = next_field(&mut seq, "rating")?;
+					novel.rating_count = next_field(&mut seq, "rating_count")?;
+					novel.views = next_field(&mut seq, "views")?;
+					novel.follows = next_field(&mut seq, "follows")?;
+					novel.word_count = next_field(&mut seq, "word_count")?;
+					novel.chapter_count = next_field(&mut seq, "chapter_count")?;
+					novel.original_language = next_field(&mut seq, "original_language")?;
+					novel.translation_status = next_field(&mut seq, "translation_status")?;
+				}
+				Ok(novel)
+			}
+		}
+
+		deserializer.deserialize_struct("Novel", NOVEL_FIELDS, NovelVisitor)
+	}
+}
+
+const NOVEL_FIELDS: &[&str] = &[
+	"key",
+	"title",
+	"cover",
+	"authors",
+	"description",
+	"url",
+	"tags",
+	"status",
+	"content_rating",
+	"update_strategy",
+	"next_update_time",
+	"chapters",
+	"has_more_chapters",
+	"alt_titles",
+	"artists",
+	"rating",
+	"rating_count",
+	"views",
+	"follows",
+	"word_count",
+	"chapter_count",
+	"original_language",
+	"translation_status",
+];
+
 /// A page of novel entries.
-#[derive(Default, Clone, Debug, PartialEq, Serialize)]
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct NovelPageResult {
 	/// List of novel entries.
 	pub entries: Vec<Novel>,
@@ -134,8 +323,42 @@ pub struct NovelPageResult {
 	pub has_next_page: bool,
 }
 
-/// A chapter of a novel.
+/// The reading progress of a novel on the source website.
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReadingProgress {
+	/// Key of the chapter the user is reading.
+	pub chapter_key: String,
+	/// Position in the chapter, from 0 (start) to 1 (end).
+	pub position: f32,
+	/// Date the progress was last updated.
+	pub updated_at: Option<i64>,
+}
+
+/// A lightweight search result shown while the user is typing a query.
 #[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SearchSuggestion {
+	/// Title of the suggested novel.
+	pub title: String,
+	/// Unique identifier for the novel, used to open it.
+	pub key: String,
+	/// Link to the novel cover image.
+	pub cover: Option<String>,
+}
+
+/// A titled group of novels, e.g. other novels by the same author.
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NovelGroup {
+	/// Title of the group, e.g. `Readers also liked`.
+	pub title: String,
+	/// List of novels in the group.
+	pub entries: Vec<Novel>,
+}
+
+/// A chapter of a novel.
+///
+/// The fields after `locked` are only encoded for hosts that negotiate [STRUCT_VERSION] 1 or
+/// later.
+#[derive(Default, Clone, Debug, PartialEq)]
 pub struct Chapter {
 	/// Unique identifier for the chapter.
 	pub key: String,
@@ -157,9 +380,231 @@ pub struct Chapter {
 	pub thumbnail: Option<String>,
 	/// Boolean indicating if the chapter is locked.
 	pub locked: bool,
+	/// Number of words in the chapter.
+	pub word_count: Option<i32>,
+	/// Price to unlock the chapter, in the website's currency (e.g. coins).
+	pub unlock_cost: Option<i32>,
+	/// Date a locked chapter becomes free to read, e.g. after early access ends.
+	pub free_at: Option<i64>,
+	/// Link to the chapter content on an external website, e.g. a translator's blog.
+	///
+	/// Chapters with an external url are opened in a browser instead of the reader.
+	pub external_url: Option<String>,
+	/// Boolean indicating if the chapter is a header for the start of a volume,
+	/// rather than a readable chapter.
+	pub is_volume_header: bool,
+}
+
+impl Chapter {
+	/// Copy the values from another chapter into this one.
+	///
+	/// Optional values are only copied if they're present in the other chapter.
+	pub fn copy_from(&mut self, chapter: Chapter) {
+		self.key = chapter.key;
+		if let Some(title) = chapter.title {
+			self.title = Some(title);
+		}
+		if let Some(chapter_number) = chapter.chapter_number {
+			self.chapter_number = Some(chapter_number);
+		}
+		if let Some(volume_number) = chapter.volume_number {
+			self.volume_number = Some(volume_number);
+		}
+		if let Some(date_uploaded) = chapter.date_uploaded {
+			self.date_uploaded = Some(date_uploaded);
+		}
+		if let Some(scanlators) = chapter.scanlators {
+			self.scanlators = Some(scanlators);
+		}
+		if let Some(url) = chapter.url {
+			self.url = Some(url);
+		}
+		if let Some(language) = chapter.language {
+			self.language = Some(language);
+		}
+		if let Some(thumbnail) = chapter.thumbnail {
+			self.thumbnail = Some(thumbnail);
+		}
+		self.locked = chapter.locked;
+		if let Some(word_count) = chapter.word_count {
+			self.word_count = Some(word_count);
+		}
+		if let Some(unlock_cost) = chapter.unlock_cost {
+			self.unlock_cost = Some(unlock_cost);
+		}
+		if let Some(free_at) = chapter.free_at {
+			self.free_at = Some(free_at);
+		}
+		if let Some(external_url) = chapter.external_url {
+			self.external_url = Some(external_url);
+		}
+		self.is_volume_header = chapter.is_volume_header;
+	}
+}
+
+impl Serialize for Chapter {
+	fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		let version = struct_version();
+		let mut state =
+			serializer.serialize_struct("Chapter", if version >= 1 { 15 } else { 10 })?;
+		state.serialize_field("key", &self.key)?;
+		state.serialize_field("title", &self.title)?;
+		state.serialize_field("chapter_number", &self.chapter_number)?;
+		state.serialize_field("volume_number", &self.volume_number)?;
+		state.serialize_field("date_uploaded", &self.date_uploaded)?;
+		state.serialize_field("scanlators", &self.scanlators)?;
+		state.serialize_field("url", &self.url)?;
+		state.serialize_field("language", &self.language)?;
+		state.serialize_field("thumbnail", &self.thumbnail)?;
+		state.serialize_field("locked", &self.locked)?;
+		if version >= 1 {
+			state.serialize_field("word_count", &self.word_count)?;
+			state.serialize_field("unlock_cost", &self.unlock_cost)?;
+			state.serialize_field("free_at", &self.free_at)?;
+			state.serialize_field("external_url", &self.external_url)?;
+			state.serialize_field("is_volume_header", &self.is_volume_header)?;
+		}
+		state.end()
+	}
+}
+
+impl<'de> Deserialize<'de> for Chapter {
+	fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		struct ChapterVisitor;
+
+		impl<'de> de::Visitor<'de> for ChapterVisitor {
+			type Value = Chapter;
+
+			fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+				formatter.write_str("a chapter")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> core::result::Result<Chapter, A::Error>
+			where
+				A: SeqAccess<'de>,
+			{
+				let mut chapter = Chapter {
+					key: next_field(&mut seq, "key")?,
+					title: next_field(&mut seq, "title")?,
+					chapter_number: next_field(&mut seq, "chapter_number")?,
+					volume_number: next_field(&mut seq, "volume_number")?,
+					date_uploaded: next_field(&mut seq, "date_uploaded")?,
+					scanlators: next_field(&mut seq, "scanlators")?,
+					url: next_field(&mut seq, "url")?,
+					language: next_field(&mut seq, "language")?,
+					thumbnail: next_field(&mut seq, "thumbnail")?,
+					locked: next_field(&mut seq, "locked")?,
+					..Default::default()
+				};
+				if struct_version() >= 1 {
+					chapter.word_count = next_field(&mut seq, "word_count")?;
+					chapter.unlock_cost = next_field(&mut seq, "unlock_cost")?;
+					chapter.free_at = next_field(&mut seq, "free_at")?;
+					chapter.external_url = next_field(&mut seq, "external_url")?;
+					chapter.is_volume_header = next_field(&mut seq, "is_volume_header")?;
+				}
+				Ok(chapter)
+			}
+		}
+
+		deserializer.deserialize_struct("Chapter", CHAPTER_FIELDS, ChapterVisitor)
+	}
+}
+
+const CHAPTER_FIELDS: &[&str] = &[
+	"key",
+	"title",
+	"chapter_number",
+	"volume_number",
+	"date_uploaded",
+	"scanlators",
+	"url",
+	"language",
+	"thumbnail",
+	"locked",
+	"word_count",
+	"unlock_cost",
+	"free_at",
+	"external_url",
+	"is_volume_header",
+];
+
+/// A novel paired with one of its chapters, e.g. for a list of latest chapter updates.
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NovelWithChapter {
+	pub novel: Novel,
+	pub chapter: Chapter,
+}
+
+/// The horizontal alignment of a paragraph.
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum TextAlignment {
+	/// Aligned to the start of the text direction.
+	#[default]
+	Natural,
+	Left,
+	Center,
+	Right,
+	Justified,
+}
+
+/// The direction text in a paragraph is written in.
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum TextDirection {
+	/// The direction of the reader's layout.
+	#[default]
+	Natural,
+	LeftToRight,
+	RightToLeft,
+	/// Vertical columns written top to bottom, ordered right to left.
+	Vertical,
+}
+
+/// The style of a paragraph.
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ParagraphStyle {
+	/// Horizontal alignment of the paragraph.
+	pub alignment: TextAlignment,
+	/// Direction of the paragraph text.
+	pub direction: TextDirection,
+	/// Whether the whole paragraph is italic, e.g. for letters or inner thoughts.
+	pub italic: bool,
+	/// Font size of the paragraph.
+	pub font_size: Option<String>,
+}
+
+impl ParagraphStyle {
+	/// Creates a style for a centered paragraph, e.g. for a scene title.
+	pub fn centered() -> Self {
+		Self {
+			alignment: TextAlignment::Center,
+			..Default::default()
+		}
+	}
+
+	/// Creates a style for an italic paragraph.
+	pub fn italic() -> Self {
+		Self {
+			italic: true,
+			..Default::default()
+		}
+	}
 }
 
 /// An element of the page content that can be a paragraph, quote block or a table.
+///
+/// # Ruby annotations
+///
+/// Markdown text can contain ruby annotations (e.g. furigana) written as
+/// `{base|annotation}`. Any `{`, `|`, and `}` characters in the text itself should be
+/// escaped with a backslash, which [escape_markdown](crate::helpers::string::PlainText::escape_markdown)
+/// does. Use [ruby](crate::helpers::string::ruby) to create an annotation.
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub enum ContentBlock {
 	/// Block/Banner quoted text.
@@ -170,6 +615,8 @@ pub enum ContentBlock {
 	Table(Vec<Vec<String>>),
 	/// Section break or divider.
 	Divider,
+	/// Markdown text content with a paragraph style.
+	StyledParagraph(String, ParagraphStyle),
 }
 
 impl ContentBlock {
@@ -187,6 +634,11 @@ impl ContentBlock {
 		Self::Paragraph(text.into(), font_size)
 	}
 
+	/// Create a new `PageContent` with a markdown text string and paragraph style.
+	pub fn styled_paragraph<T: Into<String>>(text: T, style: ParagraphStyle) -> Self {
+		Self::StyledParagraph(text.into(), style)
+	}
+
 	/// Create a new `PageContent` with a table.
 	pub fn table(data: Vec<Vec<String>>) -> Self {
 		Self::Table(data)
@@ -208,6 +660,79 @@ pub enum ListingKind {
 	List,
 }
 
+/// A result of a deep link handling.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeepLinkResult {
+	Novel { key: String },
+	Chapter { novel_key: String, key: String },
+	Listing(Listing),
+}
+
+impl Serialize for DeepLinkResult {
+	fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		let mut state = serializer.serialize_struct("DeepLinkResult", 3)?;
+		match self {
+			DeepLinkResult::Novel { key } => {
+				state.serialize_field("novel_key", &Some(key))?;
+				state.serialize_field("chapter_key", &Option::<String>::None)?;
+				state.serialize_field("listing", &Option::<Listing>::None)?;
+			}
+			DeepLinkResult::Chapter { novel_key, key } => {
+				state.serialize_field("novel_key", &Some(novel_key))?;
+				state.serialize_field("chapter_key", &Some(key))?;
+				state.serialize_field("listing", &Option::<Listing>::None)?;
+			}
+			DeepLinkResult::Listing(listing) => {
+				state.serialize_field("novel_key", &Option::<String>::None)?;
+				state.serialize_field("chapter_key", &Option::<String>::None)?;
+				state.serialize_field("listing", &Some(listing))?;
+			}
+		}
+		state.end()
+	}
+}
+
+// the flattened form of a deep link result, as it's serialized
+#[derive(Deserialize)]
+struct RawDeepLinkResult {
+	novel_key: Option<String>,
+	chapter_key: Option<String>,
+	listing: Option<Listing>,
+}
+
+impl<'de> Deserialize<'de> for DeepLinkResult {
+	fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let raw = RawDeepLinkResult::deserialize(deserializer)?;
+		match (raw.novel_key, raw.chapter_key, raw.listing) {
+			(_, _, Some(listing)) => Ok(DeepLinkResult::Listing(listing)),
+			(Some(novel_key), Some(key), None) => Ok(DeepLinkResult::Chapter { novel_key, key }),
+			(Some(key), None, None) => Ok(DeepLinkResult::Novel { key }),
+			_ => Err(de::Error::custom(
+				"deep link result has no novel key or listing",
+			)),
+		}
+	}
+}
+
+/// Reads the next field of a struct that's serialized with a variable set of fields.
+///
+/// Structs like [Filter] only serialize the fields that are used by their kind, so they
+/// can only be deserialized from formats that aren't self-describing, like postcard.
+fn next_field<'de, T, A>(seq: &mut A, name: &'static str) -> core::result::Result<T, A::Error>
+where
+	T: Deserialize<'de>,
+	A: SeqAccess<'de>,
+{
+	seq.next_element()?
+		.ok_or_else(|| de::Error::missing_field(name))
+}
+
 /// A listing of the source containing novels.
 #[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Listing {
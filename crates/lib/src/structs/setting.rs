@@ -1,4 +1,9 @@
-use serde::{ser::SerializeStruct, Serialize};
+use super::next_field;
+use serde::{
+	de::{self, SeqAccess, Visitor},
+	ser::SerializeStruct,
+	Deserialize, Deserializer, Serialize,
+};
 
 extern crate alloc;
 use alloc::{borrow::Cow, string::String, vec::Vec};
@@ -36,6 +41,55 @@ impl Serialize for Setting {
 	}
 }
 
+impl<'de> Deserialize<'de> for Setting {
+	fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		struct SettingVisitor;
+
+		impl<'de> Visitor<'de> for SettingVisitor {
+			type Value = Setting;
+
+			fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+				formatter.write_str("a setting")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> core::result::Result<Setting, A::Error>
+			where
+				A: SeqAccess<'de>,
+			{
+				// the type is also encoded by the value, so it's only needed by the app
+				let _: String = next_field(&mut seq, "type")?;
+				Ok(Setting {
+					key: next_field(&mut seq, "key")?,
+					title: next_field(&mut seq, "title")?,
+					notification: next_field(&mut seq, "notification")?,
+					requires: next_field(&mut seq, "requires")?,
+					requires_false: next_field(&mut seq, "requires_false")?,
+					refreshes: next_field(&mut seq, "refreshes")?,
+					value: next_field(&mut seq, "value")?,
+				})
+			}
+		}
+
+		deserializer.deserialize_struct(
+			"Setting",
+			&[
+				"type",
+				"key",
+				"title",
+				"notification",
+				"requires",
+				"requires_false",
+				"refreshes",
+				"value",
+			],
+			SettingVisitor,
+		)
+	}
+}
+
 /// A login method.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LoginMethod {
@@ -60,8 +114,26 @@ impl Serialize for LoginMethod {
 	}
 }
 
+impl<'de> Deserialize<'de> for LoginMethod {
+	fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let method = String::deserialize(deserializer)?;
+		match method.as_str() {
+			"basic" => Ok(Self::Basic),
+			"oauth" => Ok(Self::OAuth),
+			"web" => Ok(Self::Web),
+			method => Err(de::Error::unknown_variant(
+				method,
+				&["basic", "oauth", "web"],
+			)),
+		}
+	}
+}
+
 /// The kind of setting.
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum SettingValue {
 	/// A group of settings.
 	Group {
@@ -259,6 +331,45 @@ impl Serialize for PageIcon {
 	}
 }
 
+impl<'de> Deserialize<'de> for PageIcon {
+	fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		struct PageIconVisitor;
+
+		impl<'de> Visitor<'de> for PageIconVisitor {
+			type Value = PageIcon;
+
+			fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+				formatter.write_str("a page icon")
+			}
+
+			fn visit_seq<A>(self, mut seq: A) -> core::result::Result<PageIcon, A::Error>
+			where
+				A: SeqAccess<'de>,
+			{
+				let kind: String = next_field(&mut seq, "type")?;
+				match kind.as_str() {
+					"system" => Ok(PageIcon::System {
+						name: next_field(&mut seq, "name")?,
+						color: next_field(&mut seq, "color")?,
+						inset: next_field(&mut seq, "inset")?,
+					}),
+					"url" => Ok(PageIcon::Url(next_field(&mut seq, "url")?)),
+					kind => Err(de::Error::unknown_variant(kind, &["system", "url"])),
+				}
+			}
+		}
+
+		deserializer.deserialize_struct(
+			"PageIcon",
+			&["type", "name", "color", "inset"],
+			PageIconVisitor,
+		)
+	}
+}
+
 macro_rules! create_setting_struct {
 	(
 		$struct_name:ident,
@@ -1,9 +1,10 @@
 use super::{
-	Chapter, ContentBlock, Filter, FilterValue, HashMap, HomeLayout, Listing, Novel,
-	NovelPageResult, Setting,
+	Chapter, Comment, CommentPageResult, ContentBlock, DeepLinkResult, Filter, FilterValue,
+	HashMap, HomeLayout, Listing, Novel, NovelGroup, NovelPageResult, ReadingProgress,
+	SearchSuggestion, Setting, UnlockOption, UnlockResult,
 };
 use crate::alloc::{String, Vec};
-use serde::{ser::SerializeStruct, Deserialize, Serialize};
+use serde::{Deserialize, Serialize};
 
 pub use crate::imports::error::{BunyError, Result};
 
@@ -104,6 +105,91 @@ pub trait WebLoginHandler: Source {
 	fn handle_web_login(&self, key: String, cookies: HashMap<String, String>) -> Result<bool>;
 }
 
+/// A source that can read and modify the user's library on the website.
+///
+/// The user should be logged in with [BasicLoginHandler] or [WebLoginHandler] first,
+/// so these functions should return [BunyError::LoginRequired] otherwise.
+///
+/// This trait only syncs which novels are in the library. Syncing the last read chapter used
+/// to be part of this trait, but was moved to [ProgressSyncHandler], which syncs the reading
+/// progress of a novel with [mark_chapter_read](ProgressSyncHandler::mark_chapter_read) and
+/// [get_remote_progress](ProgressSyncHandler::get_remote_progress).
+pub trait LibrarySyncProvider: Source {
+	/// Returns a page of the novels in the user's library on the website.
+	fn get_library(&self, page: i32) -> Result<NovelPageResult>;
+
+	/// Adds a novel to the user's library on the website.
+	fn add_to_library(&self, novel: Novel) -> Result<()>;
+
+	/// Removes a novel from the user's library on the website.
+	fn remove_from_library(&self, novel: Novel) -> Result<()>;
+}
+
+/// A source that provides comments on novels and chapters.
+pub trait CommentProvider: Source {
+	/// Returns a page of the comments on a novel, or on one of its chapters if given.
+	fn get_comments(
+		&self,
+		novel: Novel,
+		chapter: Option<Chapter>,
+		page: i32,
+	) -> Result<CommentPageResult>;
+
+	/// Returns a page of the replies to a comment from [get_comments](Self::get_comments).
+	fn get_comment_replies(
+		&self,
+		novel: Novel,
+		chapter: Option<Chapter>,
+		comment: Comment,
+		page: i32,
+	) -> Result<CommentPageResult>;
+}
+
+/// A source that provides suggestions for a partial search query, e.g. from an instant search api.
+pub trait SearchSuggestionProvider: Source {
+	/// Returns suggested novels for a partial search query.
+	///
+	/// This is called as the user types, so it should make as few requests as possible.
+	fn get_search_suggestions(&self, query: String) -> Result<Vec<SearchSuggestion>>;
+}
+
+/// A source that provides novels related to a novel, e.g. for a "readers also liked" list.
+pub trait RecommendationProvider: Source {
+	/// Returns groups of novels related to a novel.
+	///
+	/// Empty groups are not shown.
+	fn get_related_novels(&self, novel: Novel) -> Result<Vec<NovelGroup>>;
+}
+
+/// A source that syncs reading progress with the user's account on the website.
+///
+/// The user should be logged in with [BasicLoginHandler] or [WebLoginHandler] first,
+/// so these functions should return [BunyError::LoginRequired] otherwise.
+pub trait ProgressSyncHandler: Source {
+	/// Marks a chapter as read up to a position, from 0 (start) to 1 (end).
+	fn mark_chapter_read(&self, novel: Novel, chapter: Chapter, position: f32) -> Result<()>;
+
+	/// Returns the reading progress of a novel on the website, if any.
+	fn get_remote_progress(&self, novel: Novel) -> Result<Option<ReadingProgress>>;
+}
+
+/// A source that can unlock locked chapters, e.g. by purchasing them with coins.
+///
+/// The user should be logged in with [BasicLoginHandler] or [WebLoginHandler] first,
+/// so these functions should return [BunyError::LoginRequired] otherwise.
+pub trait ChapterUnlockHandler: Source {
+	/// Returns the ways a locked chapter can be unlocked.
+	fn get_unlock_options(&self, novel: Novel, chapter: Chapter) -> Result<Vec<UnlockOption>>;
+
+	/// Unlocks a chapter with one of the options from [get_unlock_options](Self::get_unlock_options).
+	fn unlock_chapter(
+		&self,
+		novel: Novel,
+		chapter: Chapter,
+		option: UnlockOption,
+	) -> Result<UnlockResult>;
+}
+
 /// A source that handles key migration.
 ///
 /// If a source provides a "breakingChangeVersion" in its configuration, these functions will be
@@ -114,41 +200,6 @@ pub trait MigrationHandler: Source {
 	fn handle_chapter_migration(&self, novel_key: String, chapter_key: String) -> Result<String>;
 }
 
-/// A result of a deep link handling.
-#[derive(Debug, Clone, PartialEq)]
-pub enum DeepLinkResult {
-	Novel { key: String },
-	Chapter { novel_key: String, key: String },
-	Listing(Listing),
-}
-
-impl Serialize for DeepLinkResult {
-	fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
-	where
-		S: serde::Serializer,
-	{
-		let mut state = serializer.serialize_struct("DeepLinkResult", 3)?;
-		match self {
-			DeepLinkResult::Novel { key } => {
-				state.serialize_field("novel_key", &Some(key))?;
-				state.serialize_field("chapter_key", &Option::<String>::None)?;
-				state.serialize_field("listing", &Option::<Listing>::None)?;
-			}
-			DeepLinkResult::Chapter { novel_key, key } => {
-				state.serialize_field("novel_key", &Some(novel_key))?;
-				state.serialize_field("chapter_key", &Some(key))?;
-				state.serialize_field("listing", &Option::<Listing>::None)?;
-			}
-			DeepLinkResult::Listing(listing) => {
-				state.serialize_field("novel_key", &Option::<String>::None)?;
-				state.serialize_field("chapter_key", &Option::<String>::None)?;
-				state.serialize_field("listing", &Some(listing))?;
-			}
-		}
-		state.end()
-	}
-}
-
 /// The details of a HTTP request.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ImageRequest {
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+extern crate alloc;
+use alloc::string::String;
+
+/// The kind of payment used to unlock a chapter.
+#[derive(Default, PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum UnlockKind {
+	/// The website's currency, e.g. coins.
+	#[default]
+	Coins,
+	/// A ticket or pass, e.g. a free daily ticket.
+	Ticket,
+	/// Watching an advertisement.
+	Ad,
+	/// Waiting for a timer to end.
+	WaitTimer,
+	/// A subscription or membership that includes the chapter.
+	Subscription,
+}
+
+/// A way to unlock a locked chapter, returned by
+/// [get_unlock_options](crate::ChapterUnlockHandler::get_unlock_options).
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UnlockOption {
+	/// Unique identifier for the option.
+	pub id: String,
+	/// Title of the option shown to the user, e.g. `Unlock for 5 coins`.
+	pub title: String,
+	/// Kind of payment the option uses.
+	pub kind: UnlockKind,
+	/// Amount of the currency or tickets needed to unlock the chapter.
+	pub cost: Option<i32>,
+	/// Amount of the currency or tickets the user has.
+	pub balance: Option<i32>,
+	/// Date the option becomes available, e.g. when a wait timer ends.
+	pub available_at: Option<i64>,
+}
+
+/// The result of unlocking a chapter with
+/// [unlock_chapter](crate::ChapterUnlockHandler::unlock_chapter).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum UnlockResult {
+	/// The chapter was unlocked and can be read.
+	Unlocked,
+	/// The chapter will be unlocked at the given date, e.g. once a wait timer ends.
+	Scheduled { available_at: i64 },
+	/// The user doesn't have enough of the currency or tickets needed.
+	InsufficientBalance,
+	/// The unlock needs to be completed in a browser at the given url, e.g. to watch an ad.
+	OpenUrl(String),
+}
@@ -0,0 +1,53 @@
+/// The latest version of the layout of [Novel](super::Novel) and [Chapter](super::Chapter).
+///
+/// Postcard encodes struct fields by position, so appending fields changes the layout of every
+/// result that contains a novel or chapter. Sources encode the layout of the version that the
+/// host negotiates with the `set_struct_version` function, and hosts that don't negotiate a
+/// version get the first layout:
+///
+/// | Version | Layout |
+/// |---------|--------|
+/// | 0 | The original fields, ending with `has_more_chapters` for novels and `locked` for chapters. |
+/// | 1 | Adds the novel fields from `alt_titles` to `translation_status`, and the chapter fields from `word_count` to `is_volume_header`. |
+///
+/// New fields must be appended to the end of a struct, and only encoded for a new version.
+pub const STRUCT_VERSION: i32 = 1;
+
+// wasm sources start with the first layout, until the host negotiates a newer version
+#[cfg(not(feature = "native"))]
+static CURRENT_VERSION: core::sync::atomic::AtomicI32 = core::sync::atomic::AtomicI32::new(0);
+
+// the native runner hosts sources on several threads, which may use different versions
+#[cfg(feature = "native")]
+std::thread_local! {
+	static CURRENT_VERSION: core::cell::Cell<i32> = const { core::cell::Cell::new(STRUCT_VERSION) };
+}
+
+/// Returns the struct layout version that novels and chapters are encoded with.
+pub fn struct_version() -> i32 {
+	#[cfg(not(feature = "native"))]
+	return CURRENT_VERSION.load(core::sync::atomic::Ordering::Relaxed);
+	#[cfg(feature = "native")]
+	return CURRENT_VERSION.with(|version| version.get());
+}
+
+/// Sets the struct layout version that novels and chapters are encoded with, and returns the
+/// version that's used, which is limited to the supported versions.
+pub fn set_struct_version(version: i32) -> i32 {
+	let version = version.clamp(0, STRUCT_VERSION);
+	#[cfg(not(feature = "native"))]
+	CURRENT_VERSION.store(version, core::sync::atomic::Ordering::Relaxed);
+	#[cfg(feature = "native")]
+	CURRENT_VERSION.with(|current| current.set(version));
+	version
+}
+
+/// Calls a function with novels and chapters encoded with a struct layout version, e.g. to
+/// decode the results of a source that negotiated an older version.
+pub fn with_struct_version<R>(version: i32, f: impl FnOnce() -> R) -> R {
+	let previous = struct_version();
+	set_struct_version(version);
+	let result = f();
+	set_struct_version(previous);
+	result
+}
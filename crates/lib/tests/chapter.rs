@@ -0,0 +1,109 @@
+#![cfg(feature = "helpers")]
+
+use buny::{
+	helpers::chapter::{normalize_chapter_title, ChapterTitle},
+	Chapter,
+};
+use paste::paste;
+
+macro_rules! titles {
+	($($name:ident($($text:expr => ($volume:expr, $chapter:expr, $part:expr, $title:expr)),+ $(,)?))+) => {$(paste! {
+		#[test]
+		fn [<title_ $name>]() {
+			$(
+				assert_eq!(
+					ChapterTitle::parse($text),
+					ChapterTitle {
+						volume_number: $volume,
+						chapter_number: $chapter,
+						part_number: $part,
+						title: Option::<&str>::map($title, String::from),
+					},
+					"{}",
+					$text
+				);
+			)+
+		}
+	})+};
+}
+
+titles! {
+	english(
+		"Chapter 12" => (None, Some(12.0), None, None),
+		"Chapter 12: The Title" => (None, Some(12.0), None, Some("The Title")),
+		"Vol. 3 Chapter 12.5: Title" => (Some(3.0), Some(12.5), None, Some("Title")),
+		"Volume 2 - Chapter 3 - The Title" => (Some(2.0), Some(3.0), None, Some("The Title")),
+		"Book 1, Ch. 4 — Title" => (Some(1.0), Some(4.0), None, Some("Title")),
+		"V2C3" => (Some(2.0), Some(3.0), None, None),
+		"[Vol 1] Chapter 3" => (Some(1.0), Some(3.0), None, None),
+		"Ch 12 (Part 2)" => (None, Some(12.0), Some(2.0), None),
+		"Chapter 12 Part 2 - Title" => (None, Some(12.0), Some(2.0), Some("Title")),
+		"Chapter 7 (2/3)" => (None, Some(7.0), Some(2.0), None),
+		"Chapter 3 (Revised)" => (None, Some(3.0), None, Some("(Revised)")),
+		"Episode 45" => (None, Some(45.0), None, None),
+		"ep.45 - Title" => (None, Some(45.0), None, Some("Title")),
+		"12. Title" => (None, Some(12.0), None, Some("Title")),
+		"12 - Title" => (None, Some(12.0), None, Some("Title")),
+		"12" => (None, Some(12.0), None, None),
+	)
+	other_languages(
+		"Capítulo 12: Título" => (None, Some(12.0), None, Some("Título")),
+		"Chapitre 5 - Titre" => (None, Some(5.0), None, Some("Titre")),
+		"Chương 100: Tiêu đề" => (None, Some(100.0), None, Some("Tiêu đề")),
+		"Глава 7. Название" => (None, Some(7.0), None, Some("Название")),
+		"Bab 3" => (None, Some(3.0), None, None),
+	)
+	chinese(
+		"第一百二十三章 标题" => (None, Some(123.0), None, Some("标题")),
+		"第三卷 第十二章：标题" => (Some(3.0), Some(12.0), None, Some("标题")),
+		"第12章" => (None, Some(12.0), None, None),
+		"第 5 章 标题" => (None, Some(5.0), None, Some("标题")),
+		"第十章（下）" => (None, Some(10.0), Some(3.0), None),
+		"第一千零一章" => (None, Some(1001.0), None, None),
+		"第两万三千章" => (None, Some(23000.0), None, None),
+		"第二〇五章" => (None, Some(205.0), None, None),
+		"第１２章" => (None, Some(12.0), None, None),
+	)
+	japanese(
+		"第一話 タイトル" => (None, Some(1.0), None, Some("タイトル")),
+		"第2巻 第15話「タイトル」" => (Some(2.0), Some(15.0), None, Some("「タイトル」")),
+		"第三十回" => (None, Some(30.0), None, None),
+	)
+	korean(
+		"제12화 제목" => (None, Some(12.0), None, Some("제목")),
+		"3화" => (None, Some(3.0), None, None),
+	)
+	unnumbered(
+		"Prologue" => (None, None, None, Some("Prologue")),
+		"Volcano" => (None, None, None, Some("Volcano")),
+		"Chaos 3" => (None, None, None, Some("Chaos 3")),
+		"Side Story - Episode 45" => (None, Some(45.0), None, Some("Side Story - Episode 45")),
+		"1984" => (None, Some(1984.0), None, None),
+		"2024 Recap" => (None, None, None, Some("2024 Recap")),
+		"第一二三四五六七八九一二三四五章" => (None, None, None, Some("第一二三四五六七八九一二三四五章")),
+		"" => (None, None, None, None),
+	)
+}
+
+#[test]
+fn normalize_titles() {
+	assert_eq!(
+		normalize_chapter_title(" Chapter 1 - Title "),
+		Some("Title".into())
+	);
+	assert_eq!(normalize_chapter_title("Chapter 1"), None);
+	assert_eq!(normalize_chapter_title("Epilogue"), Some("Epilogue".into()));
+}
+
+#[test]
+fn apply_to_chapter() {
+	let mut chapter = Chapter {
+		title: Some("Vol. 2 Chapter 10 - Title".into()),
+		chapter_number: Some(11.0),
+		..Default::default()
+	};
+	ChapterTitle::parse(chapter.title.as_deref().unwrap_or_default()).apply_to(&mut chapter);
+	assert_eq!(chapter.volume_number, Some(2.0));
+	assert_eq!(chapter.chapter_number, Some(11.0));
+	assert_eq!(chapter.title, Some("Title".into()));
+}
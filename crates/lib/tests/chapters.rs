@@ -0,0 +1,192 @@
+#![cfg(feature = "helpers")]
+
+use buny::{helpers::chapters, Chapter, Novel};
+
+fn chapter(key: &str, number: Option<f32>) -> Chapter {
+	Chapter {
+		key: key.into(),
+		chapter_number: number,
+		..Default::default()
+	}
+}
+
+fn keys(chapters: &[Chapter]) -> Vec<&str> {
+	chapters
+		.iter()
+		.map(|chapter| chapter.key.as_str())
+		.collect()
+}
+
+fn numbers(chapters: &[Chapter]) -> Vec<Option<f32>> {
+	chapters
+		.iter()
+		.map(|chapter| chapter.chapter_number)
+		.collect()
+}
+
+#[test]
+fn dedupe_keeps_first() {
+	let mut list = vec![
+		chapter("/3", Some(3.0)),
+		chapter("/2", Some(2.0)),
+		chapter("/3", None),
+		chapter("/1", Some(1.0)),
+	];
+	chapters::dedupe(&mut list);
+	assert_eq!(keys(&list), ["/3", "/2", "/1"]);
+	assert_eq!(list[0].chapter_number, Some(3.0));
+}
+
+#[test]
+fn sort_by_numbers() {
+	let mut list = vec![
+		chapter("/1", Some(1.0)),
+		chapter("/2", Some(2.0)),
+		chapter("/2.5", Some(2.5)),
+		chapter("/3", Some(3.0)),
+	];
+	chapters::sort_newest_first(&mut list);
+	assert_eq!(keys(&list), ["/3", "/2.5", "/2", "/1"]);
+
+	// chapters without numbers are only reversed
+	let mut list = vec![
+		chapter("/prologue", None),
+		chapter("/1", Some(1.0)),
+		chapter("/2", Some(2.0)),
+		chapter("/epilogue", None),
+	];
+	chapters::sort_newest_first(&mut list);
+	assert_eq!(keys(&list), ["/epilogue", "/2", "/1", "/prologue"]);
+
+	// numbers that restart in each volume
+	let mut list =
+		[(1.0, 1.0), (1.0, 2.0), (2.0, 1.0), (2.0, 2.0)].map(|(volume, number)| Chapter {
+			key: format!("/{volume}/{number}"),
+			volume_number: Some(volume),
+			chapter_number: Some(number),
+			..Default::default()
+		});
+	chapters::sort_newest_first(&mut list);
+	assert_eq!(keys(&list), ["/2/2", "/2/1", "/1/2", "/1/1"]);
+}
+
+#[test]
+fn sort_by_dates() {
+	let mut list = [100, 200, 300].map(|date| Chapter {
+		key: format!("/{date}"),
+		date_uploaded: Some(date),
+		..Default::default()
+	});
+	chapters::sort_newest_first(&mut list);
+	assert_eq!(keys(&list), ["/300", "/200", "/100"]);
+
+	chapters::sort_newest_first(&mut list);
+	assert_eq!(keys(&list), ["/300", "/200", "/100"]);
+}
+
+#[test]
+fn infer_numbers() {
+	let mut list = vec![
+		chapter("/c", None),
+		chapter("/b", None),
+		chapter("/a", None),
+	];
+	chapters::infer_chapter_numbers(&mut list);
+	assert_eq!(numbers(&list), [Some(3.0), Some(2.0), Some(1.0)]);
+
+	let mut list = vec![
+		chapter("/7", Some(7.0)),
+		chapter("/6", None),
+		chapter("/5.5", Some(5.5)),
+		chapter("/5", Some(5.0)),
+		chapter("/1", None),
+		chapter("/volume", None),
+	];
+	list[5].is_volume_header = true;
+	chapters::infer_chapter_numbers(&mut list);
+	assert_eq!(
+		numbers(&list),
+		[Some(7.0), Some(6.0), Some(5.5), Some(5.0), Some(1.0), None]
+	);
+
+	let mut list = vec![Chapter {
+		title: Some("Vol. 2 Chapter 4".into()),
+		..Default::default()
+	}];
+	chapters::infer_chapter_numbers(&mut list);
+	assert_eq!(list[0].volume_number, Some(2.0));
+	assert_eq!(list[0].chapter_number, Some(4.0));
+}
+
+#[test]
+fn merge_and_fetch_pages() {
+	let pages = [
+		vec![chapter("/5", None), chapter("/4", None)],
+		vec![
+			chapter("/4", None),
+			chapter("/3", None),
+			chapter("/2", None),
+		],
+		vec![chapter("/1", None)],
+	];
+	assert_eq!(
+		keys(&chapters::merge_pages(pages.clone())),
+		["/5", "/4", "/3", "/2", "/1"]
+	);
+
+	let mut requested = Vec::new();
+	let result = chapters::fetch_pages(|page| {
+		requested.push(page);
+		Ok::<_, ()>(Novel {
+			chapters: Some(pages[page as usize - 1].clone()),
+			has_more_chapters: Some((page as usize) < pages.len()),
+			..Default::default()
+		})
+	});
+	assert_eq!(keys(&result.unwrap()), ["/5", "/4", "/3", "/2", "/1"]);
+	assert_eq!(requested, [1, 2, 3]);
+
+	// stop when a page doesn't have any new chapters
+	let result = chapters::fetch_pages(|_| {
+		Ok::<_, ()>(Novel {
+			chapters: Some(pages[0].clone()),
+			has_more_chapters: Some(true),
+			..Default::default()
+		})
+	});
+	assert_eq!(keys(&result.unwrap()), ["/5", "/4"]);
+
+	assert_eq!(chapters::fetch_pages(|_| Err("error")), Err("error"));
+}
+
+#[test]
+fn language() {
+	let mut list = vec![chapter("/2", None), chapter("/1", None)];
+	list[1].language = Some("ko".into());
+	chapters::set_language(&mut list, "en");
+	assert_eq!(list[0].language.as_deref(), Some("en"));
+	assert_eq!(list[1].language.as_deref(), Some("ko"));
+}
+
+#[test]
+fn normalize_list() {
+	let title = |key: &str, title: &str| Chapter {
+		key: key.into(),
+		title: Some(title.into()),
+		..Default::default()
+	};
+	let mut list = vec![
+		title("/prologue", "Prologue"),
+		title("/1", "Chapter 1"),
+		title("/2", "Chapter 2"),
+		title("/1", "Chapter 1"),
+		title("/interlude", "Interlude"),
+		title("/4", "Chapter 4"),
+	];
+	chapters::normalize(&mut list);
+	assert_eq!(keys(&list), ["/4", "/interlude", "/2", "/1", "/prologue"]);
+	assert_eq!(
+		numbers(&list),
+		[Some(4.0), Some(3.0), Some(2.0), Some(1.0), None]
+	);
+}
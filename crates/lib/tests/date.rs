@@ -0,0 +1,178 @@
+#![cfg(feature = "helpers")]
+
+use buny::helpers::date::parse_relative_date;
+use paste::paste;
+
+const NOW: i64 = 1751374800;
+const MINUTE: i64 = 60;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+const WEEK: i64 = 7 * DAY;
+const MONTH: i64 = 30 * DAY;
+const YEAR: i64 = 365 * DAY;
+
+macro_rules! relative {
+	($($name:ident($($text:expr => $ago:expr),+ $(,)?))+) => {$(paste! {
+		#[test]
+		fn [<relative_ $name>]() {
+			$(
+				assert_eq!(parse_relative_date($text, NOW), Some(NOW - $ago), "{}", $text);
+			)+
+		}
+	})+};
+}
+
+relative! {
+	english(
+		"Just now" => 0,
+		"today" => 0,
+		"Yesterday" => DAY,
+		"5 seconds ago" => 5,
+		"1 min ago" => MINUTE,
+		"an hour ago" => HOUR,
+		"3 hours ago" => 3 * HOUR,
+		"2 days ago." => 2 * DAY,
+		"a week ago" => WEEK,
+		"last week" => WEEK,
+		"4 months ago" => 4 * MONTH,
+		"1 year ago" => YEAR,
+		"1 hour and 20 minutes ago" => HOUR + 20 * MINUTE,
+		"2 days, 3 hours ago" => 2 * DAY + 3 * HOUR,
+		"3h" => 3 * HOUR,
+		"5d ago" => 5 * DAY,
+		"2mo" => 2 * MONTH,
+	)
+	spanish(
+		"ahora" => 0,
+		"ayer" => DAY,
+		"hace 5 minutos" => 5 * MINUTE,
+		"Hace una hora" => HOUR,
+		"hace 3 días" => 3 * DAY,
+		"hace un mes" => MONTH,
+		"hace 2 años" => 2 * YEAR,
+		"la semana pasada" => WEEK,
+	)
+	portuguese(
+		"agora" => 0,
+		"ontem" => DAY,
+		"há 2 horas" => 2 * HOUR,
+		"há uma semana" => WEEK,
+		"3 dias atrás" => 3 * DAY,
+		"há 1 mês" => MONTH,
+	)
+	french(
+		"à l'instant" => 0,
+		"hier" => DAY,
+		"il y a 10 minutes" => 10 * MINUTE,
+		"il y a une heure" => HOUR,
+		"il y a 2 jours" => 2 * DAY,
+		"il y a 3 mois" => 3 * MONTH,
+		"il y a un an" => YEAR,
+	)
+	indonesian(
+		"baru saja" => 0,
+		"kemarin" => DAY,
+		"5 menit yang lalu" => 5 * MINUTE,
+		"2 jam lalu" => 2 * HOUR,
+		"sehari yang lalu" => DAY,
+		"seminggu lalu" => WEEK,
+		"3 bulan yang lalu" => 3 * MONTH,
+	)
+	vietnamese(
+		"vừa xong" => 0,
+		"hôm qua" => DAY,
+		"5 phút trước" => 5 * MINUTE,
+		"2 giờ trước" => 2 * HOUR,
+		"3 ngày trước" => 3 * DAY,
+		"tuần trước" => WEEK,
+		"1 năm trước" => YEAR,
+	)
+	chinese(
+		"刚刚" => 0,
+		"昨天" => DAY,
+		"前天" => 2 * DAY,
+		"5分钟前" => 5 * MINUTE,
+		"3小时前" => 3 * HOUR,
+		"3天前" => 3 * DAY,
+		"三天前" => 3 * DAY,
+		"两个月前" => 2 * MONTH,
+		"十二小時前" => 12 * HOUR,
+		"一千天前" => 1000 * DAY,
+		"兩萬秒前" => 20000,
+		"1天2小时前" => DAY + 2 * HOUR,
+		"2 周前" => 2 * WEEK,
+		"1年之前" => YEAR,
+	)
+	japanese(
+		"たった今" => 0,
+		"昨日" => DAY,
+		"5分前" => 5 * MINUTE,
+		"3時間前" => 3 * HOUR,
+		"２日前" => 2 * DAY,
+		"2 週間前" => 2 * WEEK,
+		"3ヶ月前" => 3 * MONTH,
+		"1年前" => YEAR,
+	)
+	korean(
+		"방금" => 0,
+		"어제" => DAY,
+		"5분 전" => 5 * MINUTE,
+		"한 시간 전" => HOUR,
+		"3일 전" => 3 * DAY,
+		"2주 전" => 2 * WEEK,
+		"1개월 전" => MONTH,
+		"2년전" => 2 * YEAR,
+	)
+	russian(
+		"только что" => 0,
+		"вчера" => DAY,
+		"5 минут назад" => 5 * MINUTE,
+		"час назад" => HOUR,
+		"2 часа назад" => 2 * HOUR,
+		"3 дня назад" => 3 * DAY,
+		"неделю назад" => WEEK,
+		"1 месяц назад" => MONTH,
+		"5 лет назад" => 5 * YEAR,
+	)
+}
+
+#[test]
+fn absolute_dates() {
+	for text in [
+		"July 1, 2025",
+		"2025-07-01",
+		"01/07/2025",
+		"3 mars 2025",
+		"2025年7月1日",
+		"1 tháng 7, 2025",
+		"7 days",
+		"ago",
+		"",
+	] {
+		assert_eq!(parse_relative_date(text, NOW), None, "{text}");
+	}
+}
+
+#[cfg(feature = "native")]
+#[test]
+fn parser_formats() {
+	use buny::helpers::date::DateParser;
+
+	let parser = DateParser::new()
+		.format("MMMM d, yyyy")
+		.format("dd/MM/yyyy");
+	assert_eq!(parser.parse_from("3 hours ago", NOW), Some(NOW - 3 * HOUR));
+	assert_eq!(parser.parse_from(" July 1, 2025 ", NOW), Some(1751328000));
+	assert_eq!(parser.parse_from("01/07/2025", NOW), Some(1751328000));
+	assert_eq!(parser.parse_from("2025-07-01", NOW), None);
+
+	let parser = DateParser::new()
+		.format("d MMMM yyyy HH:mm")
+		.locale("fr_FR")
+		.timezone("Europe/Paris");
+	assert_eq!(
+		parser.parse_from("il y a 2 jours", NOW),
+		Some(NOW - 2 * DAY)
+	);
+	assert_eq!(parser.parse_from("1 juillet 2025 15:00", NOW), Some(NOW));
+}
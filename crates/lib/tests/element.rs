@@ -0,0 +1,42 @@
+#![cfg(all(feature = "helpers", feature = "native"))]
+
+use buny::{helpers::element::text_with_ruby, imports::html::Html};
+
+fn ruby_text(html: &str) -> String {
+	let document = Html::parse_fragment(html).unwrap();
+	text_with_ruby(&document.select_first("p").unwrap())
+}
+
+#[test]
+fn ruby_annotations() {
+	assert_eq!(
+		ruby_text("<p><ruby>漢<rt>かん</rt>字<rt>じ</rt></ruby>です</p>"),
+		"{漢|かん}{字|じ}です"
+	);
+	// annotations without a base are kept as plain text
+	assert_eq!(ruby_text("<p><ruby><rt>かん</rt></ruby></p>"), "かん");
+}
+
+#[test]
+fn ruby_fallback_parentheses() {
+	assert_eq!(
+		ruby_text("<p><ruby>漢<rp>(</rp><rt>かん</rt><rp>)</rp>字<rp>(</rp><rt>じ</rt><rp>)</rp></ruby></p>"),
+		"{漢|かん}{字|じ}"
+	);
+}
+
+#[test]
+fn ruby_base_elements() {
+	assert_eq!(
+		ruby_text("<p><ruby><rb>東京</rb><rt>とうきょう</rt></ruby>へ</p>"),
+		"{東京|とうきょう}へ"
+	);
+}
+
+#[test]
+fn line_breaks() {
+	assert_eq!(
+		ruby_text("<p>First line <br> <ruby>次<rt>つぎ</rt></ruby>の行</p>"),
+		"First line  \n{次|つぎ}の行"
+	);
+}
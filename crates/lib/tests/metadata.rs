@@ -0,0 +1,165 @@
+#![cfg(feature = "helpers")]
+
+use buny::{
+	helpers::metadata::{parse_status, Metadata},
+	Novel, NovelStatus,
+};
+
+#[test]
+fn meta_tags_priority() {
+	let metadata = Metadata::from_meta_tags([
+		("twitter:title", "Twitter Title"),
+		("og:title", "OpenGraph Title"),
+		("description", "Plain description"),
+		("og:description", "OpenGraph description"),
+		("twitter:image", "https://example.com/twitter.jpg"),
+		("og:image", "https://example.com/og.jpg"),
+	]);
+	assert_eq!(metadata.title, Some("OpenGraph Title".into()));
+	assert_eq!(metadata.description, Some("OpenGraph description".into()));
+	assert_eq!(metadata.cover, Some("https://example.com/og.jpg".into()));
+
+	let metadata = Metadata::from_meta_tags([
+		("og:title", "Chapter 1 - Novel Title"),
+		("og:novel:book_name", "Novel Title"),
+	]);
+	assert_eq!(metadata.title, Some("Novel Title".into()));
+}
+
+#[test]
+fn meta_tags_lists() {
+	let metadata = Metadata::from_meta_tags([
+		("book:author", "https://example.com/author/1"),
+		("og:novel:author", "Author"),
+		("book:author", "Author"),
+		("og:novel:category", "Fantasy, Action"),
+		("book:tag", "Action"),
+		("article:tag", " "),
+		("OG:NOVEL:STATUS", "Ongoing"),
+	]);
+	assert_eq!(metadata.authors, Some(vec!["Author".into()]));
+	assert_eq!(metadata.tags, Some(vec!["Fantasy".into(), "Action".into()]));
+	assert_eq!(metadata.status, NovelStatus::Ongoing);
+
+	let metadata = Metadata::from_meta_tags([("og:title", "")]);
+	assert_eq!(metadata, Metadata::default());
+}
+
+#[test]
+fn status() {
+	assert_eq!(parse_status(" Completed "), NovelStatus::Completed);
+	assert_eq!(parse_status("連載中"), NovelStatus::Ongoing);
+	assert_eq!(parse_status("On Hiatus"), NovelStatus::Hiatus);
+	assert_eq!(parse_status("Dropped"), NovelStatus::Cancelled);
+	assert_eq!(parse_status(""), NovelStatus::Unknown);
+}
+
+#[test]
+fn merge_and_apply() {
+	let mut metadata = Metadata {
+		title: Some("Title".into()),
+		..Default::default()
+	};
+	metadata.merge(Metadata {
+		title: Some("Other Title".into()),
+		description: Some("Description".into()),
+		status: NovelStatus::Completed,
+		..Default::default()
+	});
+	assert_eq!(metadata.title, Some("Title".into()));
+	assert_eq!(metadata.description, Some("Description".into()));
+	assert_eq!(metadata.status, NovelStatus::Completed);
+
+	let mut novel = Novel {
+		key: "1".into(),
+		description: Some("Existing".into()),
+		..Default::default()
+	};
+	metadata.apply_to(&mut novel);
+	assert_eq!(novel.title, "Title");
+	assert_eq!(novel.description, Some("Existing".into()));
+	assert_eq!(novel.status, NovelStatus::Completed);
+}
+
+#[cfg(feature = "json")]
+mod json {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn book() {
+		let metadata = Metadata::from_json_ld(&json!({
+			"@context": "https://schema.org",
+			"@type": ["Book", "Product"],
+			"name": "Novel Title",
+			"author": [
+				{ "@type": "Person", "name": "Author" },
+				"Second Author"
+			],
+			"image": { "@type": "ImageObject", "url": "https://example.com/cover.jpg" },
+			"description": "Description",
+			"genre": ["Fantasy"],
+			"keywords": "Magic, Fantasy",
+			"url": "https://example.com/novel",
+			"creativeWorkStatus": "https://schema.org/Completed"
+		}));
+		assert_eq!(
+			metadata,
+			Metadata {
+				title: Some("Novel Title".into()),
+				authors: Some(vec!["Author".into(), "Second Author".into()]),
+				cover: Some("https://example.com/cover.jpg".into()),
+				description: Some("Description".into()),
+				url: Some("https://example.com/novel".into()),
+				tags: Some(vec!["Fantasy".into(), "Magic".into()]),
+				status: NovelStatus::Completed,
+			}
+		);
+	}
+
+	#[test]
+	fn graph() {
+		let metadata = Metadata::from_json_ld(&json!({
+			"@context": "https://schema.org",
+			"@graph": [
+				{ "@type": "WebSite", "name": "Website" },
+				{
+					"@type": "Chapter",
+					"name": "Chapter 1",
+					"isPartOf": { "@type": "BookSeries", "name": "Novel Title" }
+				}
+			]
+		}));
+		assert_eq!(metadata.title, Some("Novel Title".into()));
+		assert_eq!(metadata.authors, None);
+
+		assert_eq!(Metadata::from_json_ld(&json!("Book")), Metadata::default());
+	}
+
+	#[test]
+	fn image_object() {
+		let metadata = Metadata::from_json_ld(&json!({
+			"@type": "Book",
+			"name": "Novel Title",
+			"image": {
+				"@type": "ImageObject",
+				"name": "Cover caption",
+				"url": "https://example.com/cover.jpg"
+			}
+		}));
+		assert_eq!(metadata.cover, Some("https://example.com/cover.jpg".into()));
+
+		let metadata = Metadata::from_json_ld(&json!({
+			"@type": "Book",
+			"thumbnailUrl": [{
+				"@type": "ImageObject",
+				"caption": "Cover",
+				"contentUrl": "https://example.com/thumbnail.jpg"
+			}]
+		}));
+		assert_eq!(
+			metadata.cover,
+			Some("https://example.com/thumbnail.jpg".into())
+		);
+	}
+}
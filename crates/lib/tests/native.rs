@@ -0,0 +1,328 @@
+#![cfg(feature = "native")]
+
+use buny::{
+	imports::{
+		defaults::{defaults_get, defaults_set, DefaultValue},
+		html::{Html, Node},
+		std::parse_date,
+	},
+	native::{self, with_env},
+};
+
+#[test]
+fn html_select() {
+	let document = Html::parse_with_url(
+		r#"<div class="list"><a href="/one">One</a><a href="/two">Two</a></div>"#,
+		"https://example.com/",
+	)
+	.unwrap();
+
+	let links = document.select("div.list > a").unwrap();
+	assert_eq!(links.count(), 2);
+
+	let first = document.select_first("a").unwrap();
+	assert_eq!(first.text(), Some("One".into()));
+	assert_eq!(
+		first.attr("abs:href"),
+		Some("https://example.com/one".into())
+	);
+	assert!(first.parent().unwrap().has_class("list"));
+}
+
+#[test]
+fn html_child_nodes() {
+	let document = Html::parse_fragment("<p>one<br>two<!-- note --><i>three</i></p>").unwrap();
+	let p = document.select_first("p").unwrap();
+
+	let nodes = p.child_nodes().collect::<Vec<_>>();
+	assert_eq!(nodes.len(), 5);
+	assert!(matches!(nodes[0], Node::Text(_)));
+	assert_eq!(nodes[0].text(), Some("one".into()));
+	assert_eq!(nodes[1].tag_name(), Some("br".into()));
+	assert_eq!(nodes[2].text(), Some("two".into()));
+	assert!(matches!(nodes[3], Node::Comment(_)));
+	assert_eq!(nodes[3].text(), None);
+	assert_eq!(nodes[4].text(), Some("three".into()));
+	assert!(nodes[0].child_nodes().is_none());
+	assert_eq!(nodes[4].child_nodes().map(|nodes| nodes.size()), Some(1));
+	assert_eq!(
+		nodes[2].parent().and_then(|p| p.tag_name()),
+		Some("p".into())
+	);
+}
+
+#[test]
+fn html_sibling_nodes() {
+	let document = Html::parse_fragment("<p>one<br>two<i>three</i></p>").unwrap();
+	let br = document.select_first("br").unwrap();
+
+	let next = br.next_sibling_node().unwrap();
+	assert!(matches!(next, Node::Text(_)));
+	assert_eq!(next.text(), Some("two".into()));
+	assert_eq!(
+		next.next_sibling_node().and_then(|node| node.tag_name()),
+		Some("i".into())
+	);
+	assert_eq!(
+		br.prev_sibling_node().and_then(|node| node.text()),
+		Some("one".into())
+	);
+
+	let i = document.select_first("i").unwrap();
+	assert!(i.next_sibling_node().is_none());
+	let first = br.prev_sibling_node().unwrap();
+	assert!(first.prev_sibling_node().is_none());
+}
+
+#[test]
+fn html_walk() {
+	let document = Html::parse_fragment("<div><p>a<b>b<i>c</i></b>d</p><p>e</p></div>").unwrap();
+	let div = document.select_first("div").unwrap();
+
+	let nodes = div
+		.walk()
+		.map(|node| match node {
+			Node::Element(element) => element.tag_name().unwrap_or_default(),
+			node => node.text().unwrap_or_default(),
+		})
+		.collect::<Vec<_>>();
+	assert_eq!(nodes, ["p", "a", "b", "b", "i", "c", "d", "p", "e"]);
+
+	let walk = div.walk();
+	assert_eq!(walk.size(), 9);
+	assert_eq!(
+		walk.get(4).and_then(|node| node.tag_name()),
+		Some("i".into())
+	);
+	assert!(walk.get(9).is_none());
+	assert_eq!(
+		div.walk().next_back().and_then(|node| node.text()),
+		Some("e".into())
+	);
+}
+
+#[test]
+fn html_escape() {
+	assert_eq!(Html::escape("<b>&</b>"), "&lt;b&gt;&amp;&lt;/b&gt;");
+	assert_eq!(
+		Html::unescape("&lt;b&gt;&amp;&lt;/b&gt;"),
+		Some("<b>&</b>".into())
+	);
+}
+
+#[test]
+fn defaults() {
+	with_env(|env| {
+		env.defaults.set(
+			"url".into(),
+			native::DefaultValue::String("https://example.com".into()),
+		)
+	});
+	assert_eq!(
+		defaults_get::<String>("url"),
+		Some("https://example.com".into())
+	);
+
+	defaults_set("count", DefaultValue::Int(3));
+	assert_eq!(defaults_get::<i32>("count"), Some(3));
+	assert_eq!(defaults_get::<i32>("missing"), None);
+}
+
+#[test]
+fn defaults_are_per_thread() {
+	defaults_set("thread", DefaultValue::Bool(true));
+	let value = std::thread::spawn(|| defaults_get::<bool>("thread"))
+		.join()
+		.unwrap();
+	assert_eq!(value, None);
+}
+
+#[test]
+fn date() {
+	assert_eq!(
+		parse_date("07-01-2025 13:00", "MM-dd-yyyy HH:mm"),
+		Some(1751374800)
+	);
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn mocked_request() {
+	use buny::imports::net::{mock_response, HttpMethod, Request};
+
+	mock_response(
+		Some(HttpMethod::Get),
+		"https://example.com/",
+		200,
+		"<p>mocked</p>",
+	)
+	.unwrap();
+	let document = Request::get("https://example.com/")
+		.unwrap()
+		.html()
+		.unwrap();
+	assert_eq!(
+		document.select_first("p").and_then(|p| p.text()),
+		Some("mocked".into())
+	);
+}
+
+#[test]
+fn system_clock() {
+	let now = buny::imports::std::current_date();
+	assert!((now - chrono::Utc::now().timestamp()).abs() <= 1);
+}
+
+#[test]
+fn virtual_clock() {
+	let mut clock = native::Clock::fixed(1751374800);
+	clock.timezone = Some(chrono_tz::Asia::Tokyo);
+	assert_eq!(clock.utc_offset(), -9 * 60 * 60);
+	assert_eq!(clock.resolve_timezone(Some("current")), Some("Asia/Tokyo"));
+	assert_eq!(clock.resolve_timezone(Some("UTC")), Some("UTC"));
+
+	clock.sleep(60);
+	assert_eq!(clock.now(), 1751374860);
+
+	clock.auto_advance = false;
+	clock.sleep(60);
+	assert_eq!(clock.now(), 1751374860);
+}
+
+#[cfg(feature = "test")]
+#[test]
+fn mocked_clock() {
+	use buny::imports::std::{
+		current_date, get_utc_offset, mock_clock, parse_local_date, sleep, StdError,
+	};
+
+	mock_clock(1751374800, Some("Asia/Tokyo"), true).unwrap();
+	assert_eq!(current_date(), 1751374800);
+	assert_eq!(get_utc_offset(), -9 * 60 * 60);
+	assert_eq!(
+		parse_local_date("2025-07-01 22:00", "yyyy-MM-dd HH:mm"),
+		Some(1751374800)
+	);
+	sleep(3600);
+	assert_eq!(current_date(), 1751378400);
+
+	assert_eq!(
+		mock_clock(0, Some("Mars/Olympus"), false),
+		Err(StdError::InvalidTimezone)
+	);
+}
+
+#[test]
+fn date_formats() {
+	use native::parse_date;
+
+	let parse = |string, format| parse_date(string, format, None, None);
+	assert_eq!(parse("2025-07-01", "yyyy-MM-dd"), Some(1751328000));
+	assert_eq!(parse("20250701", "yyyyMMdd"), Some(1751328000));
+	assert_eq!(parse("1:00 PM", "h:mm a"), Some(946731600));
+	assert_eq!(parse("12:30 AM", "hh:mm a"), Some(946686600));
+	assert_eq!(parse("Jul 1, 2025", "MMM d, yyyy"), Some(1751328000));
+	assert_eq!(
+		parse("Tuesday, July 1, 2025", "EEEE, MMMM d, yyyy"),
+		Some(1751328000)
+	);
+	assert_eq!(parse("July 1 '25", "MMMM d ''yy"), Some(1751328000));
+	assert_eq!(
+		parse("2025-07-01T13:00:00.123Z", "yyyy-MM-dd'T'HH:mm:ss.SSSXXX"),
+		Some(1751374800)
+	);
+	assert_eq!(
+		parse("2025-07-01 22:00 +0900", "yyyy-MM-dd HH:mm Z"),
+		Some(1751374800)
+	);
+	assert_eq!(
+		parse("2025-07-01 22:00 GMT+09:00", "yyyy-MM-dd HH:mm ZZZZ"),
+		Some(1751374800)
+	);
+	assert_eq!(
+		parse("2025-07-01 06:00 PDT", "yyyy-MM-dd HH:mm z"),
+		Some(1751374800)
+	);
+	assert_eq!(
+		parse("2025-07-01 22:00 Asia/Tokyo", "yyyy-MM-dd HH:mm VV"),
+		Some(1751374800)
+	);
+	assert_eq!(parse("2025-182", "yyyy-DDD"), Some(1751328000));
+	assert_eq!(parse("44 BC", "y G"), Some(-63524131200));
+
+	assert_eq!(parse("2025-02-30", "yyyy-MM-dd"), None);
+	assert_eq!(parse("2025-07-01 extra", "yyyy-MM-dd"), None);
+	assert_eq!(parse("2025-07-01", "yyyy-MM-dd'T'"), None);
+	assert_eq!(parse("2025", "yyyy-UUU"), None);
+}
+
+#[test]
+fn date_locales() {
+	use native::parse_date;
+
+	let parse = |string, format, locale| parse_date(string, format, Some(locale), None);
+	assert_eq!(
+		parse("1 juillet 2025", "d MMMM yyyy", "fr_FR"),
+		Some(1751328000)
+	);
+	assert_eq!(
+		parse("1 juil. 2025", "d MMM yyyy", "fr_FR"),
+		Some(1751328000)
+	);
+	assert_eq!(
+		parse("1 juil 2025", "d MMM. yyyy", "fr_FR"),
+		Some(1751328000)
+	);
+	assert_eq!(
+		parse("mardi 1 juillet 2025", "EEEE d MMMM yyyy", "fr"),
+		Some(1751328000)
+	);
+	assert_eq!(
+		parse("1. Juli 2025", "d. MMMM yyyy", "de_DE"),
+		Some(1751328000)
+	);
+	assert_eq!(
+		parse("1 de julio de 2025", "d 'de' MMMM 'de' yyyy", "es_ES"),
+		Some(1751328000)
+	);
+	assert_eq!(
+		parse("1 de julho de 2025", "d 'de' MMMM 'de' yyyy", "pt_BR"),
+		Some(1751328000)
+	);
+	assert_eq!(
+		parse("1 Agustus 2025", "d MMMM yyyy", "id_ID"),
+		Some(1754006400)
+	);
+	assert_eq!(
+		parse("1 июля 2025", "d MMMM yyyy", "ru_RU"),
+		Some(1751328000)
+	);
+	assert_eq!(parse("июль 2025", "LLLL yyyy", "ru"), Some(1751328000));
+	assert_eq!(
+		parse("2025年7月1日", "yyyy年M月d日", "ja_JP"),
+		Some(1751328000)
+	);
+	assert_eq!(
+		parse("2025年7月1日 午後1:00", "yyyy年M月d日 ah:mm", "ja_JP"),
+		Some(1751374800)
+	);
+	assert_eq!(
+		parse("2025年十二月1日", "yyyy年MMMMd日", "zh_Hans_CN"),
+		Some(1764547200)
+	);
+	assert_eq!(
+		parse("2025년 7월 1일 (화)", "yyyy년 MMM d일 (E)", "ko_KR"),
+		Some(1751328000)
+	);
+	assert_eq!(
+		parse("1 tháng 7, 2025", "d MMMM, yyyy", "vi_VN"),
+		Some(1751328000)
+	);
+	assert_eq!(
+		parse("1 Temmuz 2025 Salı", "d MMMM yyyy EEEE", "tr"),
+		Some(1751328000)
+	);
+
+	// names are only read in the language of the locale
+	assert_eq!(parse("1 July 2025", "d MMMM yyyy", "fr_FR"), None);
+}
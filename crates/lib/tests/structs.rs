@@ -0,0 +1,287 @@
+use buny::{
+	postcard, with_struct_version, Chapter, ContentRating, DeepLinkResult, Filter, GroupSetting,
+	HomeComponentValue, Listing, LoginMethod, LoginSetting, MultiSelectFilter, Novel,
+	NovelPageResult, NovelStatus, PageIcon, PageSetting, RangeFilter, Setting, SortFilter,
+	TextFilter, ToggleSetting, TranslationStatus, UpdateStrategy, STRUCT_VERSION,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+fn round_trip<T: Serialize + DeserializeOwned>(value: &T) -> T {
+	let bytes = postcard::to_allocvec(value).expect("failed to encode");
+	postcard::from_bytes(&bytes).expect("failed to decode")
+}
+
+#[test]
+fn filters() {
+	let filters: Vec<Filter> = vec![
+		TextFilter {
+			id: "title".into(),
+			placeholder: Some("Title".into()),
+			..Default::default()
+		}
+		.into(),
+		SortFilter {
+			id: "sort".into(),
+			can_ascend: true,
+			options: vec!["Latest".into(), "Popular".into()],
+			..Default::default()
+		}
+		.into(),
+		MultiSelectFilter {
+			id: "genre".into(),
+			is_genre: true,
+			can_exclude: true,
+			options: vec!["Action".into(), "Fantasy".into()],
+			default_excluded: Some(vec!["Action".into()]),
+			..Default::default()
+		}
+		.into(),
+		Filter::note("A note"),
+		RangeFilter {
+			id: "chapters".into(),
+			min: Some(1.0),
+			decimal: false,
+			..Default::default()
+		}
+		.into(),
+	];
+	assert_eq!(round_trip(&filters), filters);
+}
+
+#[test]
+fn settings() {
+	let settings: Vec<Setting> = vec![
+		GroupSetting {
+			key: "general".into(),
+			title: "General".into(),
+			items: vec![ToggleSetting {
+				key: "nsfw".into(),
+				title: "Show NSFW".into(),
+				default: true,
+				..Default::default()
+			}
+			.into()],
+			..Default::default()
+		}
+		.into(),
+		LoginSetting {
+			key: "login".into(),
+			title: "Log In".into(),
+			method: LoginMethod::Web,
+			url: Some("https://example.com/login".into()),
+			..Default::default()
+		}
+		.into(),
+		PageSetting {
+			key: "page".into(),
+			title: "Page".into(),
+			icon: Some(PageIcon::System {
+				name: "gear".into(),
+				color: "gray".into(),
+				inset: Some(4),
+			}),
+			..Default::default()
+		}
+		.into(),
+	];
+	assert_eq!(round_trip(&settings), settings);
+}
+
+#[test]
+fn deep_link_results() {
+	let results = vec![
+		DeepLinkResult::Novel {
+			key: "/novel".into(),
+		},
+		DeepLinkResult::Chapter {
+			novel_key: "/novel".into(),
+			key: "/novel/1".into(),
+		},
+		DeepLinkResult::Listing(Listing {
+			id: "latest".into(),
+			name: "Latest".into(),
+			..Default::default()
+		}),
+	];
+	assert_eq!(round_trip(&results), results);
+}
+
+#[test]
+fn home_component_value_indices() {
+	// postcard encodes enum variants by index, so new variants need to be added at the end
+	let values = [
+		HomeComponentValue::empty_image_scroller(),
+		HomeComponentValue::empty_details(),
+		HomeComponentValue::empty_scroller(),
+		HomeComponentValue::empty_stack(),
+		HomeComponentValue::empty_vertical(),
+		HomeComponentValue::empty_filters(),
+		HomeComponentValue::empty_links(),
+		HomeComponentValue::empty_chapter_feed(),
+	];
+	for (index, value) in values.iter().enumerate() {
+		let bytes = postcard::to_allocvec(value).expect("failed to encode");
+		assert_eq!(bytes[0] as usize, index, "{value:?}");
+	}
+}
+
+fn novel() -> Novel {
+	Novel {
+		key: "/novel".into(),
+		title: "Title".into(),
+		cover: Some("cover".into()),
+		authors: Some(vec!["Author".into()]),
+		description: Some("Description".into()),
+		url: Some("url".into()),
+		tags: Some(vec!["Tag".into()]),
+		status: NovelStatus::Ongoing,
+		content_rating: ContentRating::Safe,
+		update_strategy: UpdateStrategy::Never,
+		next_update_time: Some(1),
+		chapters: None,
+		has_more_chapters: Some(true),
+		alt_titles: Some(vec!["Alt".into()]),
+		artists: Some(vec!["Artist".into()]),
+		rating: Some(4.5),
+		rating_count: Some(2),
+		views: Some(3),
+		follows: Some(4),
+		word_count: Some(5),
+		chapter_count: Some(6),
+		original_language: Some("ko".into()),
+		translation_status: TranslationStatus::Translated,
+	}
+}
+
+fn chapter() -> Chapter {
+	Chapter {
+		key: "/chapter".into(),
+		title: Some("Title".into()),
+		chapter_number: Some(1.0),
+		volume_number: Some(2.0),
+		date_uploaded: Some(3),
+		scanlators: Some(vec!["Group".into()]),
+		url: Some("url".into()),
+		language: Some("en".into()),
+		thumbnail: Some("thumbnail".into()),
+		locked: true,
+		word_count: Some(4),
+		unlock_cost: Some(5),
+		free_at: Some(6),
+		external_url: Some("external".into()),
+		is_volume_header: true,
+	}
+}
+
+// the struct version is global without the `native` feature, so the tests that depend on it
+// are kept in a single test
+#[test]
+fn struct_versions() {
+	novel_layout();
+	chapter_layout();
+	first_struct_version();
+}
+
+fn novel_layout() {
+	// postcard encodes fields by position, so new fields need to be appended for a new
+	// struct version, after the fields of the previous versions
+	let novel = novel();
+	let original = (
+		&novel.key,
+		&novel.title,
+		&novel.cover,
+		&novel.authors,
+		&novel.description,
+		&novel.url,
+		&novel.tags,
+		novel.status,
+		novel.content_rating,
+		novel.update_strategy,
+		novel.next_update_time,
+		&novel.chapters,
+		novel.has_more_chapters,
+	);
+	let appended = (
+		&novel.alt_titles,
+		&novel.artists,
+		novel.rating,
+		novel.rating_count,
+		novel.views,
+		novel.follows,
+		novel.word_count,
+		novel.chapter_count,
+		&novel.original_language,
+		novel.translation_status,
+	);
+	assert_eq!(STRUCT_VERSION, 1);
+	assert_eq!(
+		with_struct_version(STRUCT_VERSION, || postcard::to_allocvec(&novel)).unwrap(),
+		postcard::to_allocvec(&(original, appended)).unwrap()
+	);
+	assert_eq!(
+		with_struct_version(0, || postcard::to_allocvec(&novel)).unwrap(),
+		postcard::to_allocvec(&original).unwrap()
+	);
+}
+
+fn chapter_layout() {
+	let chapter = chapter();
+	let original = (
+		&chapter.key,
+		&chapter.title,
+		chapter.chapter_number,
+		chapter.volume_number,
+		chapter.date_uploaded,
+		&chapter.scanlators,
+		&chapter.url,
+		&chapter.language,
+		&chapter.thumbnail,
+		chapter.locked,
+	);
+	let appended = (
+		chapter.word_count,
+		chapter.unlock_cost,
+		chapter.free_at,
+		&chapter.external_url,
+		chapter.is_volume_header,
+	);
+	assert_eq!(
+		with_struct_version(STRUCT_VERSION, || postcard::to_allocvec(&chapter)).unwrap(),
+		postcard::to_allocvec(&(original, appended)).unwrap()
+	);
+	assert_eq!(
+		with_struct_version(0, || postcard::to_allocvec(&chapter)).unwrap(),
+		postcard::to_allocvec(&original).unwrap()
+	);
+}
+
+fn first_struct_version() {
+	let page = NovelPageResult {
+		entries: vec![
+			Novel {
+				chapters: Some(vec![chapter(), chapter()]),
+				..novel()
+			},
+			novel(),
+		],
+		has_next_page: true,
+	};
+	assert_eq!(
+		with_struct_version(STRUCT_VERSION, || round_trip(&page)),
+		page
+	);
+
+	// the fields added after the first version are left out, and the rest of the result
+	// is still read from the right position
+	let decoded = with_struct_version(0, || round_trip(&page));
+	assert_eq!(decoded.entries.len(), 2);
+	assert_eq!(decoded.entries[0].key, "/novel");
+	assert_eq!(decoded.entries[0].has_more_chapters, Some(true));
+	assert_eq!(decoded.entries[0].alt_titles, None);
+	let chapters = decoded.entries[0].chapters.as_ref().unwrap();
+	assert_eq!(chapters.len(), 2);
+	assert!(chapters[1].locked);
+	assert_eq!(chapters[1].word_count, None);
+	assert_eq!(decoded.entries[1].title, "Title");
+	assert!(decoded.has_next_page);
+}
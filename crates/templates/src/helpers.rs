@@ -0,0 +1,376 @@
+//! Parsing functions shared between the templates.
+use buny::{
+	alloc::{String, Vec},
+	helpers::{metadata::parse_status, string::PlainText},
+	imports::html::{Document, Element, Node},
+	prelude::*,
+	ContentBlock, HomeComponent, HomeComponentValue, HomeLayout, Listing, Novel, NovelPageResult,
+	NovelStatus, Result,
+};
+
+/// Returns the path and query of a url relative to the base url.
+///
+/// Urls on a different host are returned unchanged.
+///
+/// # Examples
+/// ```
+/// use buny_templates::helpers::url_to_key;
+/// assert_eq!(
+///     url_to_key("https://example.com", "https://example.com/novel/title/"),
+///     "/novel/title/"
+/// );
+/// assert_eq!(url_to_key("https://example.com/", "/novel/title/"), "/novel/title/");
+/// ```
+pub fn url_to_key(base_url: &str, url: &str) -> String {
+	let base_url = base_url.trim_end_matches('/');
+	let url = url.trim();
+	let path = url
+		.strip_prefix(base_url)
+		.or_else(|| {
+			// ignore the scheme if the url uses a different one
+			let (_, host) = base_url.split_once("://")?;
+			let (_, url) = url.split_once("://")?;
+			url.strip_prefix(host)
+		})
+		.unwrap_or(url);
+	// remove any fragment
+	let path = path.split('#').next().unwrap_or_default();
+	if path.starts_with('/') || path.contains("://") {
+		path.into()
+	} else {
+		format!("/{path}")
+	}
+}
+
+/// Returns the absolute url for a key created with [url_to_key].
+///
+/// # Examples
+/// ```
+/// use buny_templates::helpers::key_to_url;
+/// assert_eq!(
+///     key_to_url("https://example.com/", "/novel/title/"),
+///     "https://example.com/novel/title/"
+/// );
+/// ```
+pub fn key_to_url(base_url: &str, key: &str) -> String {
+	if key.contains("://") {
+		key.into()
+	} else {
+		format!("{}{key}", base_url.trim_end_matches('/'))
+	}
+}
+
+/// Returns the non-empty segments of a key's path, excluding the query.
+pub fn path_segments(key: &str) -> Vec<&str> {
+	key.split(['?', '#'])
+		.next()
+		.unwrap_or_default()
+		.split('/')
+		.filter(|segment| !segment.is_empty())
+		.collect()
+}
+
+/// Returns the url of an image element, checking common lazy loading attributes.
+pub fn image_url(element: &Element) -> Option<String> {
+	["data-src", "data-lazy-src", "data-cfsrc", "src"]
+		.iter()
+		.find_map(|attr| {
+			element
+				.attr(format!("abs:{attr}"))
+				.filter(|url| !url.is_empty() && !url.starts_with("data:"))
+		})
+		.or_else(|| {
+			// use the last (usually largest) image in the srcset
+			let srcset = element
+				.attr("data-srcset")
+				.or_else(|| element.attr("srcset"))?;
+			srcset
+				.split(',')
+				.filter_map(|source| source.split_whitespace().next())
+				.next_back()
+				.map(String::from)
+		})
+}
+
+/// Returns the trimmed, non-empty text of the first element matching a selector.
+pub fn select_text(document: &Document, selector: &str) -> Option<String> {
+	if selector.is_empty() {
+		return None;
+	}
+	document
+		.select_first(selector)
+		.and_then(|element| element.text())
+		.filter(|text| !text.is_empty())
+}
+
+/// Returns the trimmed, non-empty texts of all the elements matching a selector.
+pub fn select_texts(document: &Document, selector: &str) -> Option<Vec<String>> {
+	if selector.is_empty() {
+		return None;
+	}
+	let texts = document
+		.select(selector)?
+		.filter_map(|element| element.text())
+		.filter(|text| !text.is_empty())
+		.collect::<Vec<_>>();
+	if texts.is_empty() {
+		None
+	} else {
+		Some(texts)
+	}
+}
+
+/// Returns the elements following a label (e.g. `Author:`) in a list of info rows.
+///
+/// Each row should contain the label text followed by the elements with its values,
+/// which are found with the value selector. If no values match the selector, the
+/// text of the row after the label is used instead.
+pub fn info_values(
+	document: &Document,
+	row_selector: &str,
+	value_selector: &str,
+	label: &str,
+) -> Option<Vec<String>> {
+	let label = label.to_lowercase();
+	let row = document.select(row_selector)?.find(|row| {
+		row.text()
+			.is_some_and(|text| text.to_lowercase().starts_with(&label))
+	})?;
+	let values = row
+		.select(value_selector)
+		.map(|values| {
+			values
+				.filter_map(|value| value.text())
+				.filter(|text| !text.is_empty())
+				.collect::<Vec<_>>()
+		})
+		.filter(|values| !values.is_empty())
+		.or_else(|| {
+			let text = row.text()?;
+			let value = text
+				.split_once(':')
+				.map(|(_, value)| value)
+				.unwrap_or_else(|| text.get(label.len()..).unwrap_or_default())
+				.trim();
+			Some(
+				value
+					.split(',')
+					.map(|value| value.trim())
+					.filter(|value| !value.is_empty())
+					.map(String::from)
+					.collect::<Vec<_>>(),
+			)
+		})?;
+	if values.is_empty() {
+		None
+	} else {
+		Some(values)
+	}
+}
+
+/// Selectors used to parse novel entries from a list.
+#[derive(Clone, Copy, Debug)]
+pub struct EntrySelectors<'a> {
+	/// Selector for each novel entry.
+	pub entry: &'a str,
+	/// Selector for the link to the novel, relative to the entry.
+	pub link: &'a str,
+	/// Selector for the novel title, relative to the entry.
+	///
+	/// If empty, the `title` attribute or text of the link is used.
+	pub title: &'a str,
+	/// Selector for the cover image, relative to the entry.
+	pub cover: &'a str,
+}
+
+/// Parses a list of novel entries from a document.
+pub fn parse_entries(document: &Document, base_url: &str, selectors: EntrySelectors) -> Vec<Novel> {
+	let Some(entries) = document.select(selectors.entry) else {
+		return Vec::new();
+	};
+	entries
+		.filter_map(|entry| {
+			let link = entry.select_first(selectors.link)?;
+			let url = link.attr("abs:href").or_else(|| link.attr("href"))?;
+			let title = if selectors.title.is_empty() {
+				None
+			} else {
+				entry
+					.select_first(selectors.title)
+					.and_then(|title| title.text())
+			}
+			.or_else(|| link.attr("title"))
+			.or_else(|| link.text())
+			.filter(|title| !title.is_empty())?;
+			let cover = if selectors.cover.is_empty() {
+				None
+			} else {
+				entry
+					.select_first(selectors.cover)
+					.and_then(|image| image_url(&image))
+			};
+			Some(Novel {
+				key: url_to_key(base_url, &url),
+				title,
+				cover,
+				url: Some(url),
+				..Default::default()
+			})
+		})
+		.collect()
+}
+
+/// Parses a page of novel entries, using a selector to check for a next page.
+pub fn parse_page(
+	document: &Document,
+	base_url: &str,
+	selectors: EntrySelectors,
+	next_page_selector: &str,
+) -> NovelPageResult {
+	NovelPageResult {
+		entries: parse_entries(document, base_url, selectors),
+		has_next_page: !next_page_selector.is_empty()
+			&& document.select_first(next_page_selector).is_some(),
+	}
+}
+
+/// Parses a status label, using [parse_status] with some additional English labels.
+pub fn parse_status_label(label: &str) -> NovelStatus {
+	match parse_status(label) {
+		NovelStatus::Unknown => {
+			let label = label.to_lowercase();
+			if label.contains("ongoing") || label.contains("on going") {
+				NovelStatus::Ongoing
+			} else if label.contains("complete") || label.contains("finished") {
+				NovelStatus::Completed
+			} else if label.contains("hiatus") {
+				NovelStatus::Hiatus
+			} else if label.contains("cancel") || label.contains("dropped") {
+				NovelStatus::Cancelled
+			} else {
+				NovelStatus::Unknown
+			}
+		}
+		status => status,
+	}
+}
+
+/// Returns the lines of text in an element.
+///
+/// Lines are separated by `<br>` tags and block elements such as `<p>` and `<div>`.
+/// Whitespace is trimmed and empty lines are removed.
+pub fn text_lines(element: &Element) -> Vec<String> {
+	const BLOCK_TAGS: &[&str] = &[
+		"p",
+		"div",
+		"li",
+		"ul",
+		"ol",
+		"h1",
+		"h2",
+		"h3",
+		"h4",
+		"h5",
+		"h6",
+		"blockquote",
+		"hr",
+		"table",
+		"tr",
+		"section",
+		"article",
+	];
+
+	fn flush(lines: &mut Vec<String>, line: &mut String) {
+		let text = line.split_whitespace().collect::<Vec<_>>().join(" ");
+		if !text.is_empty() {
+			lines.push(text);
+		}
+		line.clear();
+	}
+
+	fn visit(element: &Element, lines: &mut Vec<String>, line: &mut String) {
+		for node in element.child_nodes() {
+			match node {
+				Node::Text(text) => line.push_str(&text.text().unwrap_or_default()),
+				Node::Element(child) => {
+					let tag = child.tag_name().unwrap_or_default();
+					match tag.as_str() {
+						"br" => flush(lines, line),
+						"script" | "style" | "noscript" => {}
+						tag if BLOCK_TAGS.contains(&tag) => {
+							flush(lines, line);
+							visit(&child, lines, line);
+							flush(lines, line);
+						}
+						_ => visit(&child, lines, line),
+					}
+				}
+				Node::Comment(_) => {}
+			}
+		}
+	}
+
+	let mut lines = Vec::new();
+	let mut line = String::new();
+	visit(element, &mut lines, &mut line);
+	flush(&mut lines, &mut line);
+	lines
+}
+
+/// Returns the lines of text in the first element matching a selector, joined by newlines.
+pub fn select_multiline_text(document: &Document, selector: &str) -> Option<String> {
+	if selector.is_empty() {
+		return None;
+	}
+	let lines = text_lines(&document.select_first(selector)?);
+	if lines.is_empty() {
+		None
+	} else {
+		Some(lines.join("\n"))
+	}
+}
+
+/// Parses the content of a chapter from the lines of text inside an element.
+pub fn parse_content(document: &Document, selector: &str) -> Vec<ContentBlock> {
+	let Some(container) = document.select_first(selector) else {
+		return Vec::new();
+	};
+	text_lines(&container)
+		.into_iter()
+		.map(|text| ContentBlock::paragraph(text.escape_markdown(), None))
+		.collect()
+}
+
+/// Creates a home layout with a scroller for the first page of each listing.
+///
+/// Listings that fail to load or have no entries are skipped.
+pub fn home_from_listings<F>(listings: &[(&str, &str)], get_novel_list: F) -> Result<HomeLayout>
+where
+	F: Fn(Listing) -> Result<NovelPageResult>,
+{
+	let components = listings
+		.iter()
+		.filter_map(|(id, name)| {
+			let listing = Listing {
+				id: (*id).into(),
+				name: (*name).into(),
+				..Default::default()
+			};
+			let entries = get_novel_list(listing.clone()).ok()?.entries;
+			if entries.is_empty() {
+				return None;
+			}
+			Some(HomeComponent {
+				title: Some((*name).into()),
+				subtitle: None,
+				value: HomeComponentValue::Scroller {
+					entries,
+					auto_scroll_interval: None,
+					listing: Some(listing),
+					size: 1,
+				},
+			})
+		})
+		.collect();
+	Ok(HomeLayout { components })
+}
@@ -0,0 +1,7 @@
+#![doc = include_str!("../README.md")]
+#![no_std]
+
+pub mod helpers;
+pub mod lightnovelwp;
+pub mod madara;
+pub mod novelfull;
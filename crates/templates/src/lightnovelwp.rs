@@ -0,0 +1,349 @@
+//! Template for WordPress sites using the LightNovelWP theme.
+//!
+//! Novel keys are the url paths of series pages (e.g. `/series/title/`), and chapter keys
+//! are the url paths of chapter pages (e.g. `/title-chapter-1/`).
+//!
+//! The listing ids supported by default are the values of the series list `order`
+//! query parameter: `update`, `popular`, `latest`, `rating`, and `title`.
+use crate::helpers::{self, EntrySelectors};
+use buny::{
+	alloc::{string::ToString, String, Vec},
+	helpers::{chapter::ChapterTitle, metadata::Metadata, uri::QueryParameters},
+	imports::{html::Document, net::Request, std::parse_date},
+	prelude::*,
+	Chapter, ContentBlock, DeepLinkHandler, DeepLinkResult, FilterValue, Home, HomeLayout, Listing,
+	ListingProvider, Novel, NovelPageResult, Result, Source,
+};
+
+/// Configuration for a LightNovelWP source.
+pub struct Params {
+	/// Base url of the website.
+	pub base_url: String,
+	/// Path that series pages are under, e.g. `series` for `/series/title/`.
+	pub series_path: &'static str,
+	/// Selectors for search result and listing entries.
+	pub entry_selectors: EntrySelectors<'static>,
+	/// Selector that matches when there is a next page of results.
+	pub next_page_selector: &'static str,
+	pub title_selector: &'static str,
+	pub cover_selector: &'static str,
+	pub author_selector: &'static str,
+	pub description_selector: &'static str,
+	pub tag_selector: &'static str,
+	pub status_selector: &'static str,
+	pub chapter_selector: &'static str,
+	/// Selector for the chapter title, relative to the chapter element.
+	pub chapter_title_selector: &'static str,
+	/// Selector for the chapter number, relative to the chapter element.
+	pub chapter_number_selector: &'static str,
+	/// Selector for the chapter date, relative to the chapter element.
+	pub chapter_date_selector: &'static str,
+	/// Selector for the chapter price, relative to the chapter element.
+	///
+	/// Chapters with a price other than `Free` are marked as locked.
+	pub chapter_price_selector: &'static str,
+	pub content_selector: &'static str,
+	/// Selector for the link to the series on a chapter page, used for deep links.
+	pub series_link_selector: &'static str,
+	/// Format of chapter dates, for [parse_date].
+	pub date_format: &'static str,
+	/// Listings shown on the home page, as pairs of listing ids and titles.
+	pub home_listings: &'static [(&'static str, &'static str)],
+}
+
+impl Default for Params {
+	fn default() -> Self {
+		Self {
+			base_url: String::new(),
+			series_path: "series",
+			entry_selectors: EntrySelectors {
+				entry: "div.listupd article",
+				link: "a",
+				title: ".ntitle, .tt, h2",
+				cover: "img",
+			},
+			next_page_selector: ".hpage a.r, .pagination a.next",
+			title_selector: "h1.entry-title",
+			cover_selector: ".sertothumb img, .thumb img",
+			author_selector: ".sertoauth .serval a, .serl:nth-child(3) .serval a",
+			description_selector: ".sersys, .entry-content[itemprop=description]",
+			tag_selector: ".sertogenre a, .genxed a",
+			status_selector: ".sertostat span, .spe span:first-child",
+			chapter_selector: ".eplister li",
+			chapter_title_selector: ".epl-title",
+			chapter_number_selector: ".epl-num",
+			chapter_date_selector: ".epl-date",
+			chapter_price_selector: ".epl-price",
+			content_selector: ".epcontent, #chapter-content",
+			series_link_selector: ".allc a, .ts-breadcrumb li:nth-child(2) a",
+			date_format: "MMMM d, yyyy",
+			home_listings: &[("popular", "Popular"), ("update", "Latest Updates")],
+		}
+	}
+}
+
+/// A source using the LightNovelWP template.
+pub struct LightNovelWP<T: Impl> {
+	inner: T,
+	params: Params,
+}
+
+/// The implementation of a LightNovelWP source.
+///
+/// Only [Impl::new] and [Impl::params] are required; the other functions can be
+/// overridden to handle differences in a website.
+pub trait Impl {
+	fn new() -> Self;
+
+	fn params(&self) -> Params;
+
+	/// Creates a request for a page of the website.
+	fn request(&self, params: &Params, url: &str) -> Result<Request> {
+		Ok(Request::get(url)?.header("Referer", params.base_url.as_str()))
+	}
+
+	fn search_url(
+		&self,
+		params: &Params,
+		query: Option<&str>,
+		page: i32,
+		filters: &[FilterValue],
+	) -> String {
+		let base_url = params.base_url.trim_end_matches('/');
+		if let Some(query) = query.filter(|query| !query.is_empty()) {
+			let mut qs = QueryParameters::new();
+			qs.push("s", Some(query));
+			return format!("{base_url}/page/{page}/?{qs}");
+		}
+		// without a query, filters are applied to the series list
+		let mut qs = QueryParameters::new();
+		qs.push("page", Some(&page.to_string()));
+		for filter in filters {
+			match filter {
+				FilterValue::MultiSelect { id, included, .. } if id == "genre" => {
+					for genre in included {
+						qs.push("genre[]", Some(genre));
+					}
+				}
+				FilterValue::Select { id, value } if id == "status" || id == "type" => {
+					qs.push(id, Some(value));
+				}
+				_ => {}
+			}
+		}
+		format!("{base_url}/{}/?{qs}", params.series_path)
+	}
+
+	fn listing_url(&self, params: &Params, listing: &Listing, page: i32) -> String {
+		format!(
+			"{}/{}/?page={page}&order={}",
+			params.base_url.trim_end_matches('/'),
+			params.series_path,
+			listing.id
+		)
+	}
+
+	fn novel_url(&self, params: &Params, key: &str) -> String {
+		helpers::key_to_url(&params.base_url, key)
+	}
+
+	fn chapter_url(&self, params: &Params, _novel_key: &str, key: &str) -> String {
+		helpers::key_to_url(&params.base_url, key)
+	}
+
+	fn get_search_novel_list(
+		&self,
+		params: &Params,
+		query: Option<String>,
+		page: i32,
+		filters: Vec<FilterValue>,
+	) -> Result<NovelPageResult> {
+		let url = self.search_url(params, query.as_deref(), page, &filters);
+		let html = self.request(params, &url)?.html()?;
+		Ok(helpers::parse_page(
+			&html,
+			&params.base_url,
+			params.entry_selectors,
+			params.next_page_selector,
+		))
+	}
+
+	fn get_novel_list(
+		&self,
+		params: &Params,
+		listing: Listing,
+		page: i32,
+	) -> Result<NovelPageResult> {
+		let url = self.listing_url(params, &listing, page);
+		let html = self.request(params, &url)?.html()?;
+		Ok(helpers::parse_page(
+			&html,
+			&params.base_url,
+			params.entry_selectors,
+			params.next_page_selector,
+		))
+	}
+
+	fn get_novel_update(
+		&self,
+		params: &Params,
+		mut novel: Novel,
+		needs_details: bool,
+		needs_chapters: bool,
+		_page: i32,
+	) -> Result<Novel> {
+		let url = self.novel_url(params, &novel.key);
+		let html = self.request(params, &url)?.html()?;
+		if needs_details {
+			self.parse_novel_details(params, &html, &mut novel);
+			novel.url = Some(url);
+		}
+		if needs_chapters {
+			novel.chapters = Some(self.parse_chapter_list(params, &html));
+		}
+		Ok(novel)
+	}
+
+	fn parse_novel_details(&self, params: &Params, html: &Document, novel: &mut Novel) {
+		if let Some(title) = helpers::select_text(html, params.title_selector) {
+			novel.title = title;
+		}
+		novel.cover = html
+			.select_first(params.cover_selector)
+			.and_then(|image| helpers::image_url(&image))
+			.or(novel.cover.take());
+		novel.authors = helpers::select_texts(html, params.author_selector);
+		novel.description = helpers::select_multiline_text(html, params.description_selector);
+		novel.tags = helpers::select_texts(html, params.tag_selector);
+		novel.status = helpers::select_text(html, params.status_selector)
+			.map(|status| helpers::parse_status_label(&status))
+			.unwrap_or_default();
+		Metadata::from_document(html).apply_to(novel);
+	}
+
+	fn parse_chapter_list(&self, params: &Params, html: &Document) -> Vec<Chapter> {
+		let Some(chapters) = html.select(params.chapter_selector) else {
+			return Vec::new();
+		};
+		chapters
+			.filter_map(|element| {
+				let link = element.select_first("a")?;
+				let url = link.attr("abs:href").or_else(|| link.attr("href"))?;
+				let text = |selector: &str| {
+					element
+						.select_first(selector)
+						.and_then(|element| element.text())
+						.filter(|text| !text.is_empty())
+				};
+				let title = text(params.chapter_title_selector).or_else(|| link.text());
+				let chapter_number = text(params.chapter_number_selector)
+					.or_else(|| title.clone())
+					.and_then(|number| ChapterTitle::parse(number).chapter_number);
+				Some(Chapter {
+					key: helpers::url_to_key(&params.base_url, &url),
+					title,
+					chapter_number,
+					date_uploaded: text(params.chapter_date_selector)
+						.and_then(|date| parse_date(date, params.date_format)),
+					url: Some(url),
+					locked: text(params.chapter_price_selector)
+						.is_some_and(|price| !price.eq_ignore_ascii_case("free")),
+					..Default::default()
+				})
+			})
+			.collect()
+	}
+
+	fn get_chapter_content_list(
+		&self,
+		params: &Params,
+		novel: Novel,
+		chapter: Chapter,
+	) -> Result<Vec<ContentBlock>> {
+		let url = self.chapter_url(params, &novel.key, &chapter.key);
+		let html = self.request(params, &url)?.html()?;
+		Ok(helpers::parse_content(&html, params.content_selector))
+	}
+
+	fn get_home(&self, params: &Params) -> Result<HomeLayout> {
+		helpers::home_from_listings(params.home_listings, |listing| {
+			self.get_novel_list(params, listing, 1)
+		})
+	}
+
+	fn handle_deep_link(&self, params: &Params, url: String) -> Result<Option<DeepLinkResult>> {
+		let key = helpers::url_to_key(&params.base_url, &url);
+		let segments = helpers::path_segments(&key);
+		match segments.as_slice() {
+			[path, novel, ..] if *path == params.series_path => Ok(Some(DeepLinkResult::Novel {
+				key: format!("/{path}/{novel}/"),
+			})),
+			[_] => {
+				// chapter urls don't include the series, so it's found from the chapter page
+				let html = self.request(params, &url)?.html()?;
+				let novel_key = html
+					.select_first(params.series_link_selector)
+					.and_then(|link| link.attr("abs:href"))
+					.map(|url| helpers::url_to_key(&params.base_url, &url));
+				Ok(novel_key.map(|novel_key| DeepLinkResult::Chapter { novel_key, key }))
+			}
+			_ => Ok(None),
+		}
+	}
+}
+
+impl<T: Impl> Source for LightNovelWP<T> {
+	fn new() -> Self {
+		let inner = T::new();
+		let params = inner.params();
+		Self { inner, params }
+	}
+
+	fn get_search_novel_list(
+		&self,
+		query: Option<String>,
+		page: i32,
+		filters: Vec<FilterValue>,
+	) -> Result<NovelPageResult> {
+		self.inner
+			.get_search_novel_list(&self.params, query, page, filters)
+	}
+
+	fn get_novel_update(
+		&self,
+		novel: Novel,
+		needs_details: bool,
+		needs_chapters: bool,
+		page: i32,
+	) -> Result<Novel> {
+		self.inner
+			.get_novel_update(&self.params, novel, needs_details, needs_chapters, page)
+	}
+
+	fn get_chapter_content_list(
+		&self,
+		novel: Novel,
+		chapter: Chapter,
+	) -> Result<Vec<ContentBlock>> {
+		self.inner
+			.get_chapter_content_list(&self.params, novel, chapter)
+	}
+}
+
+impl<T: Impl> ListingProvider for LightNovelWP<T> {
+	fn get_novel_list(&self, listing: Listing, page: i32) -> Result<NovelPageResult> {
+		self.inner.get_novel_list(&self.params, listing, page)
+	}
+}
+
+impl<T: Impl> Home for LightNovelWP<T> {
+	fn get_home(&self) -> Result<HomeLayout> {
+		self.inner.get_home(&self.params)
+	}
+}
+
+impl<T: Impl> DeepLinkHandler for LightNovelWP<T> {
+	fn handle_deep_link(&self, url: String) -> Result<Option<DeepLinkResult>> {
+		self.inner.handle_deep_link(&self.params, url)
+	}
+}
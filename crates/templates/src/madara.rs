@@ -0,0 +1,395 @@
+//! Template for WordPress sites using the Madara theme.
+//!
+//! Novel keys are the url paths of novel pages (e.g. `/novel/title/`), and chapter keys
+//! are the url paths of chapter pages (e.g. `/novel/title/chapter-1/`).
+//!
+//! The listing ids supported by default are the values of Madara's `m_orderby` query
+//! parameter: `latest`, `trending`, `views`, `new-manga`, `rating`, and `alphabet`.
+use crate::helpers::{self, EntrySelectors};
+use buny::{
+	alloc::{String, Vec},
+	helpers::{chapter::ChapterTitle, metadata::Metadata, uri::QueryParameters},
+	imports::{
+		html::Document,
+		net::Request,
+		std::{current_date, parse_date},
+	},
+	prelude::*,
+	Chapter, ContentBlock, DeepLinkHandler, DeepLinkResult, FilterValue, Home, HomeLayout, Listing,
+	ListingProvider, Novel, NovelPageResult, Result, Source,
+};
+
+/// Configuration for a Madara source.
+pub struct Params {
+	/// Base url of the website.
+	pub base_url: String,
+	/// Path that novel pages are under, e.g. `novel` for `/novel/title/`.
+	pub novel_path: &'static str,
+	/// Value of the `post_type` search parameter.
+	pub post_type: &'static str,
+	/// Whether chapters are loaded from the `ajax/chapters/` endpoint used by newer
+	/// versions of the theme, instead of `wp-admin/admin-ajax.php`.
+	pub use_new_chapter_endpoint: bool,
+	/// Selectors for search result entries.
+	pub search_selectors: EntrySelectors<'static>,
+	/// Selectors for listing entries.
+	pub listing_selectors: EntrySelectors<'static>,
+	/// Selector that matches when there is a next page of results.
+	pub next_page_selector: &'static str,
+	pub title_selector: &'static str,
+	pub cover_selector: &'static str,
+	pub author_selector: &'static str,
+	pub description_selector: &'static str,
+	pub tag_selector: &'static str,
+	pub status_selector: &'static str,
+	/// Selector for the element holding the novel id used by the chapter endpoint.
+	pub novel_id_selector: &'static str,
+	pub chapter_selector: &'static str,
+	/// Selector for the chapter date, relative to the chapter element.
+	pub chapter_date_selector: &'static str,
+	pub content_selector: &'static str,
+	/// Format of chapter dates, for [parse_date].
+	pub date_format: &'static str,
+	/// Listings shown on the home page, as pairs of listing ids and titles.
+	pub home_listings: &'static [(&'static str, &'static str)],
+}
+
+impl Default for Params {
+	fn default() -> Self {
+		Self {
+			base_url: String::new(),
+			novel_path: "novel",
+			post_type: "wp-manga",
+			use_new_chapter_endpoint: true,
+			search_selectors: EntrySelectors {
+				entry: "div.c-tabs-item__content",
+				link: ".post-title a",
+				title: "",
+				cover: "img",
+			},
+			listing_selectors: EntrySelectors {
+				entry: "div.page-item-detail",
+				link: ".post-title a",
+				title: "",
+				cover: "img",
+			},
+			next_page_selector: ".nav-previous a, a.nextpostslink, .wp-pagenavi a.next",
+			title_selector: ".post-title h1",
+			cover_selector: ".summary_image img",
+			author_selector: ".author-content a",
+			description_selector: ".description-summary .summary__content, .manga-excerpt",
+			tag_selector: ".genres-content a",
+			status_selector: ".post-status .summary-content",
+			novel_id_selector: "#manga-chapters-holder",
+			chapter_selector: "li.wp-manga-chapter",
+			chapter_date_selector: ".chapter-release-date",
+			content_selector: ".reading-content .text-left, .reading-content",
+			date_format: "MMMM d, yyyy",
+			home_listings: &[("trending", "Trending"), ("latest", "Latest Updates")],
+		}
+	}
+}
+
+/// A source using the Madara template.
+pub struct Madara<T: Impl> {
+	inner: T,
+	params: Params,
+}
+
+/// The implementation of a Madara source.
+///
+/// Only [Impl::new] and [Impl::params] are required; the other functions can be
+/// overridden to handle differences in a website.
+pub trait Impl {
+	fn new() -> Self;
+
+	fn params(&self) -> Params;
+
+	/// Creates a request for a page of the website.
+	fn request(&self, params: &Params, url: &str) -> Result<Request> {
+		Ok(Request::get(url)?.header("Referer", params.base_url.as_str()))
+	}
+
+	fn search_url(
+		&self,
+		params: &Params,
+		query: Option<&str>,
+		page: i32,
+		filters: &[FilterValue],
+	) -> String {
+		let mut qs = QueryParameters::new();
+		qs.push("s", Some(query.unwrap_or_default()));
+		qs.push("post_type", Some(params.post_type));
+		for filter in filters {
+			match filter {
+				FilterValue::MultiSelect { id, included, .. } if id == "genre" => {
+					for genre in included {
+						qs.push("genre[]", Some(genre));
+					}
+				}
+				FilterValue::Select { id, value } if id == "status" => {
+					qs.push("status[]", Some(value));
+				}
+				FilterValue::Text { id, value } if id == "author" => {
+					qs.push("author", Some(value));
+				}
+				_ => {}
+			}
+		}
+		format!(
+			"{}/page/{page}/?{qs}",
+			params.base_url.trim_end_matches('/')
+		)
+	}
+
+	fn listing_url(&self, params: &Params, listing: &Listing, page: i32) -> String {
+		format!(
+			"{}/{}/page/{page}/?m_orderby={}",
+			params.base_url.trim_end_matches('/'),
+			params.novel_path,
+			listing.id
+		)
+	}
+
+	fn novel_url(&self, params: &Params, key: &str) -> String {
+		helpers::key_to_url(&params.base_url, key)
+	}
+
+	fn chapter_url(&self, params: &Params, _novel_key: &str, key: &str) -> String {
+		helpers::key_to_url(&params.base_url, key)
+	}
+
+	fn get_search_novel_list(
+		&self,
+		params: &Params,
+		query: Option<String>,
+		page: i32,
+		filters: Vec<FilterValue>,
+	) -> Result<NovelPageResult> {
+		let url = self.search_url(params, query.as_deref(), page, &filters);
+		let html = self.request(params, &url)?.html()?;
+		Ok(helpers::parse_page(
+			&html,
+			&params.base_url,
+			params.search_selectors,
+			params.next_page_selector,
+		))
+	}
+
+	fn get_novel_list(
+		&self,
+		params: &Params,
+		listing: Listing,
+		page: i32,
+	) -> Result<NovelPageResult> {
+		let url = self.listing_url(params, &listing, page);
+		let html = self.request(params, &url)?.html()?;
+		Ok(helpers::parse_page(
+			&html,
+			&params.base_url,
+			params.listing_selectors,
+			params.next_page_selector,
+		))
+	}
+
+	fn get_novel_update(
+		&self,
+		params: &Params,
+		mut novel: Novel,
+		needs_details: bool,
+		needs_chapters: bool,
+		_page: i32,
+	) -> Result<Novel> {
+		let url = self.novel_url(params, &novel.key);
+		let html = self.request(params, &url)?.html()?;
+		if needs_details {
+			self.parse_novel_details(params, &html, &mut novel);
+			novel.url = Some(url);
+		}
+		if needs_chapters {
+			novel.chapters = Some(self.get_chapter_list(params, &novel, &html)?);
+		}
+		Ok(novel)
+	}
+
+	fn parse_novel_details(&self, params: &Params, html: &Document, novel: &mut Novel) {
+		if let Some(title) = helpers::select_text(html, params.title_selector) {
+			novel.title = title;
+		}
+		novel.cover = html
+			.select_first(params.cover_selector)
+			.and_then(|image| helpers::image_url(&image))
+			.or(novel.cover.take());
+		novel.authors = helpers::select_texts(html, params.author_selector);
+		novel.description = helpers::select_multiline_text(html, params.description_selector);
+		novel.tags = helpers::select_texts(html, params.tag_selector);
+		novel.status = html
+			.select(params.status_selector)
+			.and_then(|mut statuses| statuses.next_back())
+			.and_then(|status| status.text())
+			.map(|status| helpers::parse_status_label(&status))
+			.unwrap_or_default();
+		Metadata::from_document(html).apply_to(novel);
+	}
+
+	/// Fetches the chapters of a novel, given the document of the novel page.
+	fn get_chapter_list(
+		&self,
+		params: &Params,
+		novel: &Novel,
+		html: &Document,
+	) -> Result<Vec<Chapter>> {
+		// some sites include the chapters in the novel page
+		if html.select_first(params.chapter_selector).is_some() {
+			return Ok(self.parse_chapter_list(params, html));
+		}
+		let request = if params.use_new_chapter_endpoint {
+			let url = self.novel_url(params, &novel.key);
+			Request::post(format!("{}/ajax/chapters/", url.trim_end_matches('/')))?
+		} else {
+			let id = html
+				.select_first(params.novel_id_selector)
+				.and_then(|element| element.attr("data-id"))
+				.unwrap_or_default();
+			Request::post(format!(
+				"{}/wp-admin/admin-ajax.php",
+				params.base_url.trim_end_matches('/')
+			))?
+			.header("Content-Type", "application/x-www-form-urlencoded")
+			.body(format!("action=manga_get_chapters&manga={id}"))
+		};
+		let html = request
+			.header("Referer", params.base_url.as_str())
+			.header("X-Requested-With", "XMLHttpRequest")
+			.html()?;
+		Ok(self.parse_chapter_list(params, &html))
+	}
+
+	fn parse_chapter_list(&self, params: &Params, html: &Document) -> Vec<Chapter> {
+		let Some(chapters) = html.select(params.chapter_selector) else {
+			return Vec::new();
+		};
+		chapters
+			.filter_map(|element| {
+				let link = element.select_first("a")?;
+				let url = link.attr("abs:href").or_else(|| link.attr("href"))?;
+				let title = link.text().filter(|title| !title.is_empty());
+				let date_uploaded = element
+					.select_first(params.chapter_date_selector)
+					.and_then(|date| date.text())
+					.and_then(|date| {
+						if date.is_empty() {
+							// new chapters show an image instead of a date
+							Some(current_date())
+						} else {
+							parse_date(date, params.date_format)
+						}
+					});
+				let numbers = title
+					.as_deref()
+					.map(ChapterTitle::parse)
+					.unwrap_or_default();
+				Some(Chapter {
+					key: helpers::url_to_key(&params.base_url, &url),
+					chapter_number: numbers.chapter_number,
+					volume_number: numbers.volume_number,
+					title,
+					date_uploaded,
+					url: Some(url),
+					locked: element.has_class("premium-block"),
+					..Default::default()
+				})
+			})
+			.collect()
+	}
+
+	fn get_chapter_content_list(
+		&self,
+		params: &Params,
+		novel: Novel,
+		chapter: Chapter,
+	) -> Result<Vec<ContentBlock>> {
+		let url = self.chapter_url(params, &novel.key, &chapter.key);
+		let html = self.request(params, &url)?.html()?;
+		Ok(helpers::parse_content(&html, params.content_selector))
+	}
+
+	fn get_home(&self, params: &Params) -> Result<HomeLayout> {
+		helpers::home_from_listings(params.home_listings, |listing| {
+			self.get_novel_list(params, listing, 1)
+		})
+	}
+
+	fn handle_deep_link(&self, params: &Params, url: String) -> Result<Option<DeepLinkResult>> {
+		let key = helpers::url_to_key(&params.base_url, &url);
+		let segments = helpers::path_segments(&key);
+		match segments.as_slice() {
+			[path, novel] if *path == params.novel_path => Ok(Some(DeepLinkResult::Novel {
+				key: format!("/{path}/{novel}/"),
+			})),
+			[path, novel, _, ..] if *path == params.novel_path => {
+				Ok(Some(DeepLinkResult::Chapter {
+					novel_key: format!("/{path}/{novel}/"),
+					key,
+				}))
+			}
+			_ => Ok(None),
+		}
+	}
+}
+
+impl<T: Impl> Source for Madara<T> {
+	fn new() -> Self {
+		let inner = T::new();
+		let params = inner.params();
+		Self { inner, params }
+	}
+
+	fn get_search_novel_list(
+		&self,
+		query: Option<String>,
+		page: i32,
+		filters: Vec<FilterValue>,
+	) -> Result<NovelPageResult> {
+		self.inner
+			.get_search_novel_list(&self.params, query, page, filters)
+	}
+
+	fn get_novel_update(
+		&self,
+		novel: Novel,
+		needs_details: bool,
+		needs_chapters: bool,
+		page: i32,
+	) -> Result<Novel> {
+		self.inner
+			.get_novel_update(&self.params, novel, needs_details, needs_chapters, page)
+	}
+
+	fn get_chapter_content_list(
+		&self,
+		novel: Novel,
+		chapter: Chapter,
+	) -> Result<Vec<ContentBlock>> {
+		self.inner
+			.get_chapter_content_list(&self.params, novel, chapter)
+	}
+}
+
+impl<T: Impl> ListingProvider for Madara<T> {
+	fn get_novel_list(&self, listing: Listing, page: i32) -> Result<NovelPageResult> {
+		self.inner.get_novel_list(&self.params, listing, page)
+	}
+}
+
+impl<T: Impl> Home for Madara<T> {
+	fn get_home(&self) -> Result<HomeLayout> {
+		self.inner.get_home(&self.params)
+	}
+}
+
+impl<T: Impl> DeepLinkHandler for Madara<T> {
+	fn handle_deep_link(&self, url: String) -> Result<Option<DeepLinkResult>> {
+		self.inner.handle_deep_link(&self.params, url)
+	}
+}
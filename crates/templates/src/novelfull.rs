@@ -0,0 +1,385 @@
+//! Template for sites based on NovelFull or ReadNovelFull.
+//!
+//! Novel keys are the url paths of novel pages (e.g. `/title.html`), and chapter keys
+//! are the url paths of chapter pages (e.g. `/title/chapter-1.html`).
+//!
+//! The listing ids supported by default are the novel list paths: `latest-release-novel`,
+//! `hot-novel`, `completed-novel`, and `most-popular`. ReadNovelFull sites can start from
+//! [Params::read_novel_full], which uses `most-popular-novel` instead.
+use crate::helpers::{self, EntrySelectors};
+use buny::{
+	alloc::{String, Vec},
+	helpers::{chapter::ChapterTitle, metadata::Metadata, uri::QueryParameters},
+	imports::{html::Document, net::Request},
+	prelude::*,
+	BunyError, Chapter, ContentBlock, DeepLinkHandler, DeepLinkResult, FilterValue, Home,
+	HomeLayout, Listing, ListingProvider, Novel, NovelPageResult, Result, Source,
+};
+
+/// Configuration for a NovelFull source.
+pub struct Params {
+	/// Base url of the website.
+	pub base_url: String,
+	/// Path of the search page.
+	pub search_path: &'static str,
+	/// Path that listing pages are under, if any.
+	pub listing_path: &'static str,
+	/// Path of the chapter list endpoint, which is given the novel id.
+	pub chapter_list_path: &'static str,
+	/// Selectors for search result and listing entries.
+	pub entry_selectors: EntrySelectors<'static>,
+	/// Selector that matches when there is a next page of results.
+	pub next_page_selector: &'static str,
+	pub title_selector: &'static str,
+	pub cover_selector: &'static str,
+	pub description_selector: &'static str,
+	/// Selector for the rows of the novel info list, e.g. `Author: Name`.
+	pub info_selector: &'static str,
+	pub author_label: &'static str,
+	pub genre_label: &'static str,
+	pub status_label: &'static str,
+	/// Selector for the element with the novel id in a `data-novel-id` attribute.
+	pub novel_id_selector: &'static str,
+	pub chapter_selector: &'static str,
+	/// Attribute of the chapter elements with the chapter url, e.g. `value` for the
+	/// options of a chapter select.
+	pub chapter_url_attr: &'static str,
+	pub content_selector: &'static str,
+	/// Listings shown on the home page, as pairs of listing ids and titles.
+	pub home_listings: &'static [(&'static str, &'static str)],
+}
+
+impl Default for Params {
+	fn default() -> Self {
+		Self {
+			base_url: String::new(),
+			search_path: "search",
+			listing_path: "",
+			chapter_list_path: "ajax-chapter-option",
+			entry_selectors: EntrySelectors {
+				entry: ".list-truyen .row",
+				link: ".truyen-title a",
+				title: "",
+				cover: "img.cover",
+			},
+			next_page_selector: "ul.pagination li.next:not(.disabled) a",
+			title_selector: "h3.title",
+			cover_selector: ".book img",
+			description_selector: ".desc-text",
+			info_selector: ".info > div",
+			author_label: "Author",
+			genre_label: "Genre",
+			status_label: "Status",
+			novel_id_selector: "#rating",
+			chapter_selector: "select option",
+			chapter_url_attr: "value",
+			content_selector: "#chapter-content",
+			home_listings: &[
+				("most-popular", "Most Popular"),
+				("latest-release-novel", "Latest Release"),
+			],
+		}
+	}
+}
+
+impl Params {
+	/// Returns the default configuration for sites based on ReadNovelFull.
+	///
+	/// # Examples
+	/// ```
+	/// use buny_templates::novelfull::Params;
+	/// let params = Params {
+	///     base_url: "https://example.com".into(),
+	///     ..Params::read_novel_full()
+	/// };
+	/// assert_eq!(params.listing_path, "novel-list");
+	/// ```
+	pub fn read_novel_full() -> Self {
+		Self {
+			search_path: "novel-list/search",
+			listing_path: "novel-list",
+			chapter_list_path: "ajax/chapter-archive",
+			entry_selectors: EntrySelectors {
+				entry: ".list-novel .row",
+				link: ".novel-title a",
+				title: "",
+				cover: "img.cover",
+			},
+			info_selector: "ul.info-meta li, ul.info li",
+			chapter_selector: "ul.list-chapter li a",
+			chapter_url_attr: "href",
+			content_selector: "#chr-content",
+			home_listings: &[
+				("most-popular-novel", "Most Popular"),
+				("latest-release-novel", "Latest Release"),
+			],
+			..Default::default()
+		}
+	}
+}
+
+/// A source using the NovelFull template.
+pub struct NovelFull<T: Impl> {
+	inner: T,
+	params: Params,
+}
+
+/// The implementation of a NovelFull source.
+///
+/// Only [Impl::new] and [Impl::params] are required; the other functions can be
+/// overridden to handle differences in a website.
+pub trait Impl {
+	fn new() -> Self;
+
+	fn params(&self) -> Params;
+
+	/// Creates a request for a page of the website.
+	fn request(&self, params: &Params, url: &str) -> Result<Request> {
+		Ok(Request::get(url)?.header("Referer", params.base_url.as_str()))
+	}
+
+	fn search_url(
+		&self,
+		params: &Params,
+		query: Option<&str>,
+		page: i32,
+		_filters: &[FilterValue],
+	) -> String {
+		let mut qs = QueryParameters::new();
+		qs.push("keyword", Some(query.unwrap_or_default()));
+		format!(
+			"{}/{}?{qs}&page={page}",
+			params.base_url.trim_end_matches('/'),
+			params.search_path
+		)
+	}
+
+	fn listing_url(&self, params: &Params, listing: &Listing, page: i32) -> String {
+		let base_url = params.base_url.trim_end_matches('/');
+		if params.listing_path.is_empty() {
+			format!("{base_url}/{}?page={page}", listing.id)
+		} else {
+			format!(
+				"{base_url}/{}/{}?page={page}",
+				params.listing_path, listing.id
+			)
+		}
+	}
+
+	fn novel_url(&self, params: &Params, key: &str) -> String {
+		helpers::key_to_url(&params.base_url, key)
+	}
+
+	fn chapter_url(&self, params: &Params, _novel_key: &str, key: &str) -> String {
+		helpers::key_to_url(&params.base_url, key)
+	}
+
+	fn chapter_list_url(&self, params: &Params, novel_id: &str) -> String {
+		format!(
+			"{}/{}?novelId={novel_id}",
+			params.base_url.trim_end_matches('/'),
+			params.chapter_list_path
+		)
+	}
+
+	fn get_search_novel_list(
+		&self,
+		params: &Params,
+		query: Option<String>,
+		page: i32,
+		filters: Vec<FilterValue>,
+	) -> Result<NovelPageResult> {
+		let url = self.search_url(params, query.as_deref(), page, &filters);
+		let html = self.request(params, &url)?.html()?;
+		Ok(helpers::parse_page(
+			&html,
+			&params.base_url,
+			params.entry_selectors,
+			params.next_page_selector,
+		))
+	}
+
+	fn get_novel_list(
+		&self,
+		params: &Params,
+		listing: Listing,
+		page: i32,
+	) -> Result<NovelPageResult> {
+		let url = self.listing_url(params, &listing, page);
+		let html = self.request(params, &url)?.html()?;
+		Ok(helpers::parse_page(
+			&html,
+			&params.base_url,
+			params.entry_selectors,
+			params.next_page_selector,
+		))
+	}
+
+	fn get_novel_update(
+		&self,
+		params: &Params,
+		mut novel: Novel,
+		needs_details: bool,
+		needs_chapters: bool,
+		_page: i32,
+	) -> Result<Novel> {
+		let url = self.novel_url(params, &novel.key);
+		let html = self.request(params, &url)?.html()?;
+		if needs_details {
+			self.parse_novel_details(params, &html, &mut novel);
+			novel.url = Some(url);
+		}
+		if needs_chapters {
+			let novel_id = html
+				.select_first(params.novel_id_selector)
+				.and_then(|element| element.attr("data-novel-id"))
+				.ok_or(BunyError::message("Missing novel id"))?;
+			let url = self.chapter_list_url(params, &novel_id);
+			let html = self.request(params, &url)?.html()?;
+			novel.chapters = Some(self.parse_chapter_list(params, &html));
+		}
+		Ok(novel)
+	}
+
+	fn parse_novel_details(&self, params: &Params, html: &Document, novel: &mut Novel) {
+		if let Some(title) = helpers::select_text(html, params.title_selector) {
+			novel.title = title;
+		}
+		novel.cover = html
+			.select_first(params.cover_selector)
+			.and_then(|image| helpers::image_url(&image))
+			.or(novel.cover.take());
+		novel.description = helpers::select_multiline_text(html, params.description_selector);
+		novel.authors = helpers::info_values(html, params.info_selector, "a", params.author_label);
+		novel.tags = helpers::info_values(html, params.info_selector, "a", params.genre_label);
+		novel.status = helpers::info_values(html, params.info_selector, "a", params.status_label)
+			.and_then(|status| status.into_iter().next())
+			.map(|status| helpers::parse_status_label(&status))
+			.unwrap_or_default();
+		Metadata::from_document(html).apply_to(novel);
+	}
+
+	fn parse_chapter_list(&self, params: &Params, html: &Document) -> Vec<Chapter> {
+		let Some(chapters) = html.select(params.chapter_selector) else {
+			return Vec::new();
+		};
+		let mut chapters = chapters
+			.filter_map(|element| {
+				let url = element
+					.attr(params.chapter_url_attr)
+					.filter(|url| !url.is_empty())?;
+				let url = element
+					.attr(format!("abs:{}", params.chapter_url_attr))
+					.unwrap_or(url);
+				let url = helpers::key_to_url(&params.base_url, &url);
+				let title = element
+					.attr("title")
+					.or_else(|| element.text())
+					.filter(|title| !title.is_empty());
+				let numbers = title
+					.as_deref()
+					.map(ChapterTitle::parse)
+					.unwrap_or_default();
+				Some(Chapter {
+					key: helpers::url_to_key(&params.base_url, &url),
+					chapter_number: numbers.chapter_number,
+					volume_number: numbers.volume_number,
+					title,
+					url: Some(url),
+					..Default::default()
+				})
+			})
+			.collect::<Vec<_>>();
+		// chapters are listed oldest first
+		chapters.reverse();
+		chapters
+	}
+
+	fn get_chapter_content_list(
+		&self,
+		params: &Params,
+		novel: Novel,
+		chapter: Chapter,
+	) -> Result<Vec<ContentBlock>> {
+		let url = self.chapter_url(params, &novel.key, &chapter.key);
+		let html = self.request(params, &url)?.html()?;
+		Ok(helpers::parse_content(&html, params.content_selector))
+	}
+
+	fn get_home(&self, params: &Params) -> Result<HomeLayout> {
+		helpers::home_from_listings(params.home_listings, |listing| {
+			self.get_novel_list(params, listing, 1)
+		})
+	}
+
+	fn handle_deep_link(&self, params: &Params, url: String) -> Result<Option<DeepLinkResult>> {
+		let key = helpers::url_to_key(&params.base_url, &url);
+		let segments = helpers::path_segments(&key);
+		match segments.as_slice() {
+			[novel] if novel.ends_with(".html") => Ok(Some(DeepLinkResult::Novel {
+				key: format!("/{novel}"),
+			})),
+			[novel, chapter] if chapter.ends_with(".html") => Ok(Some(DeepLinkResult::Chapter {
+				novel_key: format!("/{novel}.html"),
+				key,
+			})),
+			_ => Ok(None),
+		}
+	}
+}
+
+impl<T: Impl> Source for NovelFull<T> {
+	fn new() -> Self {
+		let inner = T::new();
+		let params = inner.params();
+		Self { inner, params }
+	}
+
+	fn get_search_novel_list(
+		&self,
+		query: Option<String>,
+		page: i32,
+		filters: Vec<FilterValue>,
+	) -> Result<NovelPageResult> {
+		self.inner
+			.get_search_novel_list(&self.params, query, page, filters)
+	}
+
+	fn get_novel_update(
+		&self,
+		novel: Novel,
+		needs_details: bool,
+		needs_chapters: bool,
+		page: i32,
+	) -> Result<Novel> {
+		self.inner
+			.get_novel_update(&self.params, novel, needs_details, needs_chapters, page)
+	}
+
+	fn get_chapter_content_list(
+		&self,
+		novel: Novel,
+		chapter: Chapter,
+	) -> Result<Vec<ContentBlock>> {
+		self.inner
+			.get_chapter_content_list(&self.params, novel, chapter)
+	}
+}
+
+impl<T: Impl> ListingProvider for NovelFull<T> {
+	fn get_novel_list(&self, listing: Listing, page: i32) -> Result<NovelPageResult> {
+		self.inner.get_novel_list(&self.params, listing, page)
+	}
+}
+
+impl<T: Impl> Home for NovelFull<T> {
+	fn get_home(&self) -> Result<HomeLayout> {
+		self.inner.get_home(&self.params)
+	}
+}
+
+impl<T: Impl> DeepLinkHandler for NovelFull<T> {
+	fn handle_deep_link(&self, url: String) -> Result<Option<DeepLinkResult>> {
+		self.inner.handle_deep_link(&self.params, url)
+	}
+}
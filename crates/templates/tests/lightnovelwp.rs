@@ -0,0 +1,145 @@
+use buny::{
+	imports::net::{mock_response, HttpMethod},
+	DeepLinkHandler, DeepLinkResult, FilterValue, Novel, NovelStatus, Source,
+};
+use buny_templates::lightnovelwp::{Impl, LightNovelWP, Params};
+
+struct Example;
+
+impl Impl for Example {
+	fn new() -> Self {
+		Self
+	}
+
+	fn params(&self) -> Params {
+		Params {
+			base_url: "https://example.com".into(),
+			..Default::default()
+		}
+	}
+}
+
+fn source() -> LightNovelWP<Example> {
+	<LightNovelWP<Example> as Source>::new()
+}
+
+#[test]
+fn filtered_search() {
+	mock_response(
+		Some(HttpMethod::Get),
+		"https://example.com/series/?page=1&status=completed",
+		200,
+		r#"
+		<div class="listupd">
+			<article><a href="/series/novel-one/"><img src="/one.jpg"><span class="ntitle">Novel One</span></a></article>
+			<article><a href="/series/novel-two/"><h2>Novel Two</h2></a></article>
+		</div>
+		"#,
+	)
+	.unwrap();
+	let result = source()
+		.get_search_novel_list(
+			None,
+			1,
+			vec![FilterValue::Select {
+				id: "status".into(),
+				value: "completed".into(),
+			}],
+		)
+		.unwrap();
+	assert!(!result.has_next_page);
+	let titles = result
+		.entries
+		.iter()
+		.map(|novel| novel.title.as_str())
+		.collect::<Vec<_>>();
+	assert_eq!(titles, ["Novel One", "Novel Two"]);
+	assert_eq!(result.entries[1].key, "/series/novel-two/");
+}
+
+#[test]
+fn novel_update() {
+	mock_response(
+		Some(HttpMethod::Get),
+		"https://example.com/series/novel-one/",
+		200,
+		r#"
+		<h1 class="entry-title">Novel One</h1>
+		<div class="sertostat"><span>Hiatus</span></div>
+		<div class="sertogenre"><a>Romance</a></div>
+		<div class="eplister">
+			<ul>
+				<li>
+					<a href="https://example.com/novel-one-chapter-11/">
+						<div class="epl-num">Vol. 2 Ch. 11</div>
+						<div class="epl-title">A New Day</div>
+						<div class="epl-date">July 2, 2025</div>
+						<div class="epl-price">10 Coins</div>
+					</a>
+				</li>
+				<li>
+					<a href="https://example.com/novel-one-chapter-10/">
+						<div class="epl-num">10</div>
+						<div class="epl-title">The End</div>
+						<div class="epl-price">Free</div>
+					</a>
+				</li>
+			</ul>
+		</div>
+		"#,
+	)
+	.unwrap();
+	let novel = source()
+		.get_novel_update(
+			Novel {
+				key: "/series/novel-one/".into(),
+				..Default::default()
+			},
+			true,
+			true,
+			1,
+		)
+		.unwrap();
+	assert_eq!(novel.title, "Novel One");
+	assert_eq!(novel.status, NovelStatus::Hiatus);
+	assert_eq!(novel.tags, Some(vec!["Romance".into()]));
+
+	let chapters = novel.chapters.unwrap();
+	assert_eq!(chapters.len(), 2);
+	assert_eq!(chapters[0].key, "/novel-one-chapter-11/");
+	assert_eq!(chapters[0].title.as_deref(), Some("A New Day"));
+	assert_eq!(chapters[0].chapter_number, Some(11.0));
+	assert_eq!(chapters[0].date_uploaded, Some(1751414400));
+	assert!(chapters[0].locked);
+	assert_eq!(chapters[1].chapter_number, Some(10.0));
+	assert!(!chapters[1].locked);
+}
+
+#[test]
+fn deep_links() {
+	mock_response(
+		Some(HttpMethod::Get),
+		"https://example.com/novel-one-chapter-10/",
+		200,
+		r#"<div class="allc"><a href="/series/novel-one/">Novel One</a></div>"#,
+	)
+	.unwrap();
+	let source = source();
+	assert_eq!(
+		source
+			.handle_deep_link("https://example.com/series/novel-one/".into())
+			.unwrap(),
+		Some(DeepLinkResult::Novel {
+			key: "/series/novel-one/".into()
+		})
+	);
+	assert_eq!(
+		source
+			.handle_deep_link("https://example.com/novel-one-chapter-10/".into())
+			.unwrap(),
+		Some(DeepLinkResult::Chapter {
+			novel_key: "/series/novel-one/".into(),
+			key: "/novel-one-chapter-10/".into(),
+		})
+	);
+}
@@ -0,0 +1,195 @@
+use buny::{
+	imports::net::{mock_response, HttpMethod},
+	ContentBlock, DeepLinkHandler, DeepLinkResult, FilterValue, Novel, NovelStatus, Source,
+};
+use buny_templates::madara::{Impl, Madara, Params};
+
+struct Example;
+
+impl Impl for Example {
+	fn new() -> Self {
+		Self
+	}
+
+	fn params(&self) -> Params {
+		Params {
+			base_url: "https://example.com".into(),
+			..Default::default()
+		}
+	}
+}
+
+fn source() -> Madara<Example> {
+	<Madara<Example> as Source>::new()
+}
+
+#[test]
+fn search() {
+	mock_response(
+		Some(HttpMethod::Get),
+		"https://example.com/page/1/?s=sword&post_type=wp-manga&genre%5B%5D=fantasy",
+		200,
+		r#"
+		<div class="c-tabs-item__content">
+			<div class="post-title"><a href="https://example.com/novel/sword-saint/">Sword Saint</a></div>
+			<img data-src="https://example.com/covers/sword.jpg" src="data:image/gif;base64,">
+		</div>
+		<div class="c-tabs-item__content">
+			<div class="post-title"><a href="/novel/sword-god/">Sword God</a></div>
+		</div>
+		<div class="nav-previous"><a href="/page/2/">Older</a></div>
+		"#,
+	)
+	.unwrap();
+	let result = source()
+		.get_search_novel_list(
+			Some("sword".into()),
+			1,
+			vec![FilterValue::MultiSelect {
+				id: "genre".into(),
+				included: vec!["fantasy".into()],
+				excluded: Vec::new(),
+			}],
+		)
+		.unwrap();
+	assert!(result.has_next_page);
+	assert_eq!(result.entries.len(), 2);
+	assert_eq!(result.entries[0].key, "/novel/sword-saint/");
+	assert_eq!(result.entries[0].title, "Sword Saint");
+	assert_eq!(
+		result.entries[0].cover.as_deref(),
+		Some("https://example.com/covers/sword.jpg")
+	);
+	assert_eq!(result.entries[1].key, "/novel/sword-god/");
+}
+
+#[test]
+fn novel_update() {
+	mock_response(
+		Some(HttpMethod::Get),
+		"https://example.com/novel/sword-saint/",
+		200,
+		r#"
+		<div class="post-title"><h1>Sword Saint</h1></div>
+		<div class="summary_image"><img src="https://example.com/covers/sword.jpg"></div>
+		<div class="author-content"><a href="/author/a/">Author A</a></div>
+		<div class="genres-content"><a>Action</a>, <a>Fantasy</a></div>
+		<div class="post-status">
+			<div class="summary-content">Manga</div>
+			<div class="summary-content">OnGoing</div>
+		</div>
+		<div class="description-summary"><div class="summary__content"><p>First line.</p><p>Second line.</p></div></div>
+		"#,
+	)
+	.unwrap();
+	mock_response(
+		Some(HttpMethod::Post),
+		"https://example.com/novel/sword-saint/ajax/chapters/",
+		200,
+		r#"
+		<ul>
+			<li class="wp-manga-chapter premium-block">
+				<a href="https://example.com/novel/sword-saint/chapter-2/">Vol. 1 Chapter 2 - The Duel</a>
+				<span class="chapter-release-date"><i>July 2, 2025</i></span>
+			</li>
+			<li class="wp-manga-chapter">
+				<a href="https://example.com/novel/sword-saint/chapter-1/">Chapter 1</a>
+				<span class="chapter-release-date"><i>July 1, 2025</i></span>
+			</li>
+		</ul>
+		"#,
+	)
+	.unwrap();
+
+	let novel = source()
+		.get_novel_update(
+			Novel {
+				key: "/novel/sword-saint/".into(),
+				..Default::default()
+			},
+			true,
+			true,
+			1,
+		)
+		.unwrap();
+	assert_eq!(novel.title, "Sword Saint");
+	assert_eq!(novel.authors, Some(vec!["Author A".into()]));
+	assert_eq!(novel.tags, Some(vec!["Action".into(), "Fantasy".into()]));
+	assert_eq!(novel.status, NovelStatus::Ongoing);
+	assert_eq!(
+		novel.description.as_deref(),
+		Some("First line.\nSecond line.")
+	);
+	assert_eq!(
+		novel.url.as_deref(),
+		Some("https://example.com/novel/sword-saint/")
+	);
+
+	let chapters = novel.chapters.unwrap();
+	assert_eq!(chapters.len(), 2);
+	assert_eq!(chapters[0].key, "/novel/sword-saint/chapter-2/");
+	assert_eq!(chapters[0].volume_number, Some(1.0));
+	assert_eq!(chapters[0].chapter_number, Some(2.0));
+	assert_eq!(chapters[0].date_uploaded, Some(1751414400));
+	assert!(chapters[0].locked);
+	assert_eq!(chapters[1].chapter_number, Some(1.0));
+	assert!(!chapters[1].locked);
+}
+
+#[test]
+fn chapter_content() {
+	mock_response(
+		Some(HttpMethod::Get),
+		"https://example.com/novel/sword-saint/chapter-1/",
+		200,
+		r#"<div class="reading-content"><div class="text-left"><p>One *star*</p><p>Two</p></div></div>"#,
+	)
+	.unwrap();
+	let content = source()
+		.get_chapter_content_list(
+			Novel {
+				key: "/novel/sword-saint/".into(),
+				..Default::default()
+			},
+			buny::Chapter {
+				key: "/novel/sword-saint/chapter-1/".into(),
+				..Default::default()
+			},
+		)
+		.unwrap();
+	assert_eq!(
+		content,
+		[
+			ContentBlock::paragraph("One \\*star\\*", None),
+			ContentBlock::paragraph("Two", None),
+		]
+	);
+}
+
+#[test]
+fn deep_links() {
+	let source = source();
+	assert_eq!(
+		source
+			.handle_deep_link("https://example.com/novel/sword-saint/".into())
+			.unwrap(),
+		Some(DeepLinkResult::Novel {
+			key: "/novel/sword-saint/".into()
+		})
+	);
+	assert_eq!(
+		source
+			.handle_deep_link("https://example.com/novel/sword-saint/chapter-1/".into())
+			.unwrap(),
+		Some(DeepLinkResult::Chapter {
+			novel_key: "/novel/sword-saint/".into(),
+			key: "/novel/sword-saint/chapter-1/".into(),
+		})
+	);
+	assert_eq!(
+		source
+			.handle_deep_link("https://example.com/about/".into())
+			.unwrap(),
+		None
+	);
+}
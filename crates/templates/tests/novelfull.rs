@@ -0,0 +1,218 @@
+use buny::{
+	imports::net::{mock_response, HttpMethod},
+	DeepLinkHandler, DeepLinkResult, Listing, ListingProvider, Novel, NovelStatus, Source,
+};
+use buny_templates::novelfull::{Impl, NovelFull, Params};
+
+struct Example;
+
+impl Impl for Example {
+	fn new() -> Self {
+		Self
+	}
+
+	fn params(&self) -> Params {
+		Params {
+			base_url: "https://novelfull.example".into(),
+			..Default::default()
+		}
+	}
+}
+
+struct ReadExample;
+
+impl Impl for ReadExample {
+	fn new() -> Self {
+		Self
+	}
+
+	fn params(&self) -> Params {
+		Params {
+			base_url: "https://readnovelfull.example".into(),
+			..Params::read_novel_full()
+		}
+	}
+}
+
+fn novel(key: &str) -> Novel {
+	Novel {
+		key: key.into(),
+		..Default::default()
+	}
+}
+
+#[test]
+fn listing() {
+	mock_response(
+		Some(HttpMethod::Get),
+		"https://novelfull.example/most-popular?page=2",
+		200,
+		r#"
+		<div class="list-truyen">
+			<div class="row">
+				<img class="cover" src="/covers/one.jpg">
+				<h3 class="truyen-title"><a href="/novel-one.html" title="Novel One">Novel One</a></h3>
+			</div>
+		</div>
+		<ul class="pagination"><li class="next disabled"><a>Next</a></li></ul>
+		"#,
+	)
+	.unwrap();
+	let source = <NovelFull<Example> as Source>::new();
+	let result = source
+		.get_novel_list(
+			Listing {
+				id: "most-popular".into(),
+				..Default::default()
+			},
+			2,
+		)
+		.unwrap();
+	assert!(!result.has_next_page);
+	assert_eq!(result.entries.len(), 1);
+	assert_eq!(result.entries[0].key, "/novel-one.html");
+	assert_eq!(
+		result.entries[0].cover.as_deref(),
+		Some("https://novelfull.example/covers/one.jpg")
+	);
+}
+
+#[test]
+fn novel_update() {
+	mock_response(
+		Some(HttpMethod::Get),
+		"https://novelfull.example/novel-one.html",
+		200,
+		r#"
+		<h3 class="title">Novel One</h3>
+		<div class="info">
+			<div><h3>Author:</h3><a href="/author/a">Author A</a></div>
+			<div><h3>Genre:</h3><a>Action</a>, <a>Drama</a></div>
+			<div><h3>Status:</h3><a>Completed</a></div>
+		</div>
+		<div class="desc-text"><p>Description.</p></div>
+		<div id="rating" data-novel-id="42"></div>
+		"#,
+	)
+	.unwrap();
+	mock_response(
+		Some(HttpMethod::Get),
+		"https://novelfull.example/ajax-chapter-option?novelId=42",
+		200,
+		r#"
+		<select>
+			<option value="/novel-one/chapter-1.html">Chapter 1: Start</option>
+			<option value="/novel-one/chapter-2.html">Chapter 2: End</option>
+			<option value="">Invalid</option>
+		</select>
+		"#,
+	)
+	.unwrap();
+
+	let source = <NovelFull<Example> as Source>::new();
+	let novel = source
+		.get_novel_update(novel("/novel-one.html"), true, true, 1)
+		.unwrap();
+	assert_eq!(novel.title, "Novel One");
+	assert_eq!(novel.authors, Some(vec!["Author A".into()]));
+	assert_eq!(novel.tags, Some(vec!["Action".into(), "Drama".into()]));
+	assert_eq!(novel.status, NovelStatus::Completed);
+
+	// chapters are listed oldest first
+	let chapters = novel.chapters.unwrap();
+	assert_eq!(chapters.len(), 2);
+	assert_eq!(chapters[0].key, "/novel-one/chapter-2.html");
+	assert_eq!(chapters[0].chapter_number, Some(2.0));
+	assert_eq!(chapters[0].title.as_deref(), Some("Chapter 2: End"));
+	assert_eq!(
+		chapters[1].url.as_deref(),
+		Some("https://novelfull.example/novel-one/chapter-1.html")
+	);
+}
+
+#[test]
+fn read_novel_full_preset() {
+	mock_response(
+		Some(HttpMethod::Get),
+		"https://readnovelfull.example/novel-list/most-popular-novel?page=1",
+		200,
+		r#"
+		<div class="list-novel">
+			<div class="row">
+				<h3 class="novel-title"><a href="https://readnovelfull.example/novel-two.html">Novel Two</a></h3>
+			</div>
+		</div>
+		<ul class="pagination"><li class="next"><a href="?page=2">Next</a></li></ul>
+		"#,
+	)
+	.unwrap();
+	mock_response(
+		Some(HttpMethod::Get),
+		"https://readnovelfull.example/novel-two.html",
+		200,
+		r#"
+		<h3 class="title">Novel Two</h3>
+		<ul class="info-meta"><li><h3>Status:</h3><a>Ongoing</a></li></ul>
+		<div id="rating" data-novel-id="7"></div>
+		"#,
+	)
+	.unwrap();
+	mock_response(
+		Some(HttpMethod::Get),
+		"https://readnovelfull.example/ajax/chapter-archive?novelId=7",
+		200,
+		r#"
+		<ul class="list-chapter">
+			<li><a href="/novel-two/chapter-1.html" title="Chapter 1 - One">Chapter 1</a></li>
+			<li><a href="https://readnovelfull.example/novel-two/chapter-2.html">Chapter 2</a></li>
+		</ul>
+		"#,
+	)
+	.unwrap();
+
+	let source = <NovelFull<ReadExample> as Source>::new();
+	let result = source
+		.get_novel_list(
+			Listing {
+				id: "most-popular-novel".into(),
+				..Default::default()
+			},
+			1,
+		)
+		.unwrap();
+	assert!(result.has_next_page);
+	assert_eq!(result.entries[0].key, "/novel-two.html");
+
+	let novel = source
+		.get_novel_update(novel("/novel-two.html"), true, true, 1)
+		.unwrap();
+	assert_eq!(novel.status, NovelStatus::Ongoing);
+	let chapters = novel.chapters.unwrap();
+	assert_eq!(chapters.len(), 2);
+	assert_eq!(chapters[0].key, "/novel-two/chapter-2.html");
+	assert_eq!(chapters[1].key, "/novel-two/chapter-1.html");
+	assert_eq!(chapters[1].title.as_deref(), Some("Chapter 1 - One"));
+	assert_eq!(chapters[1].chapter_number, Some(1.0));
+}
+
+#[test]
+fn deep_links() {
+	let source = <NovelFull<Example> as Source>::new();
+	assert_eq!(
+		source
+			.handle_deep_link("https://novelfull.example/novel-one.html".into())
+			.unwrap(),
+		Some(DeepLinkResult::Novel {
+			key: "/novel-one.html".into()
+		})
+	);
+	assert_eq!(
+		source
+			.handle_deep_link("https://novelfull.example/novel-one/chapter-1.html".into())
+			.unwrap(),
+		Some(DeepLinkResult::Chapter {
+			novel_key: "/novel-one.html".into(),
+			key: "/novel-one/chapter-1.html".into(),
+		})
+	);
+}
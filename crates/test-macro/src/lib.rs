@@ -8,6 +8,22 @@ pub fn buny_test(_attr: TokenStream, item: TokenStream) -> TokenStream {
 	let mut item = parse_macro_input!(item as syn::ItemFn);
 	let name = item.sig.ident.to_string();
 
+	// if the function returns a result, call it from a wrapper that reports errors
+	// with their details, since the test export can't return a value
+	if !matches!(item.sig.output, syn::ReturnType::Default) {
+		let mut inner = item.clone();
+		inner.attrs.clear();
+		let ident = item.sig.ident.clone();
+		item.sig.output = syn::ReturnType::Default;
+		item.block = parse_quote! {{
+			#inner
+			if let ::core::result::Result::Err(error) = #ident() {
+				let error: ::buny::imports::error::BunyError = ::core::convert::Into::into(error);
+				::core::panic!("test returned an error:\n{}", error.info());
+			}
+		}};
+	}
+
 	// inject panic hook at the top of the function body
 	// this way, we can see panic info when the test fails
 	item.block
@@ -16,6 +32,7 @@ pub fn buny_test(_attr: TokenStream, item: TokenStream) -> TokenStream {
 	item.block.stmts.insert(
 		1,
 		parse_quote! {
+			#[cfg(target_family = "wasm")]
 			std::panic::set_hook(::buny::alloc::Box::new(|info| {
 				::buny::prelude::println!("{info}");
 			}));
@@ -24,15 +41,19 @@ pub fn buny_test(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
 	// if the function should be ignored, add it to the export name
 	let mut ignore = "";
+	let mut native_attrs = quote! { #[cfg_attr(not(target_family = "wasm"), test)] };
 	if let Some(i) = item.attrs.iter().position(is_ignore) {
 		item.attrs.remove(i);
-		ignore = "ignore$"
+		ignore = "ignore$";
+		native_attrs.extend(quote! { #[cfg_attr(not(target_family = "wasm"), ignore)] });
 	}
 
-	// create a custom export name so we can read the exports in the test runner
+	// create a custom export name so we can read the exports in the test runner,
+	// and run the test with the default test harness when built natively
 	let res = quote! {
 		#[cfg(test)]
-		#[export_name = concat!("$buny-test$", #ignore, module_path!(), "::",  #name)]
+		#[cfg_attr(target_family = "wasm", export_name = concat!("$buny-test$", #ignore, module_path!(), "::",  #name))]
+		#native_attrs
 		#item
 	};
 	res.into()
@@ -4,7 +4,7 @@ use libtest_mimic::{Arguments, Failed, Trial};
 use std::process::ExitCode;
 use wasmer::*;
 
-use libs::WasmEnv;
+use libs::{Snapshots, WasmEnv};
 
 fn main() -> Result<ExitCode> {
 	let file = match std::env::args().nth(1) {
@@ -14,7 +14,14 @@ fn main() -> Result<ExitCode> {
 		}
 	};
 
-	let args = Arguments::from_iter(std::env::args().skip(1)); // skip the test runner executable and use wasm file as executable
+	// the snapshot flag isn't a libtest argument, so remove it before parsing the rest
+	let update_snapshots = std::env::args().any(|arg| arg == "--update-snapshots")
+		|| std::env::var_os("BUNY_UPDATE_SNAPSHOTS").is_some_and(|value| value != "0");
+	let args = Arguments::from_iter(
+		std::env::args()
+			.skip(1) // skip the test runner executable and use wasm file as executable
+			.filter(|arg| arg != "--update-snapshots"),
+	);
 
 	let mut store = Store::default();
 	let module = Module::from_file(&store, &file)?;
@@ -40,8 +47,11 @@ fn main() -> Result<ExitCode> {
 			});
 
 			let file = file.clone();
-			let trial = Trial::test(name, move || run_test(&file, export.name(), args.nocapture))
-				.with_ignored_flag(ignore);
+			let snapshots = Snapshots::new(Snapshots::default_dir(), name, update_snapshots);
+			let trial = Trial::test(name, move || {
+				run_test(&file, export.name(), snapshots, args.nocapture)
+			})
+			.with_ignored_flag(ignore);
 			tests.push(trial);
 		}
 	}
@@ -49,10 +59,16 @@ fn main() -> Result<ExitCode> {
 	libtest_mimic::run(&args, tests).exit();
 }
 
-fn run_test(file: &str, name: &str, nocapture: bool) -> Result<(), Failed> {
+fn run_test(file: &str, name: &str, snapshots: Snapshots, nocapture: bool) -> Result<(), Failed> {
 	let mut store = Store::default();
 	let module = Module::from_file(&store, file)?;
-	let env = FunctionEnv::new(&mut store, WasmEnv::new());
+	let env = FunctionEnv::new(
+		&mut store,
+		WasmEnv {
+			snapshots: Some(snapshots),
+			..WasmEnv::new()
+		},
+	);
 	let imports = imports::generate_imports(&mut store, &env);
 	let instance = Instance::new(&mut store, &module, &imports)?;
 	{
@@ -0,0 +1,404 @@
+use crate::{
+	imports,
+	libs::{SourceError, StoreItem},
+	FFIResult, Rid, WasmEnv,
+};
+use anyhow::{anyhow, Context, Result};
+use buny::{
+	Chapter, Comment, CommentPageResult, ContentBlock, DeepLinkResult, Filter, FilterValue,
+	HomeLayout, Listing, Novel, NovelGroup, NovelPageResult, ReadingProgress, SearchSuggestion,
+	Setting, UnlockOption, UnlockResult, ERROR_VERSION, STRUCT_VERSION,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{collections::HashMap, io::Read, path::Path};
+use wasmer::{FunctionEnv, Instance, Module, Store, Value};
+
+/// A source loaded into the source runner, for calling its functions from native code.
+///
+/// Each function returns the decoded result of the source. If the source returns an error, it
+/// can be inspected by downcasting to a [SourceError]:
+///
+/// ```ignore
+/// let mut host = SourceHost::from_file("package.bunpack")?;
+/// match host.home() {
+///     Ok(layout) => println!("{} components", layout.components.len()),
+///     Err(error) => match error.downcast_ref::<SourceError>() {
+///         Some(error) => println!("source error: {error}"),
+///         None => return Err(error),
+///     },
+/// }
+/// ```
+pub struct SourceHost {
+	store: Store,
+	env: FunctionEnv<WasmEnv>,
+	instance: Instance,
+	/// The descriptors of the arguments stored for the next call, which are removed after it.
+	args: Vec<Rid>,
+}
+
+impl SourceHost {
+	/// Loads a source from the bytes of its wasm file and initializes it.
+	pub fn new(wasm: &[u8]) -> Result<Self> {
+		let mut store = Store::default();
+		let module = Module::new(&store, wasm)?;
+		let env = FunctionEnv::new(&mut store, WasmEnv::new());
+		let imports = imports::generate_imports(&mut store, &env);
+		let instance = Instance::new(&mut store, &module, &imports)?;
+		env.as_mut(&mut store).memory = Some(instance.exports.get_memory("memory")?.clone());
+
+		let mut host = Self {
+			store,
+			env,
+			instance,
+			args: Vec::new(),
+		};
+		host.instance
+			.exports
+			.get_typed_function::<(), ()>(&host.store, "start")
+			.context("source does not export a start function")?
+			.call(&mut host.store)?;
+
+		// sources built with older versions of buny don't support error versions
+		if let Ok(set_error_version) = host
+			.instance
+			.exports
+			.get_typed_function::<i32, i32>(&host.store, "set_error_version")
+		{
+			let version = set_error_version.call(&mut host.store, ERROR_VERSION)?;
+			host.env_mut().error_version = version;
+		}
+		// and encode novels and chapters with the first layout
+		if let Ok(set_struct_version) = host
+			.instance
+			.exports
+			.get_typed_function::<i32, i32>(&host.store, "set_struct_version")
+		{
+			let version = set_struct_version.call(&mut host.store, STRUCT_VERSION)?;
+			host.env_mut().struct_version = version;
+		}
+		Ok(host)
+	}
+
+	/// Loads a source from a wasm file or a `.bunpack` package and initializes it.
+	pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+		let path = path.as_ref();
+		let mut wasm = Vec::new();
+		let file = std::fs::File::open(path)
+			.with_context(|| format!("failed to open {}", path.display()))?;
+		if path.extension().is_some_and(|ext| ext == "wasm") {
+			std::io::BufReader::new(file).read_to_end(&mut wasm)?;
+		} else {
+			zip::read::ZipArchive::new(std::io::BufReader::new(file))
+				.context("failed to read package")?
+				.by_name("Payload/main.wasm")
+				.context("package is missing main.wasm")?
+				.read_to_end(&mut wasm)?;
+		}
+		Self::new(&wasm)
+	}
+
+	/// The environment of the source, e.g. for setting defaults or mocking requests.
+	pub fn env(&self) -> &WasmEnv {
+		self.env.as_ref(&self.store)
+	}
+
+	/// The mutable environment of the source.
+	pub fn env_mut(&mut self) -> &mut WasmEnv {
+		self.env.as_mut(&mut self.store)
+	}
+
+	/// Takes the output the source has printed so far.
+	pub fn take_stdout(&mut self) -> String {
+		std::mem::take(&mut self.env_mut().stdout)
+	}
+
+	/// Returns whether the source exports a function, e.g. `get_home` for sources with a home page.
+	pub fn has_function(&self, name: &str) -> bool {
+		self.instance.exports.get_function(name).is_ok()
+	}
+
+	/// Searches for novels with a query and filters.
+	pub fn search(
+		&mut self,
+		query: Option<&str>,
+		page: i32,
+		filters: &[FilterValue],
+	) -> Result<NovelPageResult> {
+		let query = match query {
+			Some(query) => self.store_string(query),
+			None => -1,
+		};
+		let filters = self.store_encoded(&filters)?;
+		self.call(
+			"get_search_novel_list",
+			&[Value::I32(query), Value::I32(page), Value::I32(filters)],
+		)
+	}
+
+	/// Updates a novel with its details and/or a page of its chapters.
+	pub fn novel_update(
+		&mut self,
+		novel: &Novel,
+		needs_details: bool,
+		needs_chapters: bool,
+		page: i32,
+	) -> Result<Novel> {
+		let novel = self.store_encoded(novel)?;
+		self.call(
+			"get_novel_update",
+			&[
+				Value::I32(novel),
+				Value::I32(needs_details as i32),
+				Value::I32(needs_chapters as i32),
+				Value::I32(page),
+			],
+		)
+	}
+
+	/// Fetches the content of a chapter.
+	pub fn chapter_content(
+		&mut self,
+		novel: &Novel,
+		chapter: &Chapter,
+	) -> Result<Vec<ContentBlock>> {
+		let novel = self.store_encoded(novel)?;
+		let chapter = self.store_encoded(chapter)?;
+		self.call(
+			"get_chapter_content_list",
+			&[Value::I32(novel), Value::I32(chapter)],
+		)
+	}
+
+	/// Fetches a page of novels from a listing.
+	pub fn novel_list(&mut self, listing: &Listing, page: i32) -> Result<NovelPageResult> {
+		let listing = self.store_encoded(listing)?;
+		self.call("get_novel_list", &[Value::I32(listing), Value::I32(page)])
+	}
+
+	/// Fetches the home layout.
+	pub fn home(&mut self) -> Result<HomeLayout> {
+		self.call("get_home", &[])
+	}
+
+	/// Fetches the dynamic listings.
+	pub fn listings(&mut self) -> Result<Vec<Listing>> {
+		self.call("get_listings", &[])
+	}
+
+	/// Fetches the dynamic filters.
+	pub fn filters(&mut self) -> Result<Vec<Filter>> {
+		self.call("get_filters", &[])
+	}
+
+	/// Fetches the dynamic settings.
+	pub fn settings(&mut self) -> Result<Vec<Setting>> {
+		self.call("get_settings", &[])
+	}
+
+	/// Handles a deep link url.
+	pub fn deep_link(&mut self, url: &str) -> Result<Option<DeepLinkResult>> {
+		let url = self.store_encoded(&url)?;
+		self.call("handle_deep_link", &[Value::I32(url)])
+	}
+
+	/// Logs in with a username and password, returning whether the login succeeded.
+	pub fn login(&mut self, key: &str, username: &str, password: &str) -> Result<bool> {
+		let key = self.store_encoded(&key)?;
+		let username = self.store_encoded(&username)?;
+		let password = self.store_encoded(&password)?;
+		self.call(
+			"handle_basic_login",
+			&[Value::I32(key), Value::I32(username), Value::I32(password)],
+		)
+	}
+
+	/// Logs in with the cookies of a web login, returning whether the login succeeded.
+	pub fn web_login(&mut self, key: &str, cookies: &HashMap<String, String>) -> Result<bool> {
+		let (keys, values): (Vec<_>, Vec<_>) = cookies.iter().unzip();
+		let key = self.store_encoded(&key)?;
+		let keys = self.store_encoded(&keys)?;
+		let values = self.store_encoded(&values)?;
+		self.call(
+			"handle_web_login",
+			&[Value::I32(key), Value::I32(keys), Value::I32(values)],
+		)
+	}
+
+	/// Fetches a page of the novels in the user's library on the website.
+	pub fn library(&mut self, page: i32) -> Result<NovelPageResult> {
+		self.call("get_library", &[Value::I32(page)])
+	}
+
+	/// Adds a novel to the user's library on the website.
+	pub fn add_to_library(&mut self, novel: &Novel) -> Result<()> {
+		let novel = self.store_encoded(novel)?;
+		self.call("add_to_library", &[Value::I32(novel)])
+	}
+
+	/// Removes a novel from the user's library on the website.
+	pub fn remove_from_library(&mut self, novel: &Novel) -> Result<()> {
+		let novel = self.store_encoded(novel)?;
+		self.call("remove_from_library", &[Value::I32(novel)])
+	}
+
+	/// Fetches a page of the comments on a novel, or on one of its chapters.
+	pub fn comments(
+		&mut self,
+		novel: &Novel,
+		chapter: Option<&Chapter>,
+		page: i32,
+	) -> Result<CommentPageResult> {
+		let novel = self.store_encoded(novel)?;
+		let chapter = match chapter {
+			Some(chapter) => self.store_encoded(chapter)?,
+			None => -1,
+		};
+		self.call(
+			"get_comments",
+			&[Value::I32(novel), Value::I32(chapter), Value::I32(page)],
+		)
+	}
+
+	/// Fetches a page of the replies to a comment.
+	pub fn comment_replies(
+		&mut self,
+		novel: &Novel,
+		chapter: Option<&Chapter>,
+		comment: &Comment,
+		page: i32,
+	) -> Result<CommentPageResult> {
+		let novel = self.store_encoded(novel)?;
+		let chapter = match chapter {
+			Some(chapter) => self.store_encoded(chapter)?,
+			None => -1,
+		};
+		let comment = self.store_encoded(comment)?;
+		self.call(
+			"get_comment_replies",
+			&[
+				Value::I32(novel),
+				Value::I32(chapter),
+				Value::I32(comment),
+				Value::I32(page),
+			],
+		)
+	}
+
+	/// Fetches suggested novels for a partial search query.
+	pub fn search_suggestions(&mut self, query: &str) -> Result<Vec<SearchSuggestion>> {
+		let query = self.store_encoded(&query)?;
+		self.call("get_search_suggestions", &[Value::I32(query)])
+	}
+
+	/// Fetches groups of novels related to a novel.
+	pub fn related_novels(&mut self, novel: &Novel) -> Result<Vec<NovelGroup>> {
+		let novel = self.store_encoded(novel)?;
+		self.call("get_related_novels", &[Value::I32(novel)])
+	}
+
+	/// Marks a chapter as read up to a position, from 0 (start) to 1 (end).
+	pub fn mark_chapter_read(
+		&mut self,
+		novel: &Novel,
+		chapter: &Chapter,
+		position: f32,
+	) -> Result<()> {
+		let novel = self.store_encoded(novel)?;
+		let chapter = self.store_encoded(chapter)?;
+		self.call(
+			"mark_chapter_read",
+			&[Value::I32(novel), Value::I32(chapter), Value::F32(position)],
+		)
+	}
+
+	/// Fetches the reading progress of a novel on the website.
+	pub fn remote_progress(&mut self, novel: &Novel) -> Result<Option<ReadingProgress>> {
+		let novel = self.store_encoded(novel)?;
+		self.call("get_remote_progress", &[Value::I32(novel)])
+	}
+
+	/// Fetches the ways a locked chapter can be unlocked.
+	pub fn unlock_options(
+		&mut self,
+		novel: &Novel,
+		chapter: &Chapter,
+	) -> Result<Vec<UnlockOption>> {
+		let novel = self.store_encoded(novel)?;
+		let chapter = self.store_encoded(chapter)?;
+		self.call(
+			"get_unlock_options",
+			&[Value::I32(novel), Value::I32(chapter)],
+		)
+	}
+
+	/// Unlocks a chapter with one of its unlock options.
+	pub fn unlock_chapter(
+		&mut self,
+		novel: &Novel,
+		chapter: &Chapter,
+		option: &UnlockOption,
+	) -> Result<UnlockResult> {
+		let novel = self.store_encoded(novel)?;
+		let chapter = self.store_encoded(chapter)?;
+		let option = self.store_encoded(option)?;
+		self.call(
+			"unlock_chapter",
+			&[Value::I32(novel), Value::I32(chapter), Value::I32(option)],
+		)
+	}
+
+	/// Stores a string for the source to read.
+	///
+	/// The string is removed from the store after the next call to the source.
+	pub fn store_string(&mut self, string: &str) -> Rid {
+		let rid = self.env_mut().store.store(StoreItem::String(string.into()));
+		self.args.push(rid);
+		rid
+	}
+
+	/// Stores an encoded value for the source to read.
+	///
+	/// Novels and chapters are encoded with the layout version used by the source. The value is
+	/// removed from the store after the next call to the source.
+	pub fn store_encoded<T: Serialize + ?Sized>(&mut self, item: &T) -> Result<Rid> {
+		let version = self.env().struct_version;
+		let rid = buny::with_struct_version(version, || self.env_mut().store.store_encoded(&item))?;
+		self.args.push(rid);
+		Ok(rid)
+	}
+
+	/// Calls a source function and decodes its result, freeing the result buffer.
+	///
+	/// Errors returned by the source are [SourceError]s.
+	pub fn call<T: DeserializeOwned>(&mut self, name: &str, args: &[Value]) -> Result<T> {
+		let bytes = self.call_raw(name, args)?;
+		buny::with_struct_version(self.env().struct_version, || postcard::from_bytes(&bytes))
+			.with_context(|| format!("failed to decode {name} result"))
+	}
+
+	fn call_raw(&mut self, name: &str, args: &[Value]) -> Result<Vec<u8>> {
+		let result = self
+			.instance
+			.exports
+			.get_function(name)
+			.with_context(|| format!("source does not export {name}"))
+			.and_then(|function| Ok(function.call(&mut self.store, args)?));
+		// the arguments are only read during the call, so they're removed even if it failed
+		for rid in std::mem::take(&mut self.args) {
+			self.env_mut().store.remove(rid);
+		}
+		let value: FFIResult = match result?.first() {
+			Some(Value::I32(value)) => *value,
+			_ => return Err(anyhow!("{name} returned an invalid value")),
+		};
+
+		let result = self.env().read_result(&self.store, value);
+		if value > 0 {
+			self.instance
+				.exports
+				.get_typed_function::<i32, ()>(&self.store, "free_result")?
+				.call(&mut self.store, value)?;
+		}
+		result?.map_err(|error: SourceError| error.into())
+	}
+}
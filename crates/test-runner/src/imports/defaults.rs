@@ -29,35 +29,10 @@ pub fn get(mut env: FunctionEnvMut<WasmEnv>, key_ptr: Ptr, len: u32) -> FFIResul
 	let Some(object) = env.data().defaults.get(&key).cloned() else {
 		return Result::InvalidValue.into();
 	};
-	match object {
-		DefaultValue::Data(data) => env.data_mut().store.store(StoreItem::Encoded(data)),
-		DefaultValue::Bool(bool) => env
-			.data_mut()
-			.store
-			.store_encoded(&bool)
-			.unwrap_or(Result::FailedEncoding.into()),
-		DefaultValue::Int(int) => env
-			.data_mut()
-			.store
-			.store_encoded(&int)
-			.unwrap_or(Result::FailedEncoding.into()),
-		DefaultValue::Float(float) => env
-			.data_mut()
-			.store
-			.store_encoded(&float)
-			.unwrap_or(Result::FailedEncoding.into()),
-		DefaultValue::String(string) => env
-			.data_mut()
-			.store
-			.store_encoded(&string)
-			.unwrap_or(Result::FailedEncoding.into()),
-		DefaultValue::StringArray(array) => env
-			.data_mut()
-			.store
-			.store_encoded(&array)
-			.unwrap_or(Result::FailedEncoding.into()),
-		DefaultValue::Null => Result::InvalidValue.into(),
-		DefaultValue::HashMap(_) => Result::InvalidValue.into(),
+	match object.encode() {
+		Some(Ok(data)) => env.data_mut().store.store(StoreItem::Encoded(data)),
+		Some(Err(_)) => Result::FailedEncoding.into(),
+		None => Result::InvalidValue.into(),
 	}
 }
 
@@ -80,16 +55,7 @@ pub fn set(
 		return Result::FailedDecoding.into();
 	};
 
-	let default_value = match default_kind {
-		DefaultKind::Data => Ok(DefaultValue::Data(data)),
-		DefaultKind::Bool => postcard::from_bytes(&data).map(DefaultValue::Bool),
-		DefaultKind::Int => postcard::from_bytes(&data).map(DefaultValue::Int),
-		DefaultKind::Float => postcard::from_bytes(&data).map(DefaultValue::Float),
-		DefaultKind::String => postcard::from_bytes(&data).map(DefaultValue::String),
-		DefaultKind::StringArray => postcard::from_bytes(&data).map(DefaultValue::StringArray),
-		DefaultKind::Null => Ok(DefaultValue::Null),
-	};
-	let Ok(default_value) = default_value else {
+	let Ok(default_value) = DefaultValue::decode(default_kind, data) else {
 		return Result::FailedDecoding.into();
 	};
 
@@ -15,8 +15,8 @@ pub fn print(mut env: FunctionEnvMut<WasmEnv>, ptr: Ptr, len: u32) {
 	env.data_mut().write_stdout("\n");
 }
 
-pub fn sleep(_env: FunctionEnvMut<WasmEnv>, seconds: i32) {
-	std::thread::sleep(std::time::Duration::from_secs(seconds as u64));
+pub fn sleep(mut env: FunctionEnvMut<WasmEnv>, seconds: i32) {
+	env.data_mut().clock.sleep(seconds);
 }
 
 pub fn send_partial_result(_env: FunctionEnvMut<WasmEnv>, _value: i32) {
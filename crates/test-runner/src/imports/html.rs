@@ -1,9 +1,8 @@
 use crate::{
-	libs::{HtmlDocument, HtmlElement, HtmlElementList, StoreItem},
+	libs::{escape_html, unescape_html, HtmlDocument, HtmlNode, StoreItem},
 	FFIResult, Ptr, Rid, WasmEnv,
 };
-use scraper::{Html, Selector};
-use url::Url;
+use scraper::Selector;
 use wasmer::FunctionEnvMut;
 
 enum Result {
@@ -43,11 +42,10 @@ pub fn parse(
 	let Ok(base_url_string) = env.data().read_string(&env, base_url_ptr, base_url_len) else {
 		return Result::InvalidString.into();
 	};
-	let html = Html::parse_document(&text);
-	let base_uri = Url::parse(&base_url_string).ok();
+	let document = HtmlDocument::parse(&text, &base_url_string);
 	env.data_mut()
 		.store
-		.store(StoreItem::HtmlDocument(HtmlDocument { html, base_uri }))
+		.store(StoreItem::HtmlDocument(document))
 }
 pub fn parse_fragment(
 	mut env: FunctionEnvMut<WasmEnv>,
@@ -62,58 +60,26 @@ pub fn parse_fragment(
 	let Ok(base_url_string) = env.data().read_string(&env, base_url_ptr, base_url_len) else {
 		return Result::InvalidString.into();
 	};
-	let html = Html::parse_fragment(&text);
-	let base_uri = Url::parse(&base_url_string).ok();
+	let document = HtmlDocument::parse_fragment(&text, &base_url_string);
 	env.data_mut()
 		.store
-		.store(StoreItem::HtmlDocument(HtmlDocument { html, base_uri }))
+		.store(StoreItem::HtmlDocument(document))
 }
 pub fn escape(mut env: FunctionEnvMut<WasmEnv>, text_ptr: Ptr, text_len: u32) -> FFIResult {
 	let Ok(text) = env.data().read_string(&env, text_ptr, text_len) else {
 		return Result::InvalidString.into();
 	};
-	let mut escaped = String::with_capacity(text.len());
-	for c in text.chars() {
-		match c {
-			'&' => escaped.push_str("&amp;"),
-			'<' => escaped.push_str("&lt;"),
-			'>' => escaped.push_str("&gt;"),
-			_ => escaped.push(c),
-		}
-	}
-	env.data_mut().store.store(StoreItem::String(escaped))
+	env.data_mut()
+		.store
+		.store(StoreItem::String(escape_html(&text)))
 }
 pub fn unescape(mut env: FunctionEnvMut<WasmEnv>, text_ptr: Ptr, text_len: u32) -> FFIResult {
 	let Ok(text) = env.data().read_string(&env, text_ptr, text_len) else {
 		return Result::InvalidString.into();
 	};
-
-	let mut unescaped = String::with_capacity(text.len());
-	let mut chars = text.chars().peekable();
-
-	while let Some(c) = chars.next() {
-		if c == '&' {
-			let mut entity = String::from("&");
-			while let Some(&next_c) = chars.peek() {
-				entity.push(next_c);
-				if next_c == ';' || entity.len() > 10 {
-					chars.next();
-					break;
-				}
-				chars.next();
-			}
-			match entity.as_str() {
-				"&amp;" => unescaped.push('&'),
-				"&lt;" => unescaped.push('<'),
-				"&gt;" => unescaped.push('>'),
-				_ => unescaped.push_str(&entity),
-			}
-		} else {
-			unescaped.push(c);
-		}
-	}
-
-	env.data_mut().store.store(StoreItem::String(unescaped))
+	env.data_mut()
+		.store
+		.store(StoreItem::String(unescape_html(&text)))
 }
 
 pub fn select(
@@ -132,18 +98,10 @@ pub fn select(
 		return Result::InvalidDescriptor.into();
 	};
 	if let Some(document) = item.as_html_document() {
-		let elements: Vec<HtmlElement> = document
-			.html
-			.select(&selector)
-			.map(|element| HtmlElement {
-				html: document.html.clone(),
-				id: element.id(),
-				base_uri: document.base_uri.clone(),
-			})
-			.collect();
+		let elements = document.select(&selector);
 		env.data_mut()
 			.store
-			.store(StoreItem::HtmlElementList(HtmlElementList(elements)))
+			.store(StoreItem::HtmlElementList(elements))
 	} else if let Some(element) = item.as_html_element() {
 		let Some(elements) = element.select(&selector) else {
 			return Result::NoResult.into();
@@ -178,16 +136,7 @@ pub fn select_first(
 		return Result::InvalidDescriptor.into();
 	};
 	if let Some(document) = item.as_html_document() {
-		let Some(result) = document
-			.html
-			.select(&selector)
-			.map(|element| HtmlElement {
-				html: document.html.clone(),
-				id: element.id(),
-				base_uri: document.base_uri.clone(),
-			})
-			.next()
-		else {
+		let Some(result) = document.select_first(&selector) else {
 			return Result::NoResult.into();
 		};
 		env.data_mut().store.store(StoreItem::HtmlElement(result))
@@ -310,13 +259,17 @@ pub fn parent(mut env: FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
 	let Some(item) = env.data_mut().store.get_mut(rid) else {
 		return Result::InvalidDescriptor.into();
 	};
-	if let Some(element) = item.as_html_element() {
-		let Some(parent) = element.parent() else {
-			return Result::NoResult.into();
-		};
+	let parent = if let Some(element) = item.as_html_element() {
+		element.parent()
+	} else if let Some(node) = item.as_html_node() {
+		node.parent()
+	} else {
+		return Result::InvalidDescriptor.into();
+	};
+	if let Some(parent) = parent {
 		env.data_mut().store.store(StoreItem::HtmlElement(parent))
 	} else {
-		Result::InvalidDescriptor.into()
+		Result::NoResult.into()
 	}
 }
 pub fn children(mut env: FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
@@ -407,9 +360,18 @@ pub fn own_text(mut env: FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
 		Result::InvalidDescriptor.into()
 	}
 }
-pub fn data(_env: FunctionEnvMut<WasmEnv>, _rid: Rid) -> FFIResult {
-	// i don't think scraper supports this?
-	-1
+pub fn data(mut env: FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
+	let Some(item) = env.data_mut().store.get_mut(rid) else {
+		return Result::InvalidDescriptor.into();
+	};
+	if let Some(element) = item.as_html_element() {
+		let Some(data) = element.data() else {
+			return Result::NoResult.into();
+		};
+		env.data_mut().store.store(StoreItem::String(data))
+	} else {
+		Result::InvalidDescriptor.into()
+	}
 }
 pub fn id(mut env: FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
 	let Some(item) = env.data_mut().store.get_mut(rid) else {
@@ -490,6 +452,74 @@ pub fn has_attr(mut env: FunctionEnvMut<WasmEnv>, rid: Rid, attr_ptr: Ptr, attr_
 	}
 }
 
+fn item_as_node(item: &mut StoreItem) -> Option<HtmlNode> {
+	if let Some(element) = item.as_html_element() {
+		Some(element.as_node())
+	} else {
+		item.as_html_node().cloned()
+	}
+}
+fn store_node(env: &mut FunctionEnvMut<WasmEnv>, node: HtmlNode) -> FFIResult {
+	// elements are stored as elements so that all element functions can be used on them
+	if let Some(element) = node.as_element() {
+		env.data_mut().store.store(StoreItem::HtmlElement(element))
+	} else {
+		env.data_mut().store.store(StoreItem::HtmlNode(node))
+	}
+}
+
+pub fn child_nodes(mut env: FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
+	let Some(node) = env.data_mut().store.get_mut(rid).and_then(item_as_node) else {
+		return Result::InvalidDescriptor.into();
+	};
+	let Some(nodes) = node.child_nodes() else {
+		return Result::NoResult.into();
+	};
+	env.data_mut().store.store(StoreItem::HtmlNodeList(nodes))
+}
+pub fn walk(mut env: FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
+	let Some(node) = env.data_mut().store.get_mut(rid).and_then(item_as_node) else {
+		return Result::InvalidDescriptor.into();
+	};
+	let Some(nodes) = node.descendants() else {
+		return Result::NoResult.into();
+	};
+	env.data_mut().store.store(StoreItem::HtmlNodeList(nodes))
+}
+pub fn next_sibling_node(mut env: FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
+	let Some(node) = env.data_mut().store.get_mut(rid).and_then(item_as_node) else {
+		return Result::InvalidDescriptor.into();
+	};
+	let Some(sibling) = node.next_sibling() else {
+		return Result::NoResult.into();
+	};
+	store_node(&mut env, sibling)
+}
+pub fn previous_sibling_node(mut env: FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
+	let Some(node) = env.data_mut().store.get_mut(rid).and_then(item_as_node) else {
+		return Result::InvalidDescriptor.into();
+	};
+	let Some(sibling) = node.prev_sibling() else {
+		return Result::NoResult.into();
+	};
+	store_node(&mut env, sibling)
+}
+pub fn node_type(mut env: FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
+	let Some(node) = env.data_mut().store.get_mut(rid).and_then(item_as_node) else {
+		return Result::InvalidDescriptor.into();
+	};
+	node.node_type().unwrap_or(Result::InvalidDescriptor.into())
+}
+pub fn node_value(mut env: FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
+	let Some(node) = env.data_mut().store.get_mut(rid).and_then(item_as_node) else {
+		return Result::InvalidDescriptor.into();
+	};
+	let Some(value) = node.value() else {
+		return Result::NoResult.into();
+	};
+	env.data_mut().store.store(StoreItem::String(value))
+}
+
 pub fn first(mut env: FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
 	let Some(item) = env.data_mut().store.get_mut(rid) else {
 		return Result::InvalidDescriptor.into();
@@ -525,6 +555,11 @@ pub fn get(mut env: FunctionEnvMut<WasmEnv>, rid: Rid, index: u32) -> FFIResult
 			return Result::InvalidDescriptor.into();
 		};
 		env.data_mut().store.store(StoreItem::HtmlElement(element))
+	} else if let Some(nodes) = item.as_html_node_list() {
+		let Some(node) = nodes.0.get(index as usize).cloned() else {
+			return Result::InvalidDescriptor.into();
+		};
+		store_node(&mut env, node)
 	} else {
 		Result::InvalidDescriptor.into()
 	}
@@ -535,6 +570,8 @@ pub fn size(mut env: FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
 	};
 	if let Some(elements) = item.as_html_element_list() {
 		elements.0.len() as i32
+	} else if let Some(nodes) = item.as_html_node_list() {
+		nodes.0.len() as i32
 	} else {
 		Result::InvalidDescriptor.into()
 	}
@@ -1,5 +1,7 @@
-use crate::{libs::StoreItem, FFIResult, Ptr, Rid, WasmEnv};
-use boa_engine::{JsString, Source};
+use crate::{
+	libs::{eval_js, get_js_global, StoreItem},
+	FFIResult, Ptr, Rid, WasmEnv,
+};
 use wasmer::FunctionEnvMut;
 
 enum Result {
@@ -48,15 +50,7 @@ pub fn context_eval(
 	else {
 		return Result::InvalidContext.into();
 	};
-	let src = Source::from_bytes(&string);
-	let Ok(result) = context.eval(src) else {
-		return Result::MissingResult.into();
-	};
-	let Some(result_string) = result
-		.to_string(context)
-		.ok()
-		.and_then(|s| s.to_std_string().ok())
-	else {
+	let Some(result_string) = eval_js(context, &string) else {
 		return Result::MissingResult.into();
 	};
 	env.data_mut().store.store(StoreItem::String(result_string))
@@ -78,15 +72,7 @@ pub fn context_get(
 	else {
 		return Result::InvalidContext.into();
 	};
-	let key: JsString = string.into();
-	let Ok(result) = context.global_object().get(key, context) else {
-		return Result::MissingResult.into();
-	};
-	let Some(result_string) = result
-		.to_string(context)
-		.ok()
-		.and_then(|s| s.to_std_string().ok())
-	else {
+	let Some(result_string) = get_js_global(context, &string) else {
 		return Result::MissingResult.into();
 	};
 	env.data_mut().store.store(StoreItem::String(result_string))
@@ -23,6 +23,8 @@ pub fn generate_imports(store: &mut Store, env: &FunctionEnv<WasmEnv>) -> Import
 			"current_date" => Function::new_typed_with_env(store, env, std::current_date),
 			"utc_offset" => Function::new_typed_with_env(store, env, std::utc_offset),
 			"parse_date" => Function::new_typed_with_env(store, env, std::parse_date),
+			"snapshot" => Function::new_typed_with_env(store, env, std::snapshot),
+			"set_clock" => Function::new_typed_with_env(store, env, std::set_clock),
 		},
 		"defaults" => {
 			"get" => Function::new_typed_with_env(store, env, defaults::get),
@@ -60,6 +62,13 @@ pub fn generate_imports(store: &mut Store, env: &FunctionEnv<WasmEnv>) -> Import
 			"has_class" => Function::new_typed_with_env(store, env, html::has_class),
 			"has_attr" => Function::new_typed_with_env(store, env, html::has_attr),
 
+			"child_nodes" => Function::new_typed_with_env(store, env, html::child_nodes),
+			"walk" => Function::new_typed_with_env(store, env, html::walk),
+			"next_sibling_node" => Function::new_typed_with_env(store, env, html::next_sibling_node),
+			"previous_sibling_node" => Function::new_typed_with_env(store, env, html::previous_sibling_node),
+			"node_type" => Function::new_typed_with_env(store, env, html::node_type),
+			"node_value" => Function::new_typed_with_env(store, env, html::node_value),
+
 			"first" => Function::new_typed_with_env(store, env, html::first),
 			"last" => Function::new_typed_with_env(store, env, html::last),
 			"get" => Function::new_typed_with_env(store, env, html::get),
@@ -95,6 +104,7 @@ pub fn generate_imports(store: &mut Store, env: &FunctionEnv<WasmEnv>) -> Import
 			"html" => Function::new_typed_with_env(store, env, net::html),
 
 			"set_rate_limit" => Function::new_typed_with_env(store, env, net::set_rate_limit),
+			"mock_response" => Function::new_typed_with_env(store, env, net::mock_response),
 		},
 	}
 }
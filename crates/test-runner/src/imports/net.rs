@@ -1,16 +1,15 @@
 use crate::{
-	libs::{HtmlDocument, HttpMethod, ImageData, NetRequest, NetResponse, StoreItem},
+	libs::{HttpMethod, MockResponse, NetRequest, SendError, StoreItem},
 	FFIResult, Ptr, Rid, WasmEnv,
 };
-use image::ImageReader;
-use reqwest::header::{HeaderName, HeaderValue, USER_AGENT};
-use scraper::Html;
-use std::{io::Cursor, str::FromStr};
+use reqwest::{
+	header::{HeaderName, HeaderValue},
+	StatusCode,
+};
+use std::str::FromStr;
 use url::Url;
 use wasmer::FunctionEnvMut;
 
-const DEFAULT_USER_AGENT: &str = "Buny/1 CFNetwork/3826.500.131 Darwin/24.5.0";
-
 enum Result {
 	Success,
 	InvalidDescriptor,
@@ -48,17 +47,8 @@ impl From<Result> for i32 {
 }
 
 pub fn init(mut env: FunctionEnvMut<WasmEnv>, method: u8) -> FFIResult {
-	let method = match method {
-		0 => HttpMethod::Get,
-		1 => HttpMethod::Post,
-		2 => HttpMethod::Put,
-		3 => HttpMethod::Head,
-		4 => HttpMethod::Delete,
-		5 => HttpMethod::Patch,
-		6 => HttpMethod::Options,
-		7 => HttpMethod::Connect,
-		8 => HttpMethod::Trace,
-		_ => return Result::InvalidMethod.into(),
+	let Some(method) = HttpMethod::from_code(method.into()) else {
+		return Result::InvalidMethod.into();
 	};
 	let request = NetRequest::new(method);
 	env.data_mut()
@@ -66,63 +56,15 @@ pub fn init(mut env: FunctionEnvMut<WasmEnv>, method: u8) -> FFIResult {
 		.store(StoreItem::Request(Box::new(request)))
 }
 fn common_send(env: &mut FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
-	let Some(request) = env
-		.data_mut()
-		.store
-		.get_mut(rid)
-		.and_then(|item| item.as_request())
-	else {
+	let env = env.data_mut();
+	let Some(request) = env.store.get_mut(rid).and_then(|item| item.as_request()) else {
 		return Result::InvalidDescriptor.into();
 	};
-	// add a default user agent if none is provided
-	if !request.headers.contains_key(USER_AGENT) {
-		let default_ua = HeaderValue::from_static(DEFAULT_USER_AGENT);
-		request.headers.insert(USER_AGENT, default_ua);
-	}
-	let Some(url) = request.url.as_ref() else {
-		return Result::InvalidUrl.into();
-	};
-	let mut builder = reqwest::blocking::Client::new()
-		.request(
-			match request.method {
-				HttpMethod::Get => reqwest::Method::GET,
-				HttpMethod::Post => reqwest::Method::POST,
-				HttpMethod::Put => reqwest::Method::PUT,
-				HttpMethod::Delete => reqwest::Method::DELETE,
-				HttpMethod::Head => reqwest::Method::HEAD,
-				HttpMethod::Patch => reqwest::Method::PATCH,
-				HttpMethod::Options => reqwest::Method::OPTIONS,
-				HttpMethod::Connect => reqwest::Method::CONNECT,
-				HttpMethod::Trace => reqwest::Method::TRACE,
-			},
-			url.to_string(),
-		)
-		.headers(request.headers.clone());
-	if let Some(body) = request.body.take() {
-		builder = builder.body(body);
-	}
-	if let Some(timeout) = request.timeout.take() {
-		let secs = timeout.trunc() as u64;
-		let nanos = ((timeout.fract()) * 1_000_000_000.0).round() as u32;
-		builder = builder.timeout(std::time::Duration::new(secs, nanos));
+	match request.send(&env.mocks) {
+		Ok(()) => Result::Success.into(),
+		Err(SendError::MissingUrl) => Result::InvalidUrl.into(),
+		Err(SendError::RequestFailed) => Result::RequestError.into(),
 	}
-	// make a blocking request with reqwest
-	let Ok(response) = builder.send() else {
-		return Result::RequestError.into();
-	};
-	let url = response.url().clone();
-	let status = response.status();
-	let headers = response.headers().clone();
-	let Ok(bytes) = response.bytes() else {
-		return Result::RequestError.into();
-	};
-	request.response = Some(NetResponse {
-		url,
-		status,
-		headers,
-		data: bytes.into(),
-	});
-	Result::Success.into()
 }
 pub fn send(mut env: FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
 	common_send(&mut env, rid)
@@ -280,26 +222,12 @@ pub fn get_image(mut env: FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
 	let Some(response) = request.response.take() else {
 		return Result::MissingResponse.into();
 	};
-	let data = response.data.clone();
+	let image = response.image();
 	request.response = Some(response);
 
-	let cursor = Cursor::new(data);
-	let Some(rgba_img) = ImageReader::new(cursor)
-		.with_guessed_format()
-		.ok()
-		.and_then(|r| r.decode().ok())
-		.map(|img| img.to_rgb8())
-	else {
+	let Some(image) = image else {
 		return Result::NotAnImage.into();
 	};
-	let width = rgba_img.width() as i32;
-	let height = rgba_img.height() as i32;
-	let data = rgba_img.into_raw();
-	let image = ImageData {
-		data,
-		width,
-		height,
-	};
 	env.data_mut().store.store(StoreItem::ImageData(image))
 }
 pub fn get_status_code(mut env: FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
@@ -357,17 +285,10 @@ pub fn get_header(
 	let Some(response) = request.response.take() else {
 		return Result::MissingResponse.into();
 	};
-	let headers = response.headers.clone();
+	let value = response.header(&name);
 	request.response = Some(response);
 
-	let value = headers
-		.get_all(&name)
-		.iter()
-		.map(|value| value.as_bytes())
-		.filter_map(|bytes| String::from_utf8(bytes.to_vec()).ok())
-		.collect::<Vec<String>>()
-		.join(", ");
-	if value.is_empty() {
+	let Some(value) = value else {
 		return Result::MissingData.into();
 	};
 	env.data_mut().store.store(StoreItem::String(value))
@@ -384,20 +305,56 @@ pub fn html(mut env: FunctionEnvMut<WasmEnv>, rid: Rid) -> FFIResult {
 	let Some(response) = request.response.take() else {
 		return Result::MissingResponse.into();
 	};
-	let data = response.data.clone();
-	let base_uri = response.url.clone();
+	let document = response.html();
 	request.response = Some(response);
-	let text = match String::from_utf8(data) {
-		Ok(s) => s,
-		Err(_) => return Result::InvalidString.into(),
+
+	let Some(document) = document else {
+		return Result::InvalidString.into();
 	};
-	let html = Html::parse_document(&text);
 	env.data_mut()
 		.store
-		.store(StoreItem::HtmlDocument(HtmlDocument {
-			html,
-			base_uri: Some(base_uri),
-		}))
+		.store(StoreItem::HtmlDocument(document))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn mock_response(
+	mut env: FunctionEnvMut<WasmEnv>,
+	method: i32,
+	url_ptr: Ptr,
+	url_len: u32,
+	status: i32,
+	data_ptr: Ptr,
+	data_len: u32,
+) -> FFIResult {
+	let method = match method {
+		-1 => None,
+		method => match HttpMethod::from_code(method) {
+			Some(method) => Some(method),
+			None => return Result::InvalidMethod.into(),
+		},
+	};
+	let Ok(url) = env.data().read_string(&env, url_ptr, url_len) else {
+		return Result::InvalidString.into();
+	};
+	let Ok(url) = Url::parse(&url) else {
+		return Result::InvalidUrl.into();
+	};
+	let Some(status) = u16::try_from(status)
+		.ok()
+		.and_then(|status| StatusCode::from_u16(status).ok())
+	else {
+		return Result::RequestError.into();
+	};
+	let Ok(data) = env.data().read_bytes(&env, data_ptr, data_len) else {
+		return Result::InvalidString.into();
+	};
+	env.data_mut().mocks.push(MockResponse {
+		method,
+		url,
+		status,
+		data,
+	});
+	Result::Success.into()
 }
 
 pub fn set_rate_limit(_env: FunctionEnvMut<WasmEnv>, _permits: i32, _period: i32, _unit: i32) {
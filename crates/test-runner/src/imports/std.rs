@@ -1,4 +1,7 @@
-use crate::{FFIResult, Ptr, Rid, WasmEnv};
+use crate::{
+	libs::{self, format_snapshot, Clock, SnapshotResult},
+	FFIResult, Ptr, Rid, WasmEnv,
+};
 use wasmer::FunctionEnvMut;
 
 enum Result {
@@ -8,6 +11,12 @@ enum Result {
 	FailedMemoryWrite,
 	InvalidString,
 	InvalidDateString,
+	SnapshotMismatch,
+	SnapshotUnavailable,
+	FailedSnapshotWrite,
+	InvalidTimezone,
+	SnapshotMissing,
+	InvalidSnapshotValue,
 }
 
 impl From<Result> for i32 {
@@ -19,6 +28,12 @@ impl From<Result> for i32 {
 			Result::FailedMemoryWrite => -3,
 			Result::InvalidString => -4,
 			Result::InvalidDateString => -5,
+			Result::SnapshotMismatch => -6,
+			Result::SnapshotUnavailable => -7,
+			Result::FailedSnapshotWrite => -8,
+			Result::InvalidTimezone => -9,
+			Result::SnapshotMissing => -10,
+			Result::InvalidSnapshotValue => -11,
 		}
 	}
 }
@@ -50,14 +65,103 @@ pub fn read_buffer(env: FunctionEnvMut<WasmEnv>, rid: Rid, ptr: Ptr, size: u32)
 	}
 }
 
-pub fn current_date(_env: FunctionEnvMut<WasmEnv>) -> f64 {
-	use chrono::Utc;
-	Utc::now().timestamp() as f64
+pub fn current_date(env: FunctionEnvMut<WasmEnv>) -> f64 {
+	env.data().clock.now() as f64
 }
 
-pub fn utc_offset(_env: FunctionEnvMut<WasmEnv>) -> i64 {
-	use chrono::Local;
-	Local::now().offset().utc_minus_local() as i64
+pub fn utc_offset(env: FunctionEnvMut<WasmEnv>) -> i64 {
+	env.data().clock.utc_offset()
+}
+
+pub fn set_clock(
+	mut env: FunctionEnvMut<WasmEnv>,
+	time: i64,
+	timezone_ptr: Ptr,
+	timezone_len: u32,
+	auto_advance: i32,
+) -> FFIResult {
+	let timezone = if timezone_len > 0 {
+		let Ok(timezone) = env.data().read_string(&env, timezone_ptr, timezone_len) else {
+			return Result::InvalidString.into();
+		};
+		let Ok(timezone) = timezone.parse() else {
+			return Result::InvalidTimezone.into();
+		};
+		timezone
+	} else {
+		chrono_tz::UTC
+	};
+	env.data_mut().clock = Clock {
+		time: Some(time),
+		timezone: Some(timezone),
+		auto_advance: auto_advance != 0,
+	};
+	Result::Success.into()
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn snapshot(
+	mut env: FunctionEnvMut<WasmEnv>,
+	name_ptr: Ptr,
+	name_len: u32,
+	expression_ptr: Ptr,
+	expression_len: u32,
+	kind_ptr: Ptr,
+	kind_len: u32,
+	value_ptr: Ptr,
+	value_len: u32,
+) -> FFIResult {
+	let Ok(name) = env.data().read_string(&env, name_ptr, name_len) else {
+		return Result::InvalidString.into();
+	};
+	let Ok(expression) = env.data().read_string(&env, expression_ptr, expression_len) else {
+		return Result::InvalidString.into();
+	};
+	let Ok(kind) = env.data().read_string(&env, kind_ptr, kind_len) else {
+		return Result::InvalidString.into();
+	};
+	let Ok(bytes) = env.data().read_bytes(&env, value_ptr, value_len) else {
+		return Result::FailedMemoryWrite.into();
+	};
+	let version = env.data().struct_version;
+	let Some(value) = buny::with_struct_version(version, || format_snapshot(&kind, &bytes)) else {
+		return Result::InvalidSnapshotValue.into();
+	};
+	let name = (!name.is_empty()).then_some(name.as_str());
+
+	let data = env.data_mut();
+	let Some(snapshots) = data.snapshots.as_mut() else {
+		return Result::SnapshotUnavailable.into();
+	};
+	match snapshots.assert(name, &expression, &value) {
+		Ok(SnapshotResult::Matched) => Result::Success.into(),
+		Ok(SnapshotResult::Created(path)) => {
+			data.write_stdout(&format!("created snapshot {}\n", path.display()));
+			Result::Success.into()
+		}
+		Ok(SnapshotResult::Updated(path)) => {
+			data.write_stdout(&format!("updated snapshot {}\n", path.display()));
+			Result::Success.into()
+		}
+		Ok(SnapshotResult::Mismatched { path, diff }) => {
+			data.write_stdout(&format!(
+				"snapshot {} doesn't match (rerun with --update-snapshots to update it):\n{diff}",
+				path.display()
+			));
+			Result::SnapshotMismatch.into()
+		}
+		Ok(SnapshotResult::Missing(path)) => {
+			data.write_stdout(&format!(
+				"snapshot {} doesn't exist (rerun with --update-snapshots to create it)\n",
+				path.display()
+			));
+			Result::SnapshotMissing.into()
+		}
+		Err(error) => {
+			data.write_stdout(&format!("failed to write snapshot: {error}\n"));
+			Result::FailedSnapshotWrite.into()
+		}
+	}
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -67,8 +171,8 @@ pub fn parse_date(
 	date_len: u32,
 	format_str: Ptr,
 	format_len: u32,
-	_locale_str: Ptr,
-	_locale_len: u32,
+	locale_str: Ptr,
+	locale_len: u32,
 	timezone_str: Ptr,
 	timezone_len: u32,
 ) -> f64 {
@@ -78,7 +182,12 @@ pub fn parse_date(
 	let Ok(format) = env.data().read_string(&env, format_str, format_len) else {
 		return Into::<i32>::into(Result::InvalidString) as f64;
 	};
-	let timezone_string = if timezone_len > 0 {
+	let locale = if locale_len > 0 {
+		env.data().read_string(&env, locale_str, locale_len).ok()
+	} else {
+		None
+	};
+	let timezone = if timezone_len > 0 {
 		env.data()
 			.read_string(&env, timezone_str, timezone_len)
 			.ok()
@@ -86,138 +195,9 @@ pub fn parse_date(
 		None
 	};
 
-	let timezone: chrono_tz::Tz = timezone_string
-		.as_deref()
-		.and_then(|tz_str| tz_str.parse().ok())
-		.unwrap_or(chrono_tz::UTC);
-
-	use chrono::NaiveDateTime;
-
-	let Some(timestamp) =
-		NaiveDateTime::parse_from_str(&string, &swift_date_format_to_chrono(&format))
-			.ok()
-			.and_then(|dt| dt.and_local_timezone(timezone).single())
-			.map(|dt| dt.timestamp() as f64)
-	else {
-		return Into::<i32>::into(Result::InvalidDateString) as f64;
-	};
-	timestamp
-}
-
-fn swift_date_format_to_chrono(format: &str) -> String {
-	let mut result = String::new();
-	let mut chars = format.chars().peekable();
-
-	while let Some(c) = chars.next() {
-		let token = match c {
-			// year
-			'y' => {
-				// yyyy or yy
-				let mut count = 1;
-				while chars.peek() == Some(&'y') {
-					chars.next();
-					count += 1;
-				}
-				if count == 2 {
-					"%y"
-				} else {
-					"%Y"
-				}
-			}
-			// month
-			'M' => {
-				// MM or MMM or MMMM
-				let mut count = 1;
-				while chars.peek() == Some(&'M') {
-					chars.next();
-					count += 1;
-				}
-				match count {
-					1 | 2 => "%m",
-					3 => "%b",
-					_ => "%B",
-				}
-			}
-			// day
-			'd' => {
-				// dd or d
-				while chars.peek() == Some(&'d') {
-					chars.next();
-				}
-				"%d"
-			}
-			// hour
-			'H' => {
-				// HH or H
-				while chars.peek() == Some(&'H') {
-					chars.next();
-				}
-				"%H"
-			}
-			'h' => {
-				// hh or h
-				while chars.peek() == Some(&'h') {
-					chars.next();
-				}
-				"%I"
-			}
-			// minute
-			'm' => {
-				// mm or m
-				while chars.peek() == Some(&'m') {
-					chars.next();
-				}
-				"%M"
-			}
-			// second
-			's' => {
-				// ss or s
-				while chars.peek() == Some(&'s') {
-					chars.next();
-				}
-				"%S"
-			}
-			'a' => "%p", // AM/PM
-			// weekday
-			'E' => {
-				// EEE or EEEE
-				let mut count = 1;
-				while chars.peek() == Some(&'E') {
-					chars.next();
-					count += 1;
-				}
-				if count >= 4 {
-					"%A"
-				} else {
-					"%a"
-				}
-			}
-			// timezone
-			'z' => {
-				let mut count = 1;
-				while chars.peek() == Some(&c) {
-					chars.next();
-					count += 1;
-				}
-				if count >= 4 {
-					"%Z"
-				} else {
-					"%z"
-				}
-			}
-			'Z' => {
-				while chars.peek() == Some(&c) {
-					chars.next();
-				}
-				"%Z"
-			}
-			// literal
-			c => {
-				result.push(c);
-				continue;
-			}
-		};
-		result.push_str(token);
+	let timezone = env.data().clock.resolve_timezone(timezone.as_deref());
+	match libs::parse_date(&string, &format, locale.as_deref(), timezone) {
+		Some(timestamp) => timestamp as f64,
+		None => Into::<i32>::into(Result::InvalidDateString) as f64,
 	}
-	result
 }
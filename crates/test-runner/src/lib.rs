@@ -1,5 +1,8 @@
 #![doc = include_str!("../README.md")]
+mod host;
 pub mod imports;
 pub mod libs;
 
+pub use host::SourceHost;
+
 pub use libs::{FFIResult, Ptr, Rid, WasmEnv};
@@ -0,0 +1,103 @@
+use buny::{Chapter, ContentBlock, Novel, NovelPageResult};
+use std::collections::HashSet;
+use url::Url;
+
+/// Returns whether a url is an absolute http(s) url.
+pub fn is_absolute_url(url: &str) -> bool {
+	Url::parse(url).is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+}
+
+/// Returns the problems with a novel entry, e.g. a missing key or title.
+pub fn check_novel_entry(novel: &Novel) -> Vec<String> {
+	let mut problems = Vec::new();
+	if novel.key.is_empty() {
+		problems.push(format!("novel `{}` has no key", novel.title));
+	}
+	if novel.title.is_empty() {
+		problems.push(format!("novel {} has no title", novel.key));
+	}
+	for (name, url) in [("url", &novel.url), ("cover", &novel.cover)] {
+		if let Some(url) = url.as_ref().filter(|url| !is_absolute_url(url)) {
+			problems.push(format!("novel {} has a relative {name}: {url}", novel.key));
+		}
+	}
+	problems
+}
+
+/// Returns the problems with a page of novels, e.g. from a search or listing.
+///
+/// The page should contain entries with unique keys, and can't have a next page if it's empty.
+pub fn check_novel_page(page: &NovelPageResult) -> Vec<String> {
+	let mut problems = Vec::new();
+	if page.entries.is_empty() && page.has_next_page {
+		problems.push("page has no entries, but has a next page".into());
+	}
+	let mut keys = HashSet::new();
+	for novel in &page.entries {
+		problems.extend(check_novel_entry(novel));
+		if !novel.key.is_empty() && !keys.insert(&novel.key) {
+			problems.push(format!("duplicate novel key {}", novel.key));
+		}
+	}
+	problems
+}
+
+/// Returns the problems with a list of chapters.
+///
+/// Chapter keys should be unique, chapter urls should be absolute, and chapter numbers should be
+/// consistently ascending or descending within each volume.
+pub fn check_chapters(chapters: &[Chapter]) -> Vec<String> {
+	let mut problems = Vec::new();
+	let mut keys = HashSet::new();
+	for chapter in chapters {
+		if chapter.key.is_empty() {
+			problems.push("chapter has no key".into());
+		} else if !keys.insert(&chapter.key) {
+			problems.push(format!("duplicate chapter key {}", chapter.key));
+		}
+		if let Some(url) = chapter.url.as_ref().filter(|url| !is_absolute_url(url)) {
+			problems.push(format!("chapter {} has a relative url: {url}", chapter.key));
+		}
+	}
+
+	// compare each numbered chapter with the previous one in the same volume
+	let mut ascending = None;
+	let mut previous: Option<&Chapter> = None;
+	for chapter in chapters.iter().filter(|chapter| !chapter.is_volume_header) {
+		let Some(number) = chapter.chapter_number else {
+			continue;
+		};
+		if let Some(last) = previous.filter(|last| last.volume_number == chapter.volume_number) {
+			let last_number = last.chapter_number.unwrap_or_default();
+			if number != last_number {
+				let is_ascending = number > last_number;
+				match ascending {
+					None => ascending = Some(is_ascending),
+					Some(ascending) if ascending != is_ascending => problems.push(format!(
+						"chapter numbers aren't monotonic: {} ({last_number}) is followed by {} ({number})",
+						last.key, chapter.key
+					)),
+					_ => {}
+				}
+			}
+		}
+		previous = Some(chapter);
+	}
+	problems
+}
+
+/// Returns the problems with the content of a chapter, which should have some text.
+pub fn check_content(blocks: &[ContentBlock]) -> Vec<String> {
+	let has_text = blocks.iter().any(|block| match block {
+		ContentBlock::BlockQuote(text)
+		| ContentBlock::Paragraph(text, _)
+		| ContentBlock::StyledParagraph(text, _) => !text.trim().is_empty(),
+		ContentBlock::Table(rows) => rows.iter().flatten().any(|cell| !cell.trim().is_empty()),
+		ContentBlock::Divider => false,
+	});
+	if has_text {
+		Vec::new()
+	} else {
+		vec![format!("content has no text ({} blocks)", blocks.len())]
+	}
+}
@@ -1,15 +1,22 @@
 use anyhow::{anyhow, Result};
 use wasmer::*;
 
-mod defaults;
-mod html;
-mod net;
-mod store;
-
-pub use defaults::*;
-pub use html::*;
-pub use net::*;
-pub use store::*;
+mod contract;
+mod result;
+
+pub use contract::*;
+pub use result::*;
+
+// the data types of the runner are shared with the native imports of the buny crate
+pub use buny::native::{
+	diff_lines, escape_html, eval_js, format_chapter, format_comment, format_content_block,
+	format_home_layout, format_novel, format_novel_group, format_novel_page,
+	format_reading_progress, format_search_suggestion, format_snapshot, format_unlock_option,
+	format_unlock_result, get_js_global, parse_date, unescape_html, Clock, DefaultKind,
+	DefaultValue, GlobalStore, HtmlDocument, HtmlElement, HtmlElementList, HtmlNode, HtmlNodeList,
+	HttpMethod, ImageData, MockResponse, NetRequest, NetResponse, SendError, SnapshotResult,
+	Snapshots, StoreItem, UserDefaults,
+};
 
 /// A standard descriptor, used for data exchange between the runner and the source (reference id).
 ///
@@ -30,7 +37,16 @@ pub struct WasmEnv {
 	pub memory: Option<Memory>,
 	pub store: GlobalStore,
 	pub defaults: UserDefaults,
+	pub mocks: Vec<MockResponse>,
+	/// The clock returned by the date imports, which can be replaced with a virtual clock.
+	pub clock: Clock,
+	/// The snapshots of the running test, if any.
+	pub snapshots: Option<Snapshots>,
 	pub stdout: String,
+	/// The error buffer encoding version used by the source.
+	pub error_version: i32,
+	/// The layout version of the novels and chapters exchanged with the source.
+	pub struct_version: i32,
 }
 
 impl WasmEnv {
@@ -39,7 +55,12 @@ impl WasmEnv {
 			memory: None,
 			store: GlobalStore::new(),
 			defaults: UserDefaults::new(),
+			mocks: Vec::new(),
+			clock: Clock::default(),
+			snapshots: None,
 			stdout: String::new(),
+			error_version: 0,
+			struct_version: 0,
 		}
 	}
 
@@ -107,6 +128,31 @@ impl WasmEnv {
 		self.read_bytes(store, ptr + 8, len - 8)
 	}
 
+	/// Reads the value returned by a source function.
+	///
+	/// Negative values and error buffers are decoded into a [SourceError], and the
+	/// serialized data of result buffers is returned otherwise.
+	pub fn read_result(
+		&self,
+		store: &(impl AsStoreRef + ?Sized),
+		value: FFIResult,
+	) -> Result<std::result::Result<Vec<u8>, SourceError>> {
+		if value < 0 {
+			return Ok(Err(SourceError::decode(value, None)));
+		}
+		let ptr = value as Ptr;
+		let code = self.read_u32(store, ptr)? as i32;
+		if code < 0 {
+			let len = self.read_u32(store, ptr + 8)?;
+			let buffer = self.read_bytes(store, ptr, len)?;
+			let error = SourceError::decode_buffer(&buffer, self.error_version)
+				.ok_or(anyhow!("Invalid error buffer"))?;
+			Ok(Err(error))
+		} else {
+			Ok(Ok(self.read_item_bytes(store, ptr)?))
+		}
+	}
+
 	pub fn read_string(
 		&self,
 		store: &(impl AsStoreRef + ?Sized),
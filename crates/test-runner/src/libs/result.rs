@@ -0,0 +1,123 @@
+use buny::ErrorInfo;
+use std::fmt::Display;
+
+/// The kind of an error returned by a source function, from its error code.
+///
+/// See the `BunyError` documentation in the buny crate for the wire encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SourceErrorKind {
+	Message(String),
+	Unimplemented,
+	RequestError,
+	LoginRequired,
+	NotFound,
+	ContentLocked,
+	Cloudflare {
+		url: String,
+	},
+	RateLimited {
+		retry_after: Option<i32>,
+	},
+	/// An error code that isn't recognized.
+	Unknown(i32),
+}
+
+/// An error returned by a source function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceError {
+	pub kind: SourceErrorKind,
+	/// The details of the error, if the source returned an error buffer.
+	pub info: Option<ErrorInfo>,
+}
+
+impl SourceError {
+	/// Size of the header of an error buffer (code, capacity, and length).
+	pub const HEADER_SIZE: usize = 12;
+
+	/// Decodes an error from an error code and the payload of a version 1 error buffer, if any.
+	pub fn decode(code: i32, payload: Option<&[u8]>) -> Self {
+		let info = payload.and_then(|payload| postcard::from_bytes::<ErrorInfo>(payload).ok());
+		let kind = match code {
+			-1 => match &info {
+				Some(info) if info.kind == "Message" => {
+					SourceErrorKind::Message(info.message.clone())
+				}
+				_ => SourceErrorKind::Unknown(code),
+			},
+			-2 => SourceErrorKind::Unimplemented,
+			-3 => SourceErrorKind::RequestError,
+			-4 => SourceErrorKind::LoginRequired,
+			-5 => SourceErrorKind::NotFound,
+			-6 => SourceErrorKind::ContentLocked,
+			-7 => SourceErrorKind::Cloudflare {
+				url: info
+					.as_ref()
+					.and_then(|info| info.url.clone())
+					.unwrap_or_default(),
+			},
+			-8 => SourceErrorKind::RateLimited {
+				retry_after: info.as_ref().and_then(|info| info.retry_after),
+			},
+			code => SourceErrorKind::Unknown(code),
+		};
+		Self { kind, info }
+	}
+
+	/// Decodes an error from a complete error buffer, including its header, using the
+	/// encoding version of the source.
+	///
+	/// Returns `None` if the buffer doesn't start with a negative error code.
+	pub fn decode_buffer(buffer: &[u8], version: i32) -> Option<Self> {
+		let code = i32::from_le_bytes(buffer.get(0..4)?.try_into().ok()?);
+		if code >= 0 {
+			return None;
+		}
+		let len = i32::from_le_bytes(buffer.get(8..12)?.try_into().ok()?) as usize;
+		let payload = buffer.get(Self::HEADER_SIZE..len.max(Self::HEADER_SIZE))?;
+		if version >= 1 {
+			Some(Self::decode(code, Some(payload)))
+		} else if code == -1 {
+			// the first version only passes the message of message errors
+			let message = String::from_utf8_lossy(payload).into_owned();
+			Some(SourceErrorKind::Message(message).into())
+		} else {
+			Some(Self::decode(code, None))
+		}
+	}
+}
+
+impl From<SourceErrorKind> for SourceError {
+	fn from(kind: SourceErrorKind) -> Self {
+		Self { kind, info: None }
+	}
+}
+
+impl Display for SourceErrorKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Message(message) => write!(f, "{message}"),
+			Self::Unimplemented => write!(f, "unimplemented"),
+			Self::RequestError => write!(f, "request error"),
+			Self::LoginRequired => write!(f, "login required"),
+			Self::NotFound => write!(f, "not found"),
+			Self::ContentLocked => write!(f, "content locked"),
+			Self::Cloudflare { url } => write!(f, "cloudflare challenge at {url}"),
+			Self::RateLimited {
+				retry_after: Some(seconds),
+			} => write!(f, "rate limited, retry after {seconds}s"),
+			Self::RateLimited { retry_after: None } => write!(f, "rate limited"),
+			Self::Unknown(code) => write!(f, "unknown error ({code})"),
+		}
+	}
+}
+
+impl Display for SourceError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match &self.info {
+			Some(info) => write!(f, "{info}"),
+			None => write!(f, "{}", self.kind),
+		}
+	}
+}
+
+impl std::error::Error for SourceError {}
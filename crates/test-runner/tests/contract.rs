@@ -0,0 +1,100 @@
+use buny::{Chapter, ContentBlock, Novel, NovelPageResult};
+use buny_test_runner::libs::{check_chapters, check_content, check_novel_page, is_absolute_url};
+
+fn chapter(key: &str, number: f32) -> Chapter {
+	Chapter {
+		key: key.into(),
+		chapter_number: Some(number),
+		..Default::default()
+	}
+}
+
+#[test]
+fn absolute_urls() {
+	assert!(is_absolute_url("https://example.com/novel"));
+	assert!(!is_absolute_url("/novel"));
+	assert!(!is_absolute_url("ftp://example.com/novel"));
+}
+
+#[test]
+fn novel_pages() {
+	let novel = Novel {
+		key: "/novel".into(),
+		title: "Title".into(),
+		url: Some("https://example.com/novel".into()),
+		..Default::default()
+	};
+	let page = NovelPageResult {
+		entries: vec![novel.clone()],
+		has_next_page: true,
+	};
+	assert!(check_novel_page(&page).is_empty());
+
+	let page = NovelPageResult {
+		entries: vec![
+			novel.clone(),
+			Novel {
+				cover: Some("/cover.jpg".into()),
+				..novel
+			},
+		],
+		has_next_page: false,
+	};
+	assert_eq!(
+		check_novel_page(&page),
+		[
+			"novel /novel has a relative cover: /cover.jpg",
+			"duplicate novel key /novel"
+		]
+	);
+
+	let page = NovelPageResult {
+		entries: Vec::new(),
+		has_next_page: true,
+	};
+	assert_eq!(
+		check_novel_page(&page),
+		["page has no entries, but has a next page"]
+	);
+}
+
+#[test]
+fn chapter_order() {
+	let descending = [chapter("3", 3.0), chapter("2", 2.0), chapter("1", 1.0)];
+	assert!(check_chapters(&descending).is_empty());
+
+	// numbers restart for each volume
+	let volumes = [
+		Chapter {
+			volume_number: Some(1.0),
+			..chapter("1", 1.0)
+		},
+		Chapter {
+			volume_number: Some(1.0),
+			..chapter("2", 2.0)
+		},
+		Chapter {
+			volume_number: Some(2.0),
+			..chapter("3", 1.0)
+		},
+	];
+	assert!(check_chapters(&volumes).is_empty());
+
+	let unordered = [chapter("1", 1.0), chapter("3", 3.0), chapter("2", 2.0)];
+	assert_eq!(
+		check_chapters(&unordered),
+		["chapter numbers aren't monotonic: 3 (3) is followed by 2 (2)"]
+	);
+
+	let duplicate = [chapter("1", 1.0), chapter("1", 2.0)];
+	assert_eq!(check_chapters(&duplicate), ["duplicate chapter key 1"]);
+}
+
+#[test]
+fn content() {
+	assert!(check_content(&[ContentBlock::paragraph("Text", None)]).is_empty());
+	assert_eq!(
+		check_content(&[ContentBlock::divider(), ContentBlock::paragraph(" ", None)]),
+		["content has no text (2 blocks)"]
+	);
+}
@@ -0,0 +1,162 @@
+use buny::{
+	imports::{error::BunyError, net::RequestError},
+	ERROR_VERSION,
+};
+use buny_test_runner::{
+	libs::{SourceError, SourceErrorKind},
+	WasmEnv,
+};
+use wasmer::{Memory, MemoryType, Store};
+
+fn decode(error: BunyError) -> SourceError {
+	let buffer = error.encode(ERROR_VERSION).expect("missing error buffer");
+	SourceError::decode_buffer(&buffer, ERROR_VERSION).expect("invalid error buffer")
+}
+
+#[test]
+fn plain_codes() {
+	assert_eq!(
+		SourceError::decode(-2, None),
+		SourceErrorKind::Unimplemented.into()
+	);
+	assert_eq!(
+		SourceError::decode(-5, None),
+		SourceErrorKind::NotFound.into()
+	);
+	assert_eq!(
+		SourceError::decode(-1, None),
+		SourceErrorKind::Unknown(-1).into()
+	);
+}
+
+#[test]
+fn kinds() {
+	assert_eq!(
+		decode(BunyError::Unimplemented).kind,
+		SourceErrorKind::Unimplemented
+	);
+	assert_eq!(
+		decode(BunyError::LoginRequired).kind,
+		SourceErrorKind::LoginRequired
+	);
+	assert_eq!(decode(BunyError::NotFound).kind, SourceErrorKind::NotFound);
+	assert_eq!(
+		decode(BunyError::ContentLocked).kind,
+		SourceErrorKind::ContentLocked
+	);
+	assert_eq!(
+		decode(BunyError::DeserializeError).kind,
+		SourceErrorKind::Unknown(-1)
+	);
+	assert_eq!(
+		decode(BunyError::message("Something went wrong")).kind,
+		SourceErrorKind::Message("Something went wrong".into())
+	);
+	assert_eq!(
+		decode(BunyError::cloudflare("https://example.com/novel")).kind,
+		SourceErrorKind::Cloudflare {
+			url: "https://example.com/novel".into()
+		}
+	);
+	assert_eq!(
+		decode(BunyError::RateLimited {
+			retry_after: Some(60)
+		})
+		.kind,
+		SourceErrorKind::RateLimited {
+			retry_after: Some(60)
+		}
+	);
+	assert_eq!(
+		decode(BunyError::RateLimited { retry_after: None }).kind,
+		SourceErrorKind::RateLimited { retry_after: None }
+	);
+}
+
+#[test]
+fn details() {
+	let error = decode(
+		BunyError::from(RequestError::InvalidUrl)
+			.with_request(Some("https://example.com/novel".into()), None),
+	);
+	assert_eq!(error.kind, SourceErrorKind::RequestError);
+	let info = error.info.as_ref().expect("missing error info");
+	assert_eq!(info.kind, "RequestError");
+	assert_eq!(info.sources, ["invalid url"]);
+	assert_eq!(info.url.as_deref(), Some("https://example.com/novel"));
+	assert_eq!(
+		error.to_string(),
+		"RequestError: request error\ncaused by: invalid url\nurl: https://example.com/novel"
+	);
+
+	let error = decode(BunyError::NotFound.with_request(None, Some(404)));
+	assert_eq!(error.kind, SourceErrorKind::NotFound);
+	assert_eq!(error.info.and_then(|info| info.status), Some(404));
+}
+
+#[test]
+fn first_version() {
+	// only message errors have a buffer, with the message as the payload
+	let buffer = BunyError::message("Something went wrong")
+		.encode(0)
+		.expect("missing error buffer");
+	assert_eq!(buffer[0..4], (-1i32).to_le_bytes());
+	assert_eq!(&buffer[12..], b"Something went wrong");
+	assert_eq!(
+		SourceError::decode_buffer(&buffer, 0),
+		Some(SourceErrorKind::Message("Something went wrong".into()).into())
+	);
+
+	assert_eq!(BunyError::Unimplemented.encode(0), None);
+	assert_eq!(BunyError::DeserializeError.encode(0), None);
+	assert_eq!(
+		BunyError::from(RequestError::InvalidUrl)
+			.with_request(None, Some(404))
+			.encode(0),
+		None
+	);
+}
+
+#[test]
+fn result_buffer() {
+	// result buffers start with their positive length
+	let mut buffer = 12i32.to_le_bytes().to_vec();
+	buffer.extend_from_slice(&[0; 8]);
+	assert_eq!(SourceError::decode_buffer(&buffer, ERROR_VERSION), None);
+}
+
+#[test]
+fn read_result() {
+	let mut store = Store::default();
+	let memory = Memory::new(&mut store, MemoryType::new(1, None, false)).unwrap();
+	let env = WasmEnv {
+		memory: Some(memory.clone()),
+		error_version: ERROR_VERSION,
+		..WasmEnv::new()
+	};
+
+	// plain error codes aren't pointers
+	let error = env.read_result(&store, -4).unwrap().unwrap_err();
+	assert_eq!(error.kind, SourceErrorKind::LoginRequired);
+
+	let buffer = BunyError::RateLimited {
+		retry_after: Some(5),
+	}
+	.encode(env.error_version)
+	.unwrap();
+	memory.view(&store).write(64, &buffer).unwrap();
+	let error = env.read_result(&store, 64).unwrap().unwrap_err();
+	assert_eq!(
+		error.kind,
+		SourceErrorKind::RateLimited {
+			retry_after: Some(5)
+		}
+	);
+
+	// result buffers have their length and capacity followed by the data
+	let mut buffer = 12i32.to_le_bytes().to_vec();
+	buffer.extend_from_slice(&12i32.to_le_bytes());
+	buffer.extend_from_slice(&[1, 2, 3, 4]);
+	memory.view(&store).write(256, &buffer).unwrap();
+	assert_eq!(env.read_result(&store, 256).unwrap().unwrap(), [1, 2, 3, 4]);
+}
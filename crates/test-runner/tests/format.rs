@@ -0,0 +1,257 @@
+use buny::{
+	Chapter, Comment, ContentBlock, HomeComponent, HomeComponentValue, HomeLayout, Link, LinkValue,
+	Listing, Novel, NovelGroup, NovelPageResult, NovelStatus, ParagraphStyle, ReadingProgress,
+	SearchSuggestion, TranslationStatus, UnlockKind, UnlockOption, UnlockResult,
+};
+use buny_test_runner::libs::{
+	format_chapter, format_comment, format_content_block, format_home_layout, format_novel,
+	format_novel_group, format_novel_page, format_reading_progress, format_search_suggestion,
+	format_snapshot, format_unlock_option, format_unlock_result,
+};
+
+#[test]
+fn novel_fields() {
+	let novel = Novel {
+		key: "/novel".into(),
+		title: "Title".into(),
+		alt_titles: Some(vec!["Alt".into(), "Other".into()]),
+		artists: Some(vec!["Artist".into()]),
+		status: NovelStatus::Ongoing,
+		translation_status: TranslationStatus::MachineTranslated,
+		original_language: Some("zh".into()),
+		rating: Some(4.5),
+		rating_count: Some(10),
+		chapter_count: Some(120),
+		..Default::default()
+	};
+	assert_eq!(
+		format_novel(&novel),
+		"key: /novel\n\
+		title: Title\n\
+		alt titles: Alt, Other\n\
+		artists: Artist\n\
+		status: Ongoing\n\
+		translation: MachineTranslated\n\
+		original language: zh\n\
+		rating: 4.5 (10 ratings)\n\
+		chapters: 120\n"
+	);
+}
+
+#[test]
+fn chapter_fields() {
+	let chapter = Chapter {
+		key: "/novel/2".into(),
+		title: Some("Title".into()),
+		chapter_number: Some(2.0),
+		locked: true,
+		unlock_cost: Some(5),
+		word_count: Some(2400),
+		..Default::default()
+	};
+	assert_eq!(
+		format_chapter(&chapter),
+		"Ch. 2 Title (/novel/2) [locked, 5] [2400 words]"
+	);
+
+	let header = Chapter {
+		key: "volume-1".into(),
+		volume_number: Some(1.0),
+		is_volume_header: true,
+		..Default::default()
+	};
+	assert_eq!(format_chapter(&header), "[volume] Vol. 1 (volume-1)");
+}
+
+#[test]
+fn provider_results() {
+	let option = UnlockOption {
+		id: "coins".into(),
+		title: "Unlock for 5 coins".into(),
+		kind: UnlockKind::Coins,
+		cost: Some(5),
+		balance: Some(12),
+		..Default::default()
+	};
+	let comment = Comment {
+		key: "42".into(),
+		author: "Reader".into(),
+		body_markdown: "Thanks for the chapter!\n**Great** cliffhanger".into(),
+		likes: Some(3),
+		replies_count: Some(1),
+		..Default::default()
+	};
+	let group = NovelGroup {
+		title: "Same author".into(),
+		entries: vec![Novel {
+			key: "/other".into(),
+			title: "Other".into(),
+			..Default::default()
+		}],
+	};
+	let suggestion = SearchSuggestion {
+		title: "Title".into(),
+		key: "/novel".into(),
+		cover: Some("https://example.com/cover.jpg".into()),
+	};
+	let progress = ReadingProgress {
+		chapter_key: "/novel/3".into(),
+		position: 0.25,
+		updated_at: Some(1700000000),
+	};
+
+	let cases = [
+		(
+			format_unlock_option(&option),
+			"Unlock for 5 coins (Coins, coins) [cost 5] [balance 12]",
+		),
+		(
+			format_unlock_result(&UnlockResult::Scheduled { available_at: 100 }),
+			"unlocks at 100",
+		),
+		(
+			format_comment(&comment),
+			"Reader (42) [3 likes] [1 replies]\n  Thanks for the chapter!\n  **Great** cliffhanger",
+		),
+		(
+			format_novel_group(&group),
+			"Same author (1 entries)\n  Other (/other)",
+		),
+		(
+			format_search_suggestion(&suggestion),
+			"Title (/novel) [cover: https://example.com/cover.jpg]",
+		),
+		(
+			format_reading_progress(&progress),
+			"/novel/3 at 25% [updated 1700000000]",
+		),
+	];
+	for (output, expected) in cases {
+		assert_eq!(output, expected);
+	}
+}
+
+#[test]
+fn novel_page() {
+	let page = NovelPageResult {
+		entries: vec![Novel {
+			key: "/novel".into(),
+			title: "Title".into(),
+			..Default::default()
+		}],
+		has_next_page: true,
+	};
+	assert_eq!(
+		format_novel_page(&page),
+		"Title (/novel)\n1 entries, has next page"
+	);
+}
+
+#[test]
+fn content_blocks() {
+	assert_eq!(
+		format_content_block(&ContentBlock::block_quote("one\ntwo")),
+		"> one\n> two"
+	);
+	assert_eq!(
+		format_content_block(&ContentBlock::table(vec![vec!["a".into(), "b".into()]])),
+		"| a | b |"
+	);
+	assert_eq!(format_content_block(&ContentBlock::divider()), "---");
+	assert_eq!(
+		format_content_block(&ContentBlock::styled_paragraph(
+			"text",
+			ParagraphStyle::centered()
+		)),
+		"[center] text"
+	);
+	assert_eq!(
+		format_content_block(&ContentBlock::styled_paragraph(
+			"text",
+			ParagraphStyle::default()
+		)),
+		"text"
+	);
+}
+
+#[test]
+fn home_layout() {
+	let layout = HomeLayout {
+		components: vec![
+			HomeComponent {
+				title: Some("Popular".into()),
+				subtitle: None,
+				value: HomeComponentValue::Scroller {
+					entries: vec![Novel {
+						key: "/novel".into(),
+						title: "Title".into(),
+						..Default::default()
+					}],
+					auto_scroll_interval: None,
+					listing: None,
+					size: 0,
+				},
+			},
+			HomeComponent {
+				title: None,
+				subtitle: Some("More".into()),
+				value: HomeComponentValue::Links(vec![Link {
+					title: "Latest".into(),
+					value: Some(LinkValue::Listing(Listing {
+						id: "latest".into(),
+						..Default::default()
+					})),
+					..Default::default()
+				}]),
+			},
+		],
+	};
+	assert_eq!(
+		format_home_layout(&layout),
+		"Popular [scroller]\n  - Title (/novel)\n[links]\n  More\n  - Latest (listing latest)"
+	);
+}
+
+#[test]
+fn snapshot_values() {
+	let chapters = vec![
+		Chapter {
+			key: "/1".into(),
+			chapter_number: Some(1.0),
+			..Default::default()
+		},
+		Chapter {
+			key: "/2".into(),
+			chapter_number: Some(2.0),
+			..Default::default()
+		},
+	];
+	let bytes = postcard::to_allocvec(&chapters).unwrap();
+	assert_eq!(
+		format_snapshot("Vec<Chapter>", &bytes).as_deref(),
+		Some("Ch. 1 (/1)\nCh. 2 (/2)")
+	);
+
+	let blocks = vec![
+		ContentBlock::Paragraph("First".into(), None),
+		ContentBlock::Divider,
+	];
+	let bytes = postcard::to_allocvec(&blocks).unwrap();
+	assert_eq!(
+		format_snapshot("Vec<ContentBlock>", &bytes),
+		Some(
+			blocks
+				.iter()
+				.map(format_content_block)
+				.collect::<Vec<_>>()
+				.join("\n\n")
+		)
+	);
+
+	let bytes = postcard::to_allocvec("text").unwrap();
+	assert_eq!(format_snapshot("String", &bytes).as_deref(), Some("text"));
+
+	// unknown types and values that don't match the type can't be formatted
+	assert_eq!(format_snapshot("Listing", &bytes), None);
+	assert_eq!(format_snapshot("Novel", &[]), None);
+}
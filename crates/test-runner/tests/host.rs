@@ -0,0 +1,36 @@
+use buny::{Listing, NovelPageResult};
+use buny_test_runner::{libs::SourceError, SourceHost};
+
+// returns an empty page of novels from search, and an error from listings
+const SOURCE: &str = r#"
+(module
+	(memory (export "memory") 1)
+	(data (i32.const 16) "\0a\00\00\00\0a\00\00\00\00\00")
+	(func (export "start"))
+	(func (export "free_result") (param i32))
+	(func (export "get_search_novel_list") (param i32 i32 i32) (result i32)
+		(i32.const 16))
+	(func (export "get_novel_list") (param i32 i32) (result i32)
+		(i32.const -1))
+)
+"#;
+
+#[test]
+fn arguments_are_removed() {
+	let mut host = SourceHost::new(SOURCE.as_bytes()).unwrap();
+
+	let page = host.search(Some("query"), 1, &[]).unwrap();
+	assert_eq!(page, NovelPageResult::default());
+	assert!(host.env().store.get(1).is_none());
+	assert!(host.env().store.get(2).is_none());
+
+	// arguments are also removed when the source returns an error
+	let error = host.novel_list(&Listing::default(), 1).unwrap_err();
+	assert!(error.downcast_ref::<SourceError>().is_some());
+	assert!(host.env().store.get(1).is_none());
+
+	// and when the function doesn't exist
+	let rid = host.store_string("key");
+	assert!(host.home().is_err());
+	assert!(host.env().store.get(rid).is_none());
+}
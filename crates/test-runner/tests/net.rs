@@ -0,0 +1,67 @@
+use buny_test_runner::{
+	imports,
+	libs::{MockResponse, WasmEnv},
+};
+use reqwest::StatusCode;
+use url::Url;
+use wasmer::{FunctionEnv, Instance, Module, Store};
+
+// sends a GET request, copies the response data to offset 1024 and returns the status code
+const SOURCE: &str = r#"
+(module
+	(import "net" "init" (func $init (param i32) (result i32)))
+	(import "net" "set_url" (func $set_url (param i32 i32 i32) (result i32)))
+	(import "net" "send" (func $send (param i32) (result i32)))
+	(import "net" "get_status_code" (func $get_status_code (param i32) (result i32)))
+	(import "net" "data_len" (func $data_len (param i32) (result i32)))
+	(import "net" "read_data" (func $read_data (param i32 i32 i32) (result i32)))
+	(memory (export "memory") 1)
+	(data (i32.const 0) "https://example.com/api/library?page=1")
+	(func (export "fetch") (result i32)
+		(local $rid i32)
+		(local $result i32)
+		(local.set $rid (call $init (i32.const 0)))
+		(drop (call $set_url (local.get $rid) (i32.const 0) (i32.const 38)))
+		(local.set $result (call $send (local.get $rid)))
+		(if (i32.lt_s (local.get $result) (i32.const 0))
+			(then (return (local.get $result))))
+		(i32.store (i32.const 1020) (call $data_len (local.get $rid)))
+		(drop (call $read_data (local.get $rid) (i32.const 1024) (i32.load (i32.const 1020))))
+		(call $get_status_code (local.get $rid)))
+)
+"#;
+
+#[test]
+fn mock_response() {
+	let mut store = Store::default();
+	let module = Module::new(&store, SOURCE).unwrap();
+	let env = FunctionEnv::new(
+		&mut store,
+		WasmEnv {
+			mocks: vec![MockResponse {
+				method: None,
+				url: Url::parse("https://example.com/api/library?page=1").unwrap(),
+				status: StatusCode::UNAUTHORIZED,
+				data: br#"{"error":"login required"}"#.to_vec(),
+			}],
+			..WasmEnv::new()
+		},
+	);
+	let imports = imports::generate_imports(&mut store, &env);
+	let instance = Instance::new(&mut store, &module, &imports).unwrap();
+	let memory = instance.exports.get_memory("memory").unwrap().clone();
+	env.as_mut(&mut store).memory = Some(memory);
+
+	let status = instance
+		.exports
+		.get_typed_function::<(), i32>(&store, "fetch")
+		.unwrap()
+		.call(&mut store)
+		.unwrap();
+	assert_eq!(status, 401);
+
+	let env = env.as_ref(&store);
+	let len = env.read_u32(&store, 1020).unwrap();
+	let data = env.read_bytes(&store, 1024, len).unwrap();
+	assert_eq!(data, br#"{"error":"login required"}"#);
+}
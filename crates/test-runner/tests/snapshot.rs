@@ -0,0 +1,107 @@
+use buny_test_runner::libs::{diff_lines, SnapshotResult, Snapshots};
+use std::path::PathBuf;
+
+// a fresh directory for the snapshots of a test
+fn snapshot_dir(name: &str) -> PathBuf {
+	let dir = std::env::temp_dir().join(format!("buny-snapshots-{}-{name}", std::process::id()));
+	_ = std::fs::remove_dir_all(&dir);
+	dir
+}
+
+#[test]
+fn paths() {
+	let mut snapshots = Snapshots::new("snapshots", "test::search", false);
+	assert_eq!(
+		snapshots.path(None),
+		PathBuf::from("snapshots/test__search.snap")
+	);
+	assert_eq!(
+		snapshots.path(Some("first entry")),
+		PathBuf::from("snapshots/test__search@first_entry.snap")
+	);
+	assert_eq!(
+		snapshots.path(None),
+		PathBuf::from("snapshots/test__search-2.snap")
+	);
+}
+
+#[test]
+fn create_and_compare() {
+	let dir = snapshot_dir("compare");
+	let path = dir.join("test.snap");
+
+	let mut snapshots = Snapshots::new(&dir, "test", false);
+	snapshots.create_missing = true;
+	assert_eq!(
+		snapshots
+			.assert(None, "novel", "Novel {\n    key: \"1\",\n}")
+			.unwrap(),
+		SnapshotResult::Created(path.clone())
+	);
+	assert_eq!(
+		std::fs::read_to_string(&path).unwrap(),
+		"---\nexpression: novel\n---\nNovel {\n    key: \"1\",\n}\n"
+	);
+
+	let mut snapshots = Snapshots::new(&dir, "test", false);
+	assert_eq!(
+		snapshots
+			.assert(None, "novel", "Novel {\n    key: \"1\",\n}")
+			.unwrap(),
+		SnapshotResult::Matched
+	);
+
+	let mut snapshots = Snapshots::new(&dir, "test", false);
+	assert_eq!(
+		snapshots
+			.assert(None, "novel", "Novel {\n    key: \"2\",\n}")
+			.unwrap(),
+		SnapshotResult::Mismatched {
+			path: path.clone(),
+			diff: "  Novel {\n-     key: \"1\",\n+     key: \"2\",\n  }\n".into()
+		}
+	);
+
+	let mut snapshots = Snapshots::new(&dir, "test", true);
+	assert_eq!(
+		snapshots
+			.assert(None, "novel", "Novel {\n    key: \"2\",\n}")
+			.unwrap(),
+		SnapshotResult::Updated(path.clone())
+	);
+	assert!(std::fs::read_to_string(&path)
+		.unwrap()
+		.contains("key: \"2\""));
+
+	_ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn missing_in_ci() {
+	let dir = snapshot_dir("missing");
+	let path = dir.join("test.snap");
+
+	// missing snapshots fail unless they're created or updated
+	let mut snapshots = Snapshots::new(&dir, "test", false);
+	snapshots.create_missing = false;
+	assert_eq!(
+		snapshots.assert(None, "novel", "Novel").unwrap(),
+		SnapshotResult::Missing(path.clone())
+	);
+	assert!(!path.exists());
+
+	let mut snapshots = Snapshots::new(&dir, "test", true);
+	snapshots.create_missing = false;
+	assert_eq!(
+		snapshots.assert(None, "novel", "Novel").unwrap(),
+		SnapshotResult::Created(path.clone())
+	);
+	assert!(path.exists());
+
+	_ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn line_diff() {
+	assert_eq!(diff_lines("a\nb\nc", "a\nc\nd"), "  a\n- b\n  c\n+ d\n");
+}
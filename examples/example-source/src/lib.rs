@@ -1,14 +1,15 @@
 #![no_std]
 use buny::{
 	alloc::{vec, String, Vec},
+	helpers::string::ruby,
 	imports::{defaults::defaults_get, net::Request},
 	prelude::*,
 	AlternateCoverProvider, BaseUrlProvider, Chapter, CheckFilter, ContentBlock, ContentRating,
 	DeepLinkHandler, DeepLinkResult, DynamicFilters, DynamicListings, DynamicSettings, Filter,
-	FilterValue, Home, HomeComponent, HomeLayout, Listing, ListingProvider, MigrationHandler,
+	FilterValue, Home, HomeComponent, HomeLayout, Link, Listing, ListingProvider, MigrationHandler,
 	MultiSelectFilter, NotificationHandler, Novel, NovelPageResult, NovelStatus, NovelWithChapter,
-	RangeFilter, Result, SelectFilter, Setting, SortFilter, Source, TextFilter, ToggleSetting,
-	UpdateStrategy,
+	ParagraphStyle, RangeFilter, Result, SelectFilter, Setting, SortFilter, Source, TextFilter,
+	ToggleSetting, TranslationStatus, UpdateStrategy,
 };
 
 const PAGE_SIZE: i32 = 20;
@@ -61,6 +62,7 @@ impl Source for ExampleSource {
 		mut novel: Novel,
 		needs_details: bool,
 		needs_chapters: bool,
+		_page: i32,
 	) -> Result<Novel> {
 		if needs_details {
 			novel.authors = Some(vec![String::from("Author")]);
@@ -72,9 +74,13 @@ impl Source for ExampleSource {
 		}
 		if needs_chapters {
 			novel.chapters = Some(vec![
+				// a paid chapter that becomes free after early access ends
 				Chapter {
 					key: String::from("8"),
 					chapter_number: Some(8.0),
+					locked: true,
+					unlock_cost: Some(5),
+					free_at: Some(1692923325),
 					..Default::default()
 				},
 				Chapter {
@@ -131,6 +137,11 @@ impl Source for ExampleSource {
 			ContentBlock::paragraph("This is some more text.", None),
 			ContentBlock::Divider,
 			ContentBlock::block_quote("This is a quote."),
+			// paragraphs can be styled, and can contain ruby annotations
+			ContentBlock::styled_paragraph(
+				format!("{}です", ruby("漢字", "かんじ")),
+				ParagraphStyle::centered(),
+			),
 		])
 	}
 }
@@ -167,6 +178,15 @@ impl ListingProvider for ExampleSource {
 					update_strategy: UpdateStrategy::Never,
 					next_update_time: Some(1692404925),
 					chapters: None,
+					has_more_chapters: None,
+					// optional metadata used by trackers and filters in the app
+					alt_titles: Some(vec![String::from("Alternative Title")]),
+					rating: Some(4.5),
+					rating_count: Some(120),
+					word_count: Some(250000),
+					original_language: Some(String::from("ko")),
+					translation_status: TranslationStatus::Translated,
+					..Default::default()
 				},
 				Novel {
 					key: String::from("B"),
@@ -198,7 +218,7 @@ impl Home for ExampleSource {
 			date_uploaded: Some(1692318525),
 			..Default::default()
 		};
-		let _novel_chapters = entries
+		let novel_chapters = entries
 			.iter()
 			.map(|novel| NovelWithChapter {
 				novel: novel.clone(),
@@ -208,16 +228,36 @@ impl Home for ExampleSource {
 			.collect::<Vec<_>>();
 		let size: i32 = 200;
 		Ok(HomeLayout {
-			components: vec![HomeComponent {
-				title: Some("Normal Scroller".into()),
-				value: buny::HomeComponentValue::Scroller {
-					entries: entries.clone(),
-					auto_scroll_interval: None,
-					listing: None,
-					size: size,
+			components: vec![
+				HomeComponent {
+					title: Some("Normal Scroller".into()),
+					value: buny::HomeComponentValue::Scroller {
+						entries: entries.clone(),
+						auto_scroll_interval: None,
+						listing: None,
+						size,
+					},
+					..Default::default()
 				},
-				..Default::default()
-			}],
+				// a feed of the latest chapter updates, like the front page of most novel sites
+				HomeComponent {
+					title: Some("Latest Updates".into()),
+					value: buny::HomeComponentValue::ChapterFeed {
+						page_size: Some(3),
+						entries: novel_chapters.clone(),
+						listing: None,
+					},
+					..Default::default()
+				},
+				// chapters can also be linked to directly
+				HomeComponent {
+					title: Some("Links".into()),
+					value: buny::HomeComponentValue::Links(
+						novel_chapters.into_iter().map(Link::from).collect(),
+					),
+					..Default::default()
+				},
+			],
 		})
 	}
 }